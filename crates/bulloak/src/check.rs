@@ -3,23 +3,75 @@
 //! This command performs checks on the relationship between a bulloak tree and
 //! a Solidity file.
 
-use std::{fs, path::PathBuf};
+use std::{
+    collections::HashSet,
+    fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
 
 use bulloak_foundry::{
     check::{
         context::Context,
+        location::Location,
         rules::{self, Checker},
-        violation::fix_order,
+        utils::relative_to_cwd,
+        violation::{fix_order, remove_function},
     },
+    config::Config,
+    constants::DEFAULT_SOL_VERSION,
     sol::find_contract,
     violation::{Violation, ViolationKind},
 };
 use bulloak_syntax::utils::pluralize;
 use clap::Parser;
 use owo_colors::OwoColorize;
+use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
 
-use crate::cli::Cli;
+use crate::{cli::Cli, scaffold::parse_keyword_alias};
+
+/// A violation reported by `--format json`.
+#[derive(Debug, Serialize)]
+struct ViolationReport<'a> {
+    /// The stable name of the kind of violation, e.g. `extra_function`.
+    #[serde(rename = "type")]
+    ty: &'static str,
+    /// The stable code of the kind of violation, e.g. `B507`.
+    code: &'static str,
+    /// A human-readable description of the violation.
+    message: String,
+    /// The tree file the violation was found for.
+    tree: &'a Path,
+    /// The file the violation was found in, usually the Solidity file.
+    file: String,
+    /// The line the violation was found at, if it has one.
+    line: Option<usize>,
+    /// The column the violation was found at, if it has one.
+    column: Option<usize>,
+    /// Whether `bulloak check --fix` can fix the violation.
+    fixable: bool,
+}
+
+impl<'a> ViolationReport<'a> {
+    /// Describes `violation`, found for the tree file `tree`.
+    fn new(tree: &'a Path, violation: &Violation) -> Self {
+        let (line, column) = match violation.location {
+            Location::Code(_, line, column) => (Some(line), Some(column)),
+            Location::File(_) => (None, None),
+        };
+        Self {
+            ty: violation.kind.name(),
+            code: violation.kind.code(),
+            message: violation.kind.to_string(),
+            tree,
+            file: violation.location.file(),
+            line,
+            column,
+            fixable: violation.is_fixable(),
+        }
+    }
+}
 
 /// Check that the tests match the spec.
 #[doc(hidden)]
@@ -32,13 +84,149 @@ pub struct Check {
     /// Whether to fix any issues found.
     #[arg(long, group = "fix-violations", default_value_t = false)]
     pub fix: bool,
+    /// A Solidity file to check every tree against, instead of the one
+    /// next to each tree.
+    ///
+    /// Each tree only has to match a subset of the functions in the file, so
+    /// that many small trees can specify one big test contract.
+    #[arg(long, value_name = "PATH", conflicts_with = "fix")]
+    pub sol: Option<PathBuf>,
+    /// Two Solidity files to check every tree against, reporting the
+    /// violations each one has that the other doesn't.
+    ///
+    /// Useful to make sure a refactored test file conforms to the tree at
+    /// least as well as the original one.
+    #[arg(
+        long,
+        num_args = 2,
+        value_names = ["OLD", "NEW"],
+        conflicts_with_all = ["fix", "sol"]
+    )]
+    pub diff_sol: Vec<PathBuf>,
     /// When `--fix` is passed, use `--stdout` to direct output
     /// to standard output instead of writing to files.
     #[arg(long, requires = "fix-violations", default_value_t = false)]
     pub stdout: bool,
+    /// When `--fix` is passed, copy every Solidity file to `<file>.bak`
+    /// before overwriting it.
+    #[arg(
+        long,
+        requires = "fix-violations",
+        conflicts_with = "stdout",
+        default_value_t = false
+    )]
+    pub backup: bool,
     /// Whether to emit modifiers.
     #[arg(short = 'm', long, default_value_t = false)]
     pub skip_modifiers: bool,
+    /// Whether to report test functions in the Solidity file that don't
+    /// correspond to any branch in the tree.
+    #[arg(long, default_value_t = false)]
+    pub strict_extras: bool,
+    /// When `--fix` is passed, offer to remove the test functions in the
+    /// Solidity file that don't correspond to any branch in the tree.
+    ///
+    /// Each removal has to be confirmed, unless `--yes` is passed.
+    #[arg(long, requires = "fix-violations", default_value_t = false)]
+    pub prune: bool,
+    /// Whether to remove stale test functions without asking for
+    /// confirmation.
+    #[arg(long, requires = "prune", default_value_t = false)]
+    pub yes: bool,
+    /// Whether to only check that every function generated from the spec is
+    /// present, skipping the order and comment checks.
+    #[arg(long = "check-names-only", default_value_t = false)]
+    pub names_only: bool,
+    /// Whether to print paths relative to the current working directory.
+    #[arg(long, default_value_t = false)]
+    pub relative_paths: bool,
+    /// Whether to exit successfully even if some checks failed, e.g. to
+    /// adopt `bulloak check` gradually.
+    #[arg(long, default_value_t = false)]
+    pub exit_zero: bool,
+    /// Whether to stop at the first tree with violations instead of
+    /// checking every tree, for quick feedback in large repos.
+    #[arg(long, conflicts_with = "fix", default_value_t = false)]
+    pub fail_fast: bool,
+    /// Whether to sort modifier definitions alphabetically when a tree file
+    /// has multiple roots.
+    #[arg(long, default_value_t = false)]
+    pub sort_modifiers: bool,
+    /// Whether revert tests are expected after every other test.
+    #[arg(long, default_value_t = false)]
+    pub reverts_last: bool,
+    /// Whether the tests under `given` and `when` conditions are expected in
+    /// two sections, as scaffolded with `--group-by-keyword`.
+    #[arg(long, default_value_t = false)]
+    pub group_by_keyword: bool,
+    /// Whether root names are paths relative to the directory of the tree,
+    /// as scaffolded with `--root-as-path`, e.g. the tests of `token/Foo_Test`
+    /// are expected in `token/Foo_Test.t.sol`.
+    #[arg(long, conflicts_with = "sol", default_value_t = false)]
+    pub root_as_path: bool,
+    /// Whether to error out instead of warning when two roots of a tree
+    /// file have the same name.
+    #[arg(long, default_value_t = false)]
+    pub deny_duplicate_roots: bool,
+    /// The prefix of the tests marked with `[fuzz]`.
+    #[arg(long, value_name = "PREFIX", default_value = "testFuzz_")]
+    pub fuzz_prefix: String,
+    /// Custom words to recognize as keywords, given as `WORD=KEYWORD`.
+    ///
+    /// For example, `--keyword-aliases if=when,scenario=given` reads
+    /// `if the caller is the owner` as `when the caller is the owner`.
+    #[arg(
+        long,
+        value_name = "WORD=KEYWORD",
+        value_delimiter = ',',
+        value_parser = parse_keyword_alias
+    )]
+    pub keyword_aliases: Vec<String>,
+    /// Whether to check that the pragma directive of each Solidity file
+    /// matches the Solidity version passed to `-s`.
+    #[arg(long = "check-pragma", default_value_t = false)]
+    pub pragma: bool,
+    /// Whether to check that the contract of each tree is named like its
+    /// Solidity file, e.g. `contract Foo` in `Foo.t.sol`.
+    #[arg(long = "check-file-name", default_value_t = false)]
+    pub file_name: bool,
+    /// Whether to report the modifiers named like conditions, e.g.
+    /// `whenTheCallerIsTheOwner`, that no function applies.
+    #[arg(long = "check-unused-modifiers", default_value_t = false)]
+    pub unused_modifiers: bool,
+    /// Whether to report the action comments of a test that are out of
+    /// order with respect to the actions of the tree.
+    #[arg(long = "check-comment-order", default_value_t = false)]
+    pub comment_order: bool,
+    /// Whether to report the modifiers of a test that are applied out of
+    /// order with respect to the conditions of the tree.
+    #[arg(long = "check-modifier-order", default_value_t = false)]
+    pub modifier_order: bool,
+    /// How to report the violations found.
+    ///
+    /// `text` prints a human-readable report. `json` prints a JSON array
+    /// with an object per violation instead, e.g. to annotate pull requests
+    /// in CI.
+    #[arg(
+        long,
+        default_value = "text",
+        value_parser = ["text", "json"],
+        conflicts_with_all = ["fix", "diff_sol"]
+    )]
+    pub format: String,
+    /// The Solidity version the pragma directives should declare.
+    #[arg(short = 's', long, default_value = DEFAULT_SOL_VERSION)]
+    pub solidity_version: String,
+    /// How the Solidity version is rendered in the pragma directive.
+    ///
+    /// `exact` pins it with `=`, `caret` prefixes it with `^` and
+    /// `verbatim` uses it as provided.
+    #[arg(
+        long,
+        default_value = "verbatim",
+        value_parser = ["verbatim", "exact", "caret"]
+    )]
+    pub pragma_style: String,
 }
 
 impl Default for Check {
@@ -51,102 +239,383 @@ impl Check {
     /// Entrypoint for `bulloak check`.
     ///
     /// Note that we don't deal with `solang_parser` errors at all.
+    ///
+    /// Files are parsed and checked in parallel, but violations are reported
+    /// in the order the files were passed in.
     pub(crate) fn run(&self, cfg: &Cli) {
-        let mut violations = Vec::new();
+        let mut violations: Vec<(PathBuf, Violation)> = Vec::new();
+        let config = cfg.into();
+        if let [old, new] = self.diff_sol.as_slice() {
+            self.diff(old, new, &config);
+            return;
+        }
+        if self.fail_fast {
+            self.check_until_failure(&config);
+            return;
+        }
+
+        let results: Vec<_> = self
+            .files
+            .par_iter()
+            .map(|tree_path| {
+                Context::new(tree_path.clone(), &config).map_err(Box::new)
+            })
+            .collect();
         let ctxs: Vec<Context> = self
             .files
             .iter()
-            .filter_map(|tree_path| {
-                Context::new(tree_path.clone(), &cfg.into())
-                    .map_err(|violation| violations.push(violation))
+            .zip(results)
+            .filter_map(|(tree_path, result)| {
+                result
+                    .map_err(|violation| {
+                        violations.push((tree_path.clone(), *violation));
+                    })
                     .ok()
             })
             .collect();
 
         if self.fix {
-            let mut fixed_count = 0;
-            for mut ctx in ctxs {
-                let violations = rules::StructuralMatcher::check(&ctx);
-                let fixable_count =
-                    violations.iter().filter(|v| v.is_fixable()).count();
-
-                // Process violations that affect function order first.
-                let violations = violations.into_iter().filter(|v| {
-                    !matches!(
+            self.fix(ctxs);
+        } else {
+            let checked: Vec<_> =
+                ctxs.par_iter().map(rules::StructuralMatcher::check).collect();
+            violations.extend(ctxs.iter().zip(checked).flat_map(
+                |(ctx, checked)| {
+                    checked.into_iter().map(|v| (ctx.tree.clone(), v))
+                },
+            ));
+
+            self.report(violations);
+        }
+    }
+
+    /// Fixes the violations of every context in `ctxs` and reports the
+    /// ones that were fixed and the ones that weren't.
+    fn fix(&self, ctxs: Vec<Context>) {
+        let mut fixed_count = 0;
+        let mut skipped_count = 0;
+        for mut ctx in ctxs {
+            let violations = rules::StructuralMatcher::check(&ctx);
+            let (fixed, skipped): (Vec<_>, Vec<_>) =
+                violations.iter().partition(|v| v.is_fixable());
+            let mut fixed: Vec<String> =
+                fixed.iter().map(|v| v.kind.to_string()).collect();
+            let mut skipped: Vec<String> =
+                skipped.iter().map(|v| v.kind.to_string()).collect();
+            let extras: Vec<(String, String)> = violations
+                .iter()
+                .filter_map(|v| match &v.kind {
+                    ViolationKind::ExtraFunction(name) => {
+                        Some((name.clone(), v.kind.to_string()))
+                    }
+                    _ => None,
+                })
+                .collect();
+
+            // Process violations that affect function order first.
+            let violations = violations.into_iter().filter(|v| {
+                !matches!(v.kind, ViolationKind::FunctionOrderMismatch(_, _, _))
+            });
+            for violation in violations {
+                ctx = violation.kind.fix(ctx);
+            }
+            if self.prune {
+                ctx = self.prune(ctx, &extras, &mut fixed, &mut skipped);
+            }
+
+            // Second pass fixing order violations.
+            let violations = rules::StructuralMatcher::check(&ctx);
+            let violations: Vec<Violation> = violations
+                .into_iter()
+                .filter(|v| {
+                    matches!(
                         v.kind,
                         ViolationKind::FunctionOrderMismatch(_, _, _)
                     )
-                });
-                for violation in violations {
-                    ctx = violation.kind.fix(ctx);
+                })
+                .collect();
+            if !violations.is_empty() {
+                if let Some(contract_sol) = find_contract(&ctx.pt) {
+                    if let Some(contract_hir) = ctx.hir.clone().find_contract()
+                    {
+                        ctx = fix_order(
+                            &violations,
+                            &contract_sol,
+                            contract_hir,
+                            ctx,
+                        );
+                    }
                 }
+            }
+
+            let sol = ctx.sol.clone();
+            let formatted =
+                ctx.fmt().expect("should format the emitted solidity code");
+            self.write(&formatted, sol.clone());
+            self.report_fixes(&sol, &fixed, &skipped);
 
-                // Second pass fixing order violations.
-                let violations = rules::StructuralMatcher::check(&ctx);
-                let violations: Vec<Violation> = violations
+            fixed_count += fixed.len();
+            skipped_count += skipped.len();
+        }
+
+        let issue_literal = pluralize(fixed_count, "issue", "issues");
+        println!(
+            "\n{}: {} {} fixed.",
+            "success".bold().green(),
+            fixed_count,
+            issue_literal
+        );
+        if skipped_count > 0 {
+            let issue_literal = pluralize(skipped_count, "issue", "issues");
+            println!(
+                "{}: {skipped_count} {issue_literal} can't be fixed automatically.",
+                "warn".bold().yellow(),
+            );
+        }
+    }
+
+    /// Checks the trees one at a time, in the order they were passed in, and
+    /// reports the violations of the first tree that has any.
+    ///
+    /// The rest of the trees are left unchecked.
+    fn check_until_failure(&self, config: &Config) {
+        for (idx, tree_path) in self.files.iter().enumerate() {
+            let violations = match Context::new(tree_path.clone(), config) {
+                Ok(ctx) => rules::StructuralMatcher::check(&ctx),
+                Err(violation) => vec![violation],
+            };
+            if violations.is_empty() {
+                continue;
+            }
+
+            let unchecked = self.files.len() - idx - 1;
+            if unchecked > 0 {
+                let file_literal = pluralize(unchecked, "file", "files");
+                eprintln!(
+                    "{}: Stopped at the first failing file, {unchecked} {file_literal} left unchecked.",
+                    "warn".bold().yellow(),
+                );
+            }
+            self.report(
+                violations
                     .into_iter()
-                    .filter(|v| {
-                        matches!(
-                            v.kind,
-                            ViolationKind::FunctionOrderMismatch(_, _, _)
-                        )
-                    })
-                    .collect();
+                    .map(|v| (tree_path.clone(), v))
+                    .collect(),
+            );
+            return;
+        }
+
+        self.report(vec![]);
+    }
+
+    /// Reports `violations`, each paired with the tree it was found for, in
+    /// the format passed to `--format`.
+    ///
+    /// Exits with an error if there are any violations, unless `--exit-zero`
+    /// was passed.
+    fn report(&self, violations: Vec<(PathBuf, Violation)>) {
+        let violations: Vec<_> = if self.relative_paths {
+            violations
+                .into_iter()
+                .map(|(tree, v)| (relative_to_cwd(&tree), v.relative()))
+                .collect()
+        } else {
+            violations
+        };
+        if self.format != "json" {
+            let violations: Vec<Violation> =
+                violations.into_iter().map(|(_, v)| v).collect();
+            exit(&violations, self.exit_zero);
+            return;
+        }
+
+        let reports: Vec<ViolationReport> = violations
+            .iter()
+            .map(|(tree, violation)| ViolationReport::new(tree, violation))
+            .collect();
+        match serde_json::to_string_pretty(&reports) {
+            Ok(json) => println!("{json}"),
+            Err(err) => eprintln!("{}: {err}", "error".red()),
+        }
+        if !violations.is_empty() && !self.exit_zero {
+            std::process::exit(1);
+        }
+    }
+
+    /// Checks every tree against two Solidity files and reports how their
+    /// violations differ.
+    ///
+    /// Exits with an error if `new` has violations that `old` doesn't.
+    fn diff(&self, old: &Path, new: &Path, config: &Config) {
+        let check = |tree: &PathBuf, sol: &Path| {
+            let cfg = Config {
+                shared_sol: Some(sol.to_path_buf()),
+                ..config.clone()
+            };
+            match Context::new(tree.clone(), &cfg) {
+                Ok(mut ctx) => {
+                    // The whole contract is checked against the tree, unlike
+                    // with a shared Solidity file.
+                    ctx.cfg.shared_sol = None;
+                    rules::StructuralMatcher::check(&ctx)
+                }
+                Err(violation) => vec![violation],
+            }
+            .into_iter()
+            .map(|v| if self.relative_paths { v.relative() } else { v })
+            .collect::<Vec<_>>()
+        };
+
+        let mut introduced_count = 0;
+        for tree in &self.files {
+            let old_violations = check(tree, old);
+            let new_violations = check(tree, new);
+            let messages = |violations: &[Violation]| -> HashSet<String> {
+                violations.iter().map(|v| v.kind.to_string()).collect()
+            };
+            let old_messages = messages(&old_violations);
+            let new_messages = messages(&new_violations);
+            let fixed: Vec<_> = old_violations
+                .iter()
+                .filter(|v| !new_messages.contains(&v.kind.to_string()))
+                .collect();
+            let introduced: Vec<_> = new_violations
+                .iter()
+                .filter(|v| !old_messages.contains(&v.kind.to_string()))
+                .collect();
+
+            println!("{} {}", "-->".blue(), tree.display());
+            for (sol, violations) in
+                [(old, &old_violations), (new, &new_violations)]
+            {
+                let issue_literal =
+                    pluralize(violations.len(), "issue", "issues");
+                println!(
+                    "    {}: {} {issue_literal}",
+                    sol.display(),
+                    violations.len()
+                );
+            }
+            for (title, violations) in [
+                (format!("Only in {}:", old.display()), &fixed),
+                (format!("Only in {}:", new.display()), &introduced),
+            ] {
                 if !violations.is_empty() {
-                    if let Some(contract_sol) = find_contract(&ctx.pt) {
-                        if let Some(contract_hir) =
-                            ctx.hir.clone().find_contract()
-                        {
-                            ctx = fix_order(
-                                &violations,
-                                &contract_sol,
-                                contract_hir,
-                                ctx,
-                            );
-                        }
+                    println!("\n{}", title.bold());
+                    for violation in violations {
+                        println!("{violation}");
                     }
                 }
+            }
 
-                let sol = ctx.sol.clone();
-                let formatted =
-                    ctx.fmt().expect("should format the emitted solidity code");
-                self.write(&formatted, sol);
+            introduced_count += introduced.len();
+        }
 
-                fixed_count += fixable_count;
+        if introduced_count > 0 {
+            let issue_literal = pluralize(introduced_count, "issue", "issues");
+            eprintln!(
+                "{}: {} introduced {introduced_count} {issue_literal}",
+                "warn".bold().yellow(),
+                new.display()
+            );
+            if !self.exit_zero {
+                std::process::exit(1);
             }
+        }
+    }
 
-            let issue_literal = pluralize(fixed_count, "issue", "issues");
-            println!(
-                "\n{}: {} {} fixed.",
-                "success".bold().green(),
-                fixed_count,
-                issue_literal
-            );
-        } else {
-            for ctx in ctxs {
-                violations.append(&mut rules::StructuralMatcher::check(&ctx));
+    /// Removes the functions named in `extras` from the Solidity file of
+    /// `ctx`, asking for confirmation first unless `--yes` was passed.
+    ///
+    /// Each extra is a function name paired with its violation message.
+    ///
+    /// Removed functions move from `skipped` to `fixed`.
+    fn prune(
+        &self,
+        mut ctx: Context,
+        extras: &[(String, String)],
+        fixed: &mut Vec<String>,
+        skipped: &mut Vec<String>,
+    ) -> Context {
+        for (name, violation) in extras {
+            if !self.yes && !confirm(name, &ctx.sol) {
+                continue;
             }
 
-            exit(&violations);
+            ctx = remove_function(name, ctx);
+            skipped.retain(|v| v != violation);
+            fixed.push(violation.clone());
+        }
+
+        ctx
+    }
+
+    /// Reports the violations of the Solidity file `sol` that were fixed and
+    /// the ones that were skipped, since they can't be fixed automatically.
+    ///
+    /// With `--stdout`, the report follows the fixed code of `sol`, so the
+    /// path is left out.
+    fn report_fixes(&self, sol: &Path, fixed: &[String], skipped: &[String]) {
+        if fixed.is_empty() && skipped.is_empty() {
+            return;
+        }
+
+        if !self.stdout {
+            println!("{} {}", "-->".blue(), sol.display());
+        }
+        for violation in fixed {
+            println!("    {}: {violation}", "fixed".green());
+        }
+        for violation in skipped {
+            println!("    {}: {violation}", "skipped".yellow());
         }
     }
 
     /// Handles writing the output of the `check` command.
     ///
     /// If the `--stdout` flag was passed, then the output is printed to
-    /// stdout, else it is written to the corresponding file.
+    /// stdout, else it is written to the corresponding file. With `--backup`,
+    /// the file is copied to `<file>.bak` first, and left untouched if the
+    /// copy fails.
     fn write(&self, output: &str, sol: PathBuf) {
         if self.stdout {
             println!("{} {}", "-->".blue(), sol.to_string_lossy());
             println!("{}", output.trim());
             println!("{}", "<--".blue());
-        } else if let Err(e) = fs::write(sol, output) {
+            return;
+        }
+
+        if self.backup && sol.exists() {
+            let mut backup = sol.clone().into_os_string();
+            backup.push(".bak");
+            if let Err(e) = fs::copy(&sol, backup) {
+                eprintln!("{}: {e}", "warn".yellow());
+                return;
+            }
+        }
+        if let Err(e) = fs::write(sol, output) {
             eprintln!("{}: {e}", "warn".yellow());
         }
     }
 }
 
-fn exit(violations: &[Violation]) {
+/// Asks whether to remove the function `name` from `sol`.
+///
+/// Anything but `y` or `yes`, including a closed stdin, is a no.
+fn confirm(name: &str, sol: &Path) -> bool {
+    eprint!("Remove function \"{name}\" from {}? [y/N] ", sol.display());
+    let _ = io::stderr().flush();
+
+    let mut answer = String::new();
+    if io::stdin().read_line(&mut answer).is_err() {
+        return false;
+    }
+    matches!(answer.trim().to_lowercase().as_str(), "y" | "yes")
+}
+
+/// Reports `violations` and exits with an error if there are any, unless
+/// `exit_zero` is set.
+fn exit(violations: &[Violation], exit_zero: bool) {
     if violations.is_empty() {
         println!(
             "{}",
@@ -175,6 +644,8 @@ fn exit(violations: &[Violation]) {
             eprintln!();
         }
 
-        std::process::exit(1);
+        if !exit_zero {
+            std::process::exit(1);
+        }
     }
 }
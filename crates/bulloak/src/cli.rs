@@ -1,12 +1,27 @@
 //! `bulloak`'s CLI config.
-use clap::{Parser, Subcommand};
-use figment::{providers::Serialized, Figment};
+use std::{env, path::PathBuf};
+
+use bulloak_foundry::config::KeywordAlias;
+use bulloak_syntax::KeywordAliases;
+use clap::{CommandFactory, FromArgMatches, Parser, Subcommand};
 use serde::{Deserialize, Serialize};
 
+use crate::config_file::{self, CONFIG_FILE, DEFAULT_PROFILE};
+
 /// `bulloak`'s configuration.
 #[derive(Parser, Debug, Clone, Default, Serialize, Deserialize)]
 #[command(author, version, about, long_about = None)] // Read from `Cargo.toml`
 pub struct Cli {
+    /// The profile of `bulloak.toml` to read the options from.
+    ///
+    /// Defaults to the `default` profile.
+    #[arg(long, global = true, value_name = "NAME")]
+    pub profile: Option<String>,
+    /// Whether to print the configuration in effect after applying
+    /// `bulloak.toml` and the command line flags, instead of running the
+    /// command.
+    #[arg(long, global = true, default_value_t = false)]
+    pub print_config: bool,
     /// `bulloak`'s commands.
     #[clap(subcommand)]
     pub command: Commands,
@@ -17,15 +32,30 @@ pub struct Cli {
 pub enum Commands {
     /// `bulloak scaffold`.
     #[command(name = "scaffold")]
-    Scaffold(crate::scaffold::Scaffold),
+    Scaffold(Box<crate::scaffold::Scaffold>),
     /// `bulloak check`.
     #[command(name = "check")]
     Check(crate::check::Check),
+    /// `bulloak init`.
+    #[command(name = "init")]
+    Init(crate::init::Init),
+    /// `bulloak modifiers`.
+    #[command(name = "modifiers")]
+    Modifiers(crate::modifiers::Modifiers),
+    /// `bulloak order`.
+    #[command(name = "order")]
+    Order(crate::order::Order),
+    /// `bulloak corpus`.
+    #[command(name = "corpus")]
+    Corpus(crate::corpus::Corpus),
+    /// `bulloak tree`.
+    #[command(name = "tree")]
+    Tree(crate::tree::Tree),
 }
 
 impl Default for Commands {
     fn default() -> Self {
-        Self::Scaffold(Default::default())
+        Self::Scaffold(Box::default())
     }
 }
 
@@ -35,27 +65,161 @@ impl From<&Cli> for bulloak_foundry::config::Config {
             Commands::Scaffold(cmd) => Self {
                 files: cmd.files.clone(),
                 solidity_version: cmd.solidity_version.clone(),
+                pragma_style: cmd.pragma_style.parse().unwrap_or_default(),
+                indent: cmd.indent,
                 emit_vm_skip: cmd.with_vm_skip,
                 skip_modifiers: cmd.skip_modifiers,
+                max_name_length: cmd.max_name_length,
+                max_title_words: cmd.max_title_words,
+                check_file_name: cmd.check_file_name,
+                labels: cmd.labels.clone(),
+                descriptions_as_todos: cmd.descriptions_as_todos,
+                imports: cmd.imports.clone(),
+                skip_pragma: cmd.no_pragma,
+                experimental_pragmas: cmd.experimental_pragmas.clone(),
+                solhint_disable: cmd.solhint_disable.clone(),
+                // Invalid templates are reported by `Scaffold::validate`
+                // before the config is built.
+                body_templates: cmd
+                    .body_templates
+                    .iter()
+                    .filter_map(|template| template.parse().ok())
+                    .collect(),
+                default_body_statement: cmd.default_body_statement.clone(),
+                test_base: cmd
+                    .test_base
+                    .as_deref()
+                    .and_then(|base| base.parse().ok())
+                    .unwrap_or_default(),
+                sort_modifiers: cmd.sort_modifiers,
+                reverts_last: cmd.reverts_last,
+                group_by_keyword: cmd.group_by_keyword,
+                deny_duplicate_roots: cmd.deny_duplicate_roots,
+                fuzz_prefix: cmd.fuzz_prefix.clone(),
+                keyword_aliases: keyword_aliases(&cmd.keyword_aliases),
+                emit_setup: cmd.emit_setup,
+                modifier_comments: cmd.modifier_comments,
+                condition_comments: cmd.condition_comments,
+                comments_above: cmd.comments_above,
+                mark_generated: cmd.mark_generated,
+                emit_receive: cmd.emit_receive,
+                aaa: cmd.aaa,
+                aaa_from_descriptions: cmd.aaa_from_descriptions,
+                smart_descriptions: cmd.smart_descriptions,
+                dedup_actions: cmd.dedup_actions,
+                forgefmt_disable: cmd.forgefmt_disable,
+                root_as_path: cmd.root_as_path,
+                emit_assertions: cmd.assertions,
+                emit_expect_revert: cmd.expect_revert,
+                emit_no_revert: cmd.no_revert,
+                lint_it_keyword: cmd.lint_it_keyword,
+                lint_duplicate_actions: cmd.lint_duplicate_actions,
                 ..Self::default()
             },
             Commands::Check(cmd) => Self {
                 files: cmd.files.clone(),
                 skip_modifiers: cmd.skip_modifiers,
+                strict_extras: cmd.strict_extras || cmd.prune,
+                check_names_only: cmd.names_only,
+                sort_modifiers: cmd.sort_modifiers,
+                reverts_last: cmd.reverts_last,
+                group_by_keyword: cmd.group_by_keyword,
+                root_as_path: cmd.root_as_path,
+                deny_duplicate_roots: cmd.deny_duplicate_roots,
+                fuzz_prefix: cmd.fuzz_prefix.clone(),
+                keyword_aliases: keyword_aliases(&cmd.keyword_aliases),
+                check_pragma: cmd.pragma,
+                check_file_name: cmd.file_name,
+                check_unused_modifiers: cmd.unused_modifiers,
+                check_comment_order: cmd.comment_order,
+                check_modifier_order: cmd.modifier_order,
+                shared_sol: cmd.sol.clone(),
+                solidity_version: cmd.solidity_version.clone(),
+                pragma_style: cmd.pragma_style.parse().unwrap_or_default(),
                 ..Self::default()
             },
+            Commands::Order(cmd) => Self {
+                files: cmd.files.clone(),
+                sort_modifiers: cmd.sort_modifiers,
+                reverts_last: cmd.reverts_last,
+                ..Self::default()
+            },
+            Commands::Init(_)
+            | Commands::Modifiers(_)
+            | Commands::Corpus(_)
+            | Commands::Tree(_) => Self::default(),
         }
     }
 }
 
+/// Collects the valid `WORD=KEYWORD` pairs in `aliases` into a map.
+pub(crate) fn keyword_aliases(aliases: &[String]) -> KeywordAliases {
+    aliases
+        .iter()
+        .filter_map(|alias| alias.parse::<KeywordAlias>().ok())
+        .map(|alias| (alias.word, alias.keyword))
+        .collect()
+}
+
 /// Main entrypoint of `bulloak`'s execution.
 pub(crate) fn run() -> anyhow::Result<()> {
-    let config: Cli =
-        Figment::new().merge(Serialized::defaults(Cli::parse())).extract()?;
+    let matches = Cli::command().get_matches();
+    let mut config = Cli::from_arg_matches(&matches)?;
+
+    // Options in config files are named like the long flags, so their names
+    // come from the clap definition of each command.
+    let command = Cli::command();
+    let names_of = |name: &str| {
+        command
+            .find_subcommand(name)
+            .map(config_file::option_names)
+            .unwrap_or_default()
+    };
+    let (scaffold_names, check_names) =
+        (names_of("scaffold"), names_of("check"));
+    let known: Vec<String> =
+        scaffold_names.keys().chain(check_names.keys()).cloned().collect();
+    let profile = config.profile.as_deref().unwrap_or(DEFAULT_PROFILE);
+    let path = config_file::find_config_file(&env::current_dir()?)
+        .unwrap_or_else(|| PathBuf::from(CONFIG_FILE));
+    let options = config_file::load_profile(&path, profile, &known)?;
+
+    if let Some((_, matches)) = matches.subcommand() {
+        config.command = match &config.command {
+            Commands::Scaffold(cmd) => {
+                let cmd: Box<crate::scaffold::Scaffold> = config_file::apply(
+                    cmd,
+                    &scaffold_names,
+                    &options,
+                    matches,
+                )?;
+                cmd.validate()?;
+                Commands::Scaffold(cmd)
+            }
+            Commands::Check(cmd) => Commands::Check(config_file::apply(
+                cmd,
+                &check_names,
+                &options,
+                matches,
+            )?),
+            command => command.clone(),
+        };
+    }
+
+    if config.print_config {
+        let effective: bulloak_foundry::config::Config = (&config).into();
+        println!("{effective:#?}");
+        return Ok(());
+    }
 
     match &config.command {
         Commands::Scaffold(command) => command.run(&config),
         Commands::Check(command) => command.run(&config),
+        Commands::Init(command) => command.run(),
+        Commands::Modifiers(command) => command.run(),
+        Commands::Order(command) => command.run(&config),
+        Commands::Corpus(command) => command.run(),
+        Commands::Tree(command) => command.run(),
     };
 
     Ok(())
@@ -0,0 +1,180 @@
+//! Defines how `bulloak.toml` files are loaded.
+//!
+//! A `bulloak.toml` file holds named profiles. Each profile is a table of
+//! options named like the long flags of the commands, without the leading
+//! dashes, e.g.:
+//!
+//! ```toml
+//! [profile.default]
+//! solidity-version = "0.8.20"
+//!
+//! [profile.ci]
+//! vm-skip = true
+//! ```
+//!
+//! Flags that can be repeated, like `--import`, take a list of values.
+//!
+//! The options of the selected profile override the ones of the `default`
+//! profile, which override the built-in defaults. Flags passed in the command
+//! line override them all.
+//!
+//! The file is looked up in the current working directory and then in each
+//! of its parents, so that `bulloak` can run from anywhere inside a project.
+
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{anyhow, bail};
+use clap::{parser::ValueSource, ArgMatches, Command};
+use figment::{
+    providers::{Format, Serialized, Toml},
+    value::{Dict, Value},
+    Figment,
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+/// The name of the config file.
+pub(crate) const CONFIG_FILE: &str = "bulloak.toml";
+/// The profile every other profile builds on.
+pub(crate) const DEFAULT_PROFILE: &str = "default";
+/// How options from different sources override each other, appended to the
+/// errors about config files.
+const PRECEDENCE: &str = "command line flags override the options of the \
+                          selected profile, which override the ones of the \
+                          `default` profile and the built-in defaults";
+
+/// The contents of a config file.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ConfigFile {
+    /// The options of every profile, keyed by the profile name.
+    #[serde(default)]
+    profile: BTreeMap<String, Dict>,
+}
+
+/// Finds the nearest config file, looking in `cwd` first and then in each of
+/// its parents.
+///
+/// The returned path is relative to `cwd`, e.g. `../bulloak.toml`, so that
+/// errors point to the file the way a user would refer to it.
+pub(crate) fn find_config_file(cwd: &Path) -> Option<PathBuf> {
+    let mut relative = PathBuf::new();
+    for dir in cwd.ancestors() {
+        if dir.join(CONFIG_FILE).is_file() {
+            return Some(relative.join(CONFIG_FILE));
+        }
+        relative.push("..");
+    }
+
+    None
+}
+
+/// Loads the options of `profile` from the config file at `path`, on top of
+/// the options of the default profile.
+///
+/// `known` holds the names of the options of every command.
+///
+/// # Errors
+///
+/// Returns an error if the file can't be parsed, if any profile has an
+/// option that isn't `known`, or if a profile other than the default one is
+/// selected but can't be found.
+pub(crate) fn load_profile(
+    path: &Path,
+    profile: &str,
+    known: &[String],
+) -> anyhow::Result<Dict> {
+    let file: ConfigFile = if path.exists() {
+        Figment::from(Toml::file(path)).extract()?
+    } else {
+        ConfigFile::default()
+    };
+
+    for (name, options) in &file.profile {
+        if let Some(option) = options.keys().find(|key| !known.contains(key)) {
+            bail!(
+                "unknown option `{option}` in profile `{name}` of {}\n\
+                 note: options are named after the long flags of the \
+                 commands, e.g. `vm-skip` for `--vm-skip`, and {PRECEDENCE}",
+                path.display()
+            );
+        }
+    }
+
+    let mut options =
+        file.profile.get(DEFAULT_PROFILE).cloned().unwrap_or_default();
+    if profile != DEFAULT_PROFILE {
+        let Some(overrides) = file.profile.get(profile) else {
+            bail!("profile `{profile}` not found in {}", path.display());
+        };
+        options.extend(overrides.clone());
+    }
+
+    Ok(options)
+}
+
+/// Returns the options of the clap `command`, keyed by their long flag, e.g.
+/// `vm-skip` for `--vm-skip`, along with the field each one sets.
+///
+/// Positional arguments have no long flag, so they can't be set in a config
+/// file.
+pub(crate) fn option_names(command: &Command) -> BTreeMap<String, String> {
+    command
+        .get_arguments()
+        .filter_map(|arg| {
+            Some((arg.get_long()?.to_owned(), arg.get_id().to_string()))
+        })
+        .collect()
+}
+
+/// Applies `options` to `command`, except for the options that were passed
+/// in the command line, as told by `matches`.
+///
+/// `names` maps the options to the fields of `command`, as returned by
+/// [`option_names`]. Options that `command` doesn't have are ignored, since
+/// they may belong to another command.
+///
+/// # Errors
+///
+/// Returns an error if an option has the wrong type.
+pub(crate) fn apply<T: Serialize + DeserializeOwned>(
+    command: &T,
+    names: &BTreeMap<String, String>,
+    options: &Dict,
+    matches: &ArgMatches,
+) -> anyhow::Result<T> {
+    let Value::Dict(_, args) = Value::serialize(command)? else {
+        return Ok(Figment::from(Serialized::defaults(command)).extract()?);
+    };
+
+    let is_explicit = |field: &str| {
+        matches.ids().any(|id| id.as_str() == field)
+            && matches.value_source(field) == Some(ValueSource::CommandLine)
+    };
+    let from_file: Dict = options
+        .iter()
+        .filter_map(|(name, value)| {
+            let field = names.get(name)?;
+            (args.contains_key(field) && !is_explicit(field))
+                .then(|| (field.clone(), value.clone()))
+        })
+        .collect();
+
+    Figment::from(Serialized::defaults(command))
+        .merge(Serialized::defaults(from_file))
+        .extract()
+        .map_err(|err| {
+            let field = err.path.join(".");
+            let name = names
+                .iter()
+                .find_map(|(name, f)| (*f == field).then_some(name.as_str()))
+                .unwrap_or(&field);
+            anyhow!(
+                "invalid value for option `{name}` in {CONFIG_FILE}: {}\n\
+                 note: {PRECEDENCE}",
+                err.kind
+            )
+        })
+}
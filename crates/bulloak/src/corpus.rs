@@ -0,0 +1,120 @@
+//! Defines the `bulloak corpus` command.
+//!
+//! This command parses every tree in a directory and reports the ones that
+//! fail to parse, which makes it easy to seed a fuzzing corpus with inputs
+//! that exercise the error paths of the parser.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use bulloak_syntax::diagnostic::diagnostics;
+use clap::Parser;
+use owo_colors::OwoColorize;
+use serde::{Deserialize, Serialize};
+
+/// Report the trees in a directory that fail to parse.
+#[doc(hidden)]
+#[derive(Debug, Parser, Clone, Serialize, Deserialize)]
+pub struct Corpus {
+    /// The directory to search for tree files, recursively.
+    pub dir: PathBuf,
+    /// The file to write the report to.
+    ///
+    /// Defaults to printing the report to stdout.
+    #[arg(long, short, value_name = "FILE")]
+    pub output: Option<PathBuf>,
+}
+
+/// A tree that failed to parse.
+#[derive(Debug, Serialize)]
+struct Failure {
+    /// The path of the tree file.
+    path: PathBuf,
+    /// The contents of the tree file.
+    text: String,
+    /// The error returned when parsing the tree.
+    error: String,
+    /// The diagnostics of the error.
+    diagnostics: Vec<FailureDiagnostic>,
+}
+
+/// A diagnostic of a tree that failed to parse.
+#[derive(Debug, Serialize)]
+struct FailureDiagnostic {
+    /// The stable code of the diagnostic, e.g. `B101`.
+    code: &'static str,
+    /// A human-readable description of the problem.
+    message: String,
+    /// The line where the problem starts.
+    line: usize,
+    /// The column where the problem starts.
+    column: usize,
+}
+
+impl Corpus {
+    /// Entrypoint for `bulloak corpus`.
+    pub(crate) fn run(&self) {
+        if let Err(err) = self.report() {
+            eprintln!("{}: {err}", "error".red());
+            std::process::exit(1);
+        }
+    }
+
+    /// Parses every tree under `self.dir` and writes the ones that fail to
+    /// the report.
+    fn report(&self) -> anyhow::Result<()> {
+        let mut trees = vec![];
+        find_trees(&self.dir, &mut trees)?;
+
+        let mut failures = vec![];
+        for path in trees {
+            let text = fs::read_to_string(&path)?;
+            if let Err(err) = bulloak_syntax::parse(&text) {
+                let diagnostics = diagnostics(&err)
+                    .into_iter()
+                    .map(|diagnostic| FailureDiagnostic {
+                        code: diagnostic.code,
+                        message: diagnostic.message,
+                        line: diagnostic.span.start.line,
+                        column: diagnostic.span.start.column,
+                    })
+                    .collect();
+                failures.push(Failure {
+                    path,
+                    text,
+                    error: err.to_string(),
+                    diagnostics,
+                });
+            }
+        }
+
+        let report = serde_json::to_string_pretty(&failures)?;
+        match &self.output {
+            Some(output) => fs::write(output, format!("{report}\n"))?,
+            None => println!("{report}"),
+        }
+
+        Ok(())
+    }
+}
+
+/// Collects the paths of the tree files under `dir` into `trees`, sorted so
+/// that reports are stable across runs.
+fn find_trees(dir: &Path, trees: &mut Vec<PathBuf>) -> anyhow::Result<()> {
+    let mut entries = fs::read_dir(dir)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<Result<Vec<_>, _>>()?;
+    entries.sort();
+
+    for path in entries {
+        if path.is_dir() {
+            find_trees(&path, trees)?;
+        } else if path.extension().is_some_and(|ext| ext == "tree") {
+            trees.push(path);
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,67 @@
+//! Defines the `bulloak init` command.
+//!
+//! This command writes a starter `.tree` file for new users to build on.
+
+use std::{fs, path::PathBuf};
+
+use clap::Parser;
+use owo_colors::OwoColorize;
+use serde::{Deserialize, Serialize};
+
+/// Write a starter `.tree` file.
+#[doc(hidden)]
+#[derive(Debug, Parser, Clone, Serialize, Deserialize)]
+pub struct Init {
+    /// The name of the contract under test.
+    ///
+    /// The tree is written to `<NAME>.tree`, so the name may be preceded by
+    /// the directory to write it to, e.g. `test/Vault`.
+    pub name: PathBuf,
+    /// Whether to overwrite the `.tree` file if it already exists.
+    #[arg(short, long, default_value_t = false)]
+    pub force: bool,
+}
+
+impl Init {
+    /// Entrypoint for `bulloak init`.
+    pub(crate) fn run(&self) {
+        let file = self.name.with_extension("tree");
+        if file.exists() && !self.force {
+            eprintln!(
+                "{}: Skipped emitting {:?}",
+                "warn".yellow(),
+                file.as_path().blue()
+            );
+            eprintln!(
+                "    {} The file already exists, pass `--force` to overwrite it",
+                "=".blue()
+            );
+            std::process::exit(1);
+        }
+
+        let contract_name = self
+            .name
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        if let Err(err) = fs::write(&file, starter_tree(&contract_name)) {
+            eprintln!("{}: {err}", "error".red());
+            std::process::exit(1);
+        }
+
+        println!("{}: Wrote {}", "success".bold().green(), file.display());
+    }
+}
+
+/// Returns the contents of a starter tree for `contract_name`.
+fn starter_tree(contract_name: &str) -> String {
+    format!(
+        "{contract_name}
+├── when the caller is not the owner
+│   └── it should revert.
+└── when the caller is the owner
+    ├── it should update the state.
+    └── it should emit an event.
+"
+    )
+}
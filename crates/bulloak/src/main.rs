@@ -3,7 +3,13 @@ use std::process;
 
 mod check;
 mod cli;
+mod config_file;
+mod corpus;
+mod init;
+mod modifiers;
+mod order;
 mod scaffold;
+mod tree;
 
 fn main() {
     if let Err(e) = crate::cli::run() {
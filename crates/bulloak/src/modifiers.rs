@@ -0,0 +1,76 @@
+//! Defines the `bulloak modifiers` command.
+//!
+//! This command prints the modifiers applied to every test of a tree, which
+//! helps to understand how modifiers stack in deeply nested trees.
+
+use std::{fs, path::PathBuf};
+
+use bulloak_foundry::{
+    config::Config,
+    hir::{self, Hir},
+};
+use clap::Parser;
+use serde::{Deserialize, Serialize};
+
+/// Print the modifiers applied to every test, in application order.
+#[doc(hidden)]
+#[derive(Debug, Parser, Clone, Serialize, Deserialize)]
+pub struct Modifiers {
+    /// The set of tree files to inspect.
+    pub files: Vec<PathBuf>,
+}
+
+impl Modifiers {
+    /// Entrypoint for `bulloak modifiers`.
+    pub(crate) fn run(&self) {
+        let mut failed = false;
+        for file in &self.files {
+            if let Err(err) = Modifiers::print_modifiers(file) {
+                eprintln!("{err}");
+                eprintln!("file: {}", file.display());
+                failed = true;
+            }
+        }
+
+        if failed {
+            std::process::exit(1);
+        }
+    }
+
+    /// Prints every test in `file` along with the modifiers applied to it,
+    /// from the outermost to the innermost.
+    ///
+    /// Each test is printed in its own line as
+    /// `<file>:<line> <test> <modifier> -> <modifier>`.
+    fn print_modifiers(file: &PathBuf) -> anyhow::Result<()> {
+        let text = fs::read_to_string(file)?;
+        let hir = hir::translate(&text, &Config::default())?;
+        let Some(contract) = hir.find_contract() else {
+            return Ok(());
+        };
+
+        let functions =
+            contract.children.iter().filter_map(|child| match child {
+                Hir::FunctionDefinition(function) if function.is_function() => {
+                    Some(function)
+                }
+                _ => None,
+            });
+        for function in functions {
+            let location =
+                format!("{}:{}", file.display(), function.span.start.line);
+            let modifiers = function.modifiers.as_deref().unwrap_or_default();
+            if modifiers.is_empty() {
+                println!("{location} {}", function.identifier);
+            } else {
+                println!(
+                    "{location} {} {}",
+                    function.identifier,
+                    modifiers.join(" -> ")
+                );
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,139 @@
+//! Defines the `bulloak order` command.
+//!
+//! This command reorders the functions of existing Solidity files to match
+//! the order of their trees, without otherwise changing them.
+
+use std::{fs, path::PathBuf};
+
+use bulloak_foundry::{
+    check::{
+        context::Context,
+        rules::{self, Checker},
+        violation::fix_order,
+    },
+    sol::find_contract,
+    violation::{Violation, ViolationKind},
+};
+use bulloak_syntax::utils::pluralize;
+use clap::Parser;
+use owo_colors::OwoColorize;
+use serde::{Deserialize, Serialize};
+
+use crate::cli::Cli;
+
+/// Reorder the functions of Solidity files to match their trees.
+#[doc(hidden)]
+#[derive(Debug, Parser, Clone, Serialize, Deserialize)]
+pub struct Order {
+    /// The set of tree files to use as spec.
+    ///
+    /// Solidity file names are inferred from the specs.
+    pub files: Vec<PathBuf>,
+    /// Whether to print the reordered files to stdout instead of writing
+    /// them.
+    #[arg(long, default_value_t = false)]
+    pub stdout: bool,
+    /// Whether to sort modifier definitions alphabetically when a tree file
+    /// has multiple roots.
+    #[arg(long, default_value_t = false)]
+    pub sort_modifiers: bool,
+    /// Whether to move revert tests after every other test.
+    #[arg(long, default_value_t = false)]
+    pub reverts_last: bool,
+}
+
+impl Order {
+    /// Entrypoint for `bulloak order`.
+    ///
+    /// Files with functions missing from them are skipped, since
+    /// `bulloak check --fix` has to add them first.
+    pub(crate) fn run(&self, cfg: &Cli) {
+        let config = cfg.into();
+        let mut failed = false;
+        for tree in &self.files {
+            let ctx = match Context::new(tree.clone(), &config) {
+                Ok(ctx) => ctx,
+                Err(violation) => {
+                    eprint!("{violation}");
+                    failed = true;
+                    continue;
+                }
+            };
+
+            let violations = rules::StructuralMatcher::check(&ctx);
+            let is_missing_fns = violations.iter().any(|v| {
+                matches!(v.kind, ViolationKind::MatchingFunctionMissing(_, _))
+            });
+            if is_missing_fns {
+                eprintln!(
+                    "{}: Skipped reordering {:?}",
+                    "warn".yellow(),
+                    ctx.sol.as_path().blue()
+                );
+                eprintln!(
+                    "    {} Some functions are missing, run `bulloak check --fix {}` first",
+                    "=".blue(),
+                    tree.display()
+                );
+                failed = true;
+                continue;
+            }
+
+            let violations: Vec<Violation> = violations
+                .into_iter()
+                .filter(|v| {
+                    matches!(
+                        v.kind,
+                        ViolationKind::FunctionOrderMismatch(_, _, _)
+                    )
+                })
+                .collect();
+            let sol = ctx.sol.clone();
+            let contracts =
+                find_contract(&ctx.pt).zip(ctx.hir.find_contract().cloned());
+            let ctx = match contracts {
+                Some((contract_sol, contract_hir))
+                    if !violations.is_empty() =>
+                {
+                    fix_order(&violations, &contract_sol, &contract_hir, ctx)
+                }
+                _ => ctx,
+            };
+
+            let output = if violations.is_empty() {
+                ctx.src
+            } else {
+                ctx.fmt().expect("should format the reordered solidity code")
+            };
+            self.write(&output, &sol, violations.len());
+        }
+
+        if failed {
+            std::process::exit(1);
+        }
+    }
+
+    /// Writes the reordered code of `sol` to stdout or back to the file.
+    fn write(&self, output: &str, sol: &PathBuf, moved: usize) {
+        if self.stdout {
+            println!("{} {}", "-->".blue(), sol.display());
+            println!("{}", output.trim());
+            println!("{}", "<--".blue());
+            return;
+        }
+        if moved == 0 {
+            return;
+        }
+
+        if let Err(err) = fs::write(sol, output) {
+            eprintln!("{}: {err}", "warn".yellow());
+            return;
+        }
+        let fn_literal = pluralize(moved, "function", "functions");
+        println!(
+            "{}: Moved {moved} {fn_literal} in {}",
+            "success".bold().green(),
+            sol.display()
+        );
+    }
+}
@@ -3,17 +3,36 @@
 //! This command scaffolds a Solidity file from a spec `.tree` file.
 
 use std::{
-    fs,
+    collections::HashSet,
+    fmt::Write,
+    fs, io,
     path::{Path, PathBuf},
+    sync::mpsc,
+    time::Duration,
 };
 
-use bulloak_foundry::{constants::DEFAULT_SOL_VERSION, scaffold::scaffold};
+use anyhow::anyhow;
+use bulloak_foundry::{
+    check::utils::relative_to_cwd,
+    config::{BodyTemplate, Config, KeywordAlias, TestBase},
+    constants::{DEFAULT_SOL_VERSION, INTERNAL_DEFAULT_INDENTATION},
+    hir::root_file,
+    scaffold::{
+        lints::{lint, Lint},
+        scaffold,
+    },
+    sol::{self, find_contract, is_manually_edited, stamp},
+};
+use bulloak_syntax::{parse_with_aliases, utils::pluralize, Ast};
 use clap::Parser;
 use forge_fmt::fmt;
+use notify::{RecursiveMode, Watcher};
 use owo_colors::OwoColorize;
+use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
+use solang_parser::pt::{ContractDefinition, ContractPart, FunctionTy};
 
-use crate::cli::Cli;
+use crate::cli::{keyword_aliases, Cli};
 
 /// Generate Solidity tests based on your spec.
 #[doc(hidden)]
@@ -22,7 +41,8 @@ pub struct Scaffold {
     /// The set of tree files to generate from.
     ///
     /// Each Solidity file will be named after its matching
-    /// tree spec.
+    /// tree spec. Pass `-` to read a tree from stdin, which is always
+    /// scaffolded to stdout.
     pub files: Vec<PathBuf>,
     /// Whether to write to files instead of stdout.
     ///
@@ -41,17 +61,412 @@ pub struct Scaffold {
         default_value_t = false
     )]
     pub force_write: bool,
+    /// Refuse to overwrite output files that were edited by hand since
+    /// `bulloak` generated them.
+    ///
+    /// Files written with this flag store a hash of their contents in their
+    /// `@bulloak:tree-hash` comment, which is checked before overwriting
+    /// them.
+    #[arg(long, requires = "file-handling", default_value_t = false)]
+    pub detect_manual_edits: bool,
+    /// When `--write-files` is passed, the directory that mirrors
+    /// `--test-dir`.
+    ///
+    /// Trees under this directory are written to the same path under
+    /// `--test-dir`, e.g. `src/token/Token.tree` to `test/token/Token.t.sol`.
+    #[arg(
+        long,
+        value_name = "DIR",
+        requires_all = ["file-handling", "test_dir"]
+    )]
+    pub src_dir: Option<PathBuf>,
+    /// When `--write-files` is passed, the directory that mirrors
+    /// `--src-dir`.
+    #[arg(long, value_name = "DIR", requires = "src_dir")]
+    pub test_dir: Option<PathBuf>,
+    /// When `--write-files` is passed, the directory to write every output
+    /// file to, instead of next to its tree.
+    ///
+    /// The directory is created if it doesn't exist.
+    #[arg(
+        long,
+        value_name = "DIR",
+        requires = "file-handling",
+        conflicts_with = "src_dir"
+    )]
+    pub output_dir: Option<PathBuf>,
+    /// Whether to treat the root name as a path relative to the output
+    /// directory, e.g. `token/Foo_Test` is written to
+    /// `token/Foo_Test.t.sol`.
+    ///
+    /// The directories are left out of the contract name.
+    #[arg(long, default_value_t = false)]
+    pub root_as_path: bool,
     /// Sets a Solidity version for the test contracts.
     #[arg(short = 's', long, default_value = DEFAULT_SOL_VERSION)]
     pub solidity_version: String,
+    /// Whether to omit the pragma directive.
+    ///
+    /// Useful when the generated code goes into a file that already
+    /// declares a pragma.
+    #[arg(long, default_value_t = false)]
+    pub no_pragma: bool,
+    /// How to render the Solidity version in the pragma directive.
+    ///
+    /// `exact` pins it with `=`, `caret` prefixes it with `^` and
+    /// `verbatim` emits it as provided.
+    #[arg(
+        long,
+        default_value = "verbatim",
+        value_parser = ["verbatim", "exact", "caret"]
+    )]
+    pub pragma_style: String,
+    /// The number of spaces to indent each level of the generated code by.
+    ///
+    /// Only noticeable when the code can't be formatted, since formatting
+    /// re-indents it.
+    #[arg(long, value_name = "SPACES", default_value_t = INTERNAL_DEFAULT_INDENTATION)]
+    pub indent: usize,
     /// Whether to add vm.skip(true) at the beginning of each test.
     #[arg(short = 'S', long = "vm-skip", default_value_t = false)]
     pub with_vm_skip: bool,
     /// Whether to emit modifiers.
     #[arg(short = 'm', long, default_value_t = false)]
     pub skip_modifiers: bool,
+    /// Whether to emit the title of the condition each modifier comes from
+    /// as a comment above the modifier.
+    #[arg(long, default_value_t = false)]
+    pub modifier_comments: bool,
+    /// Whether to emit a `// @bulloak:generated` comment above every
+    /// generated function.
+    #[arg(long, default_value_t = false)]
+    pub mark_generated: bool,
+    /// Whether to sort modifier definitions alphabetically when a tree file
+    /// has multiple roots.
+    #[arg(long, default_value_t = false)]
+    pub sort_modifiers: bool,
+    /// Whether to emit revert tests after every other test, keeping their
+    /// relative order otherwise.
+    #[arg(long, default_value_t = false)]
+    pub reverts_last: bool,
+    /// Whether to group the tests under `given` and `when` conditions into
+    /// two sections, each opened by a banner comment.
+    #[arg(long, default_value_t = false)]
+    pub group_by_keyword: bool,
+    /// Whether to error out instead of warning when two roots of a tree
+    /// file have the same name.
+    #[arg(long, default_value_t = false)]
+    pub deny_duplicate_roots: bool,
+    /// Paths of files to import in the test contracts, e.g. a shared
+    /// constants file.
+    #[arg(long = "import", value_name = "PATH", value_parser = parse_import)]
+    pub imports: Vec<String>,
+    /// Features to enable with `pragma experimental`, e.g. `SMTChecker`.
+    #[arg(long = "experimental-pragma", value_name = "FEATURE")]
+    pub experimental_pragmas: Vec<String>,
+    /// Solhint rules to disable in the test files, e.g. `no-empty-blocks`.
+    #[arg(long, value_name = "RULE", value_delimiter = ',')]
+    pub solhint_disable: Vec<String>,
+    /// Whether to emit action descriptions as `// TODO:` comments.
+    #[arg(long, default_value_t = false)]
+    pub descriptions_as_todos: bool,
+    /// Named addresses to label with `vm.label`.
+    ///
+    /// Every test whose branch mentions one of these names will label it,
+    /// e.g. `vm.label(alice, "alice");`.
+    #[arg(long = "label", value_name = "NAME", value_delimiter = ',')]
+    pub labels: Vec<String>,
+    /// Statements to emit in the body of the tests whose name starts with a
+    /// prefix, given as `PREFIX=TEMPLATE`.
+    ///
+    /// `{{name}}` in the template is replaced by the name of the test, e.g.
+    /// `--body-template 'test_Revert=vm.expectRevert();'`.
+    #[arg(
+        long = "body-template",
+        value_name = "PREFIX=TEMPLATE",
+        value_parser = parse_body_template
+    )]
+    pub body_templates: Vec<String>,
+    /// The contract to inherit from instead of forge-std's `Test` when a
+    /// test needs cheatcodes or assertions, given as `NAME=PATH`.
+    ///
+    /// For example, `--test-base PRBTest=@prb/test/src/PRBTest.sol`.
+    #[arg(long, value_name = "NAME=PATH", value_parser = parse_test_base)]
+    pub test_base: Option<String>,
+    /// A statement to emit at the top of every test, e.g. `_test();`.
+    #[arg(
+        long = "default-body",
+        value_name = "STATEMENT",
+        value_parser = parse_statement
+    )]
+    pub default_body_statement: Option<String>,
+    /// The prefix of the tests marked with `[fuzz]`.
+    #[arg(long, value_name = "PREFIX", default_value = "testFuzz_")]
+    pub fuzz_prefix: String,
+    /// Custom words to recognize as keywords, given as `WORD=KEYWORD`.
+    ///
+    /// For example, `--keyword-aliases if=when,scenario=given` reads
+    /// `if the caller is the owner` as `when the caller is the owner`.
+    #[arg(
+        long,
+        value_name = "WORD=KEYWORD",
+        value_delimiter = ',',
+        value_parser = parse_keyword_alias
+    )]
+    pub keyword_aliases: Vec<String>,
+    /// Whether to emit a `setUp` function at the start of the test contract.
+    ///
+    /// If the tree has invariant tests, `setUp` registers a placeholder
+    /// target contract with `targetContract`.
+    #[arg(long, default_value_t = false)]
+    pub emit_setup: bool,
+    /// Whether to emit a `receive() external payable {}` function when the
+    /// tree has tests marked with `[payable]`.
+    #[arg(long, default_value_t = false)]
+    pub emit_receive: bool,
+    /// Whether to emit an `assertEq(actual, <expected>);` placeholder in
+    /// tests whose action states the value it expects, e.g.
+    /// `it should equal MAX_UINT`.
+    #[arg(long, default_value_t = false)]
+    pub assertions: bool,
+    /// Whether to emit a `vm.expectRevert` of the custom error in tests
+    /// whose action reverts with one, e.g.
+    /// `it should revert with Unauthorized(caller)`.
+    #[arg(long, default_value_t = false)]
+    pub expect_revert: bool,
+    /// Whether to emit a low-level call that must succeed in tests whose
+    /// action never reverts, e.g. `it never reverts`.
+    #[arg(long, default_value_t = false)]
+    pub no_revert: bool,
+    /// Whether to add an `// Arrange`, `// Act` and `// Assert` skeleton
+    /// below the comments of every test.
+    #[arg(long, default_value_t = false)]
+    pub aaa: bool,
+    /// Whether to label the descriptions of actions that have exactly
+    /// three of them with `Arrange:`, `Act:` and `Assert:`.
+    #[arg(long, default_value_t = false)]
+    pub aaa_from_descriptions: bool,
+    /// Whether to turn action descriptions that call a contract or emit an
+    /// event into `vm.expectCall` and `vm.expectEmit` stubs.
+    #[arg(long, default_value_t = false)]
+    pub smart_descriptions: bool,
+    /// Whether to open every test with a comment per condition it's under,
+    /// e.g. `// GIVEN: the vault is paused`.
+    #[arg(long, default_value_t = false)]
+    pub condition_comments: bool,
+    /// Whether to emit the comments of every test above its signature
+    /// instead of inside its body.
+    #[arg(long, default_value_t = false)]
+    pub comments_above: bool,
+    /// Whether to leave out the actions that repeat the title of an earlier
+    /// action of the same test.
+    #[arg(long, default_value_t = false)]
+    pub dedup_actions: bool,
+    /// Whether to precede every generated comment with a
+    /// `// forgefmt: disable-next-line` directive.
+    #[arg(long, default_value_t = false)]
+    pub forgefmt_disable: bool,
+    /// Whether to link every generated function to the line of the tree it
+    /// comes from, e.g. `/// @dev spec: foo.tree:L3`.
+    #[arg(long, default_value_t = false)]
+    pub spec_refs: bool,
+    /// Whether to store the hash of the tree in every generated file, so
+    /// that `bulloak check` skips the files whose tree didn't change.
+    #[arg(long, default_value_t = false)]
+    pub tree_hash: bool,
+    /// Whether to print paths relative to the current working directory.
+    #[arg(long, default_value_t = false)]
+    pub relative_paths: bool,
+    /// Warn when a generated function name is longer than this many
+    /// characters.
+    #[arg(long, value_name = "LENGTH")]
+    pub max_name_length: Option<usize>,
+    /// Warn when a condition or action title has more than this many
+    /// words.
+    #[arg(long, value_name = "WORDS")]
+    pub max_title_words: Option<usize>,
+    /// Warn when the contract of a tree isn't named like its Solidity file,
+    /// e.g. `contract Foo` in `Foo.t.sol`.
+    #[arg(long, default_value_t = false)]
+    pub check_file_name: bool,
+    /// Warn when an action doesn't start with `it` or `It`, e.g. `IT`.
+    #[arg(long, default_value_t = false)]
+    pub lint_it_keyword: bool,
+    /// Warn when an action repeats the title of an earlier action of the
+    /// same test.
+    #[arg(long, default_value_t = false)]
+    pub lint_duplicate_actions: bool,
+    /// Whether to keep running and re-scaffold the tree files every time
+    /// they change.
+    #[arg(long, default_value_t = false)]
+    pub watch: bool,
+    /// Whether to list the root of every tree in the files, along with
+    /// the lines it spans, instead of scaffolding them.
+    #[arg(
+        long,
+        conflicts_with_all = ["file-handling", "watch"],
+        default_value_t = false
+    )]
+    pub list_trees: bool,
+    /// How to report the scaffolded files.
+    ///
+    /// `text` prints the Solidity code, unless it is written to files. `json`
+    /// prints a JSON object per file describing what was generated instead.
+    #[arg(long, default_value = "text", value_parser = ["text", "json"])]
+    pub format: String,
+    /// The file to list every generated contract in.
+    ///
+    /// A `.md` file gets a table of the contracts and their files. Any other
+    /// file gets a Solidity file that imports every contract.
+    #[arg(long, value_name = "FILE")]
+    pub emit_index: Option<PathBuf>,
+}
+
+/// The Solidity code scaffolded from a tree file, before it's reported.
+struct Scaffolded {
+    /// The lints found in the tree file.
+    lints: Vec<Lint>,
+    /// The scaffolded code, formatted unless formatting failed.
+    formatted: String,
+    /// The error formatting the code failed with, if any.
+    fmt_error: Option<String>,
+    /// The path of the Solidity file to write the code to.
+    output: PathBuf,
 }
 
+/// A description of the Solidity file scaffolded from a tree file, reported
+/// by `--format json`.
+#[derive(Debug, Serialize)]
+struct Artifact<'a> {
+    /// The path of the tree file.
+    input: &'a Path,
+    /// The path the Solidity file is written to with `-w`.
+    output: PathBuf,
+    /// Whether the Solidity file was written to `output`.
+    written: bool,
+    /// The name of the generated contract.
+    contract: Option<String>,
+    /// The number of generated functions, not counting modifiers.
+    functions: usize,
+}
+
+impl<'a> Artifact<'a> {
+    /// Describes the Solidity `code` scaffolded from the tree file `input`.
+    fn new(
+        input: &'a Path,
+        output: PathBuf,
+        written: bool,
+        code: &str,
+    ) -> Self {
+        let contract = parse_contract(code);
+        let functions = contract.as_ref().map_or(0, |contract| {
+            contract
+                .parts
+                .iter()
+                .filter(|part| {
+                    matches!(part, ContractPart::FunctionDefinition(f) if f.ty == FunctionTy::Function)
+                })
+                .count()
+        });
+        let contract =
+            contract.and_then(|contract| contract.name).map(|name| name.name);
+
+        Self { input, output, written, contract, functions }
+    }
+}
+
+/// Returns the first contract of the Solidity `code`, if it parses.
+fn parse_contract(code: &str) -> Option<Box<ContractDefinition>> {
+    forge_fmt::parse(code).ok().and_then(|parsed| find_contract(&parsed.pt))
+}
+
+/// Renders the index of the generated `contracts` to write to `index`.
+///
+/// `contracts` pairs the name of each contract with the path of its file.
+/// Paths under the directory of `index` are made relative to it.
+fn render_index(
+    index: &Path,
+    contracts: &[(String, PathBuf)],
+    pragma: &str,
+) -> String {
+    let dir = match index.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+    let dir = std::path::absolute(dir).unwrap_or_default();
+    let is_markdown = index.extension().is_some_and(|ext| ext == "md");
+    let header = if is_markdown {
+        "| Contract | File |\n| --- | --- |\n".to_owned()
+    } else {
+        format!("// SPDX-License-Identifier: UNLICENSED\npragma solidity {pragma};\n\n")
+    };
+    let entries = contracts.iter().map(|(contract, path)| {
+        let path = std::path::absolute(path).unwrap_or_else(|_| path.clone());
+        let path = path.strip_prefix(&dir).map_or_else(
+            |_| path.display().to_string(),
+            |relative| format!("./{}", relative.display()),
+        );
+        if is_markdown {
+            format!("| `{contract}` | `{path}` |\n")
+        } else {
+            format!("import {{{contract}}} from \"{path}\";\n")
+        }
+    });
+
+    std::iter::once(header).chain(entries).collect()
+}
+
+/// Validates a `--body-template` value.
+fn parse_body_template(s: &str) -> Result<String, String> {
+    s.parse::<BodyTemplate>().map(|_| s.to_owned())
+}
+
+/// Validates an `--import` value.
+///
+/// Paths are emitted between double quotes, so they can't contain quotes or
+/// line breaks.
+fn parse_import(s: &str) -> Result<String, String> {
+    sol::validate_import(s).map(|()| s.to_owned())
+}
+
+/// Validates a `--default-body` value.
+fn parse_statement(s: &str) -> Result<String, String> {
+    sol::validate_statement(s).map(|()| s.to_owned())
+}
+
+/// Validates a `--keyword-aliases` value.
+pub(crate) fn parse_keyword_alias(s: &str) -> Result<String, String> {
+    s.parse::<KeywordAlias>().map(|_| s.to_owned())
+}
+
+/// Validates a `--test-base` value.
+fn parse_test_base(s: &str) -> Result<String, String> {
+    s.parse::<TestBase>().map(|_| s.to_owned())
+}
+
+/// The file argument that reads a tree from stdin.
+const STDIN: &str = "-";
+
+/// Whether `file` is the `-` argument, which reads a tree from stdin.
+fn is_stdin(file: &Path) -> bool {
+    file == Path::new(STDIN)
+}
+
+/// Reads the tree in `file`, or in stdin if `file` is `-`.
+fn read_tree(file: &Path) -> io::Result<String> {
+    if is_stdin(file) {
+        io::read_to_string(io::stdin())
+    } else {
+        fs::read_to_string(file)
+    }
+}
+
+/// How long to wait for more file system events before re-scaffolding.
+///
+/// Editors usually touch a file several times per save, so we coalesce
+/// events that arrive within this window into a single run.
+const WATCH_DEBOUNCE: Duration = Duration::from_millis(100);
+
 impl Default for Scaffold {
     fn default() -> Self {
         Scaffold::parse_from(Vec::<String>::new())
@@ -59,27 +474,129 @@ impl Default for Scaffold {
 }
 
 impl Scaffold {
+    /// Checks the options that are emitted as is into the generated code.
+    ///
+    /// Options read from `bulloak.toml` skip the validation of the command
+    /// line parser, so they are checked here before scaffolding.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error describing the first invalid option found.
+    pub(crate) fn validate(&self) -> anyhow::Result<()> {
+        if let Some(statement) = &self.default_body_statement {
+            parse_statement(statement).map_err(|err| anyhow!(err))?;
+        }
+        for template in &self.body_templates {
+            parse_body_template(template).map_err(|err| anyhow!(err))?;
+        }
+        for path in &self.imports {
+            parse_import(path).map_err(|err| anyhow!(err))?;
+        }
+
+        Ok(())
+    }
+
     /// Runs the scaffold command, processing all specified files.
     ///
     /// This method iterates through all input files, processes them, and either
     /// writes the output to files or prints to stdout based on the config.
     ///
     /// If any errors occur during processing, they are collected and reported.
+    ///
+    /// Files are scaffolded in parallel, but reported and written in the
+    /// order they were passed in.
     pub(crate) fn run(&self, cfg: &Cli) {
-        let errors: Vec<_> = self
-            .files
-            .iter()
-            .filter_map(|file| {
-                self.process_file(file, cfg)
-                    .map_err(|e| (file.as_path(), e))
-                    .err()
-            })
-            .collect();
+        let errors: Vec<_> = if self.list_trees {
+            let listings: Vec<_> =
+                self.files.par_iter().map(|f| self.list_trees(f)).collect();
+            self.files
+                .iter()
+                .zip(listings)
+                .filter_map(|(file, listing)| {
+                    listing
+                        .map(|listing| print!("{listing}"))
+                        .map_err(|e| (file.as_path(), e))
+                        .err()
+                })
+                .collect()
+        } else {
+            let results: Vec<_> = self
+                .files
+                .par_iter()
+                .map(|file| self.scaffold_file(file, cfg))
+                .collect();
+            if let Some(index) = &self.emit_index {
+                Scaffold::write_index(index, &results, cfg);
+            }
+            self.files
+                .iter()
+                .zip(results)
+                .filter_map(|(file, result)| {
+                    result
+                        .and_then(|scaffolded| self.emit_file(file, scaffolded))
+                        .map_err(|e| (file.as_path(), e))
+                        .err()
+                })
+                .collect()
+        };
 
         if !errors.is_empty() {
-            Scaffold::report_errors(&errors);
-            std::process::exit(1);
+            self.report_errors(&errors);
+            if !self.watch {
+                std::process::exit(1);
+            }
+        }
+
+        if self.watch {
+            if let Err(err) = self.watch(cfg) {
+                eprintln!("{}: {err}", "error".red());
+                std::process::exit(1);
+            }
+        }
+    }
+
+    /// Watches the input files and re-scaffolds them whenever they change.
+    ///
+    /// We watch the parent directories instead of the files themselves,
+    /// since many editors save by replacing the file, which would otherwise
+    /// make us lose track of it.
+    fn watch(&self, cfg: &Cli) -> anyhow::Result<()> {
+        // Trees read from stdin can't change.
+        let inputs: Vec<&PathBuf> =
+            self.files.iter().filter(|file| !is_stdin(file)).collect();
+        let files: Vec<PathBuf> =
+            inputs.iter().map(fs::canonicalize).collect::<Result<_, _>>()?;
+        let dirs: HashSet<&Path> =
+            files.iter().filter_map(|file| file.parent()).collect();
+
+        let (tx, rx) = mpsc::channel();
+        let mut watcher = notify::recommended_watcher(tx)?;
+        for dir in dirs {
+            watcher.watch(dir, RecursiveMode::NonRecursive)?;
+        }
+
+        let file_literal = pluralize(files.len(), "file", "files");
+        eprintln!("{}: Watching {} {file_literal}", "info".blue(), files.len());
+        while let Ok(event) = rx.recv() {
+            let mut changed = HashSet::new();
+            changed.extend(event?.paths);
+            // Debounce: collect everything that happens shortly after.
+            while let Ok(event) = rx.recv_timeout(WATCH_DEBOUNCE) {
+                changed.extend(event?.paths);
+            }
+
+            for (file, canonical) in inputs.iter().zip(&files) {
+                if !changed.contains(canonical) {
+                    continue;
+                }
+
+                if let Err(err) = self.process_file(file, cfg) {
+                    self.report_errors(&[(file.as_path(), err)]);
+                }
+            }
         }
+
+        Ok(())
     }
 
     /// Processes a single input file.
@@ -87,28 +604,163 @@ impl Scaffold {
     /// This method reads the input file, scaffolds the Solidity code, formats
     /// it, and either writes it to a file or prints it to stdout.
     fn process_file(&self, file: &Path, cfg: &Cli) -> anyhow::Result<()> {
-        let text = fs::read_to_string(file)?;
-        let emitted = scaffold(&text, &cfg.into())?;
-        let formatted = fmt(&emitted).unwrap_or_else(|err| {
-            eprintln!("{}: {}", "WARN".yellow(), err);
-            emitted
-        });
+        let scaffolded = self.scaffold_file(file, cfg)?;
+        self.emit_file(file, scaffolded)
+    }
 
-        if self.write_files {
-            let file = file.with_extension("t.sol");
-            self.write_file(&formatted, &file);
+    /// Scaffolds and formats the Solidity code of a single input file,
+    /// without reporting anything.
+    ///
+    /// This has no side effects, so that files can be scaffolded in parallel.
+    fn scaffold_file(
+        &self,
+        file: &Path,
+        cfg: &Cli,
+    ) -> anyhow::Result<Scaffolded> {
+        let text = read_tree(file)?;
+        let mut cfg: Config = cfg.into();
+        if !is_stdin(file) {
+            cfg.tree_path = Some(file.to_path_buf());
+            if self.spec_refs {
+                cfg.spec_path = Some(file.to_path_buf());
+            }
+        }
+        // The stamp of `--detect-manual-edits` is stored next to the tree hash.
+        if self.tree_hash || self.detect_manual_edits {
+            cfg.tree_hash = Some(sol::hash(&text));
+        }
+        let emitted = scaffold(&text, &cfg)?;
+        let lints = lint(&text, &cfg)?;
+        let (formatted, fmt_error) = match fmt(&emitted) {
+            Ok(formatted) => (formatted, None),
+            Err(err) => (emitted, Some(err.to_string())),
+        };
+        // Trees read from stdin have no path to derive the output path from.
+        let output = if is_stdin(file) {
+            PathBuf::from(STDIN)
         } else {
+            self.output_path(file, &text)?
+        };
+
+        Ok(Scaffolded { lints, formatted, fmt_error, output })
+    }
+
+    /// Reports the lints of a scaffolded file, and either writes its code to
+    /// a file or prints it to stdout.
+    fn emit_file(
+        &self,
+        file: &Path,
+        scaffolded: Scaffolded,
+    ) -> anyhow::Result<()> {
+        let Scaffolded { lints, formatted, fmt_error, output } = scaffolded;
+        self.report_lints(file, &lints);
+        if let Some(err) = fmt_error {
+            eprintln!("{}: {}", "WARN".yellow(), err);
+        }
+
+        let write_files = self.write_files && !is_stdin(file);
+        let written = write_files && self.write_file(&formatted, &output);
+        if self.format == "json" {
+            let artifact = Artifact::new(file, output, written, &formatted);
+            println!("{}", serde_json::to_string(&artifact)?);
+        } else if !write_files {
             println!("{formatted}");
         }
 
         Ok(())
     }
 
+    /// Returns the root of every tree in `file` and the lines it spans, one
+    /// per line.
+    fn list_trees(&self, file: &Path) -> anyhow::Result<String> {
+        let text = read_tree(file)?;
+        let aliases = keyword_aliases(&self.keyword_aliases);
+        let mut listing = String::new();
+        for (line, ast) in parse_with_aliases(&text, &aliases)? {
+            let Ast::Root(root) = ast else {
+                unreachable!("the parser always returns a root");
+            };
+
+            let end = line + root.span.end.line - 1;
+            writeln!(
+                listing,
+                "{}:{line}-{end} {}",
+                self.display_path(file).display(),
+                root.contract_name
+            )?;
+        }
+
+        Ok(listing)
+    }
+
+    /// Returns the path of the Solidity file to write the tests of `file` to.
+    ///
+    /// This is `file` with a `.t.sol` extension, mirrored from `--src-dir`
+    /// into `--test-dir` if `file` is under `--src-dir`, or moved into
+    /// `--output-dir`. With `--root-as-path`,
+    /// the root name in `text` replaces the file name if it has directories.
+    fn output_path(&self, file: &Path, text: &str) -> anyhow::Result<PathBuf> {
+        let mut file = file.with_extension("t.sol");
+        if let (Some(src_dir), Some(test_dir)) = (&self.src_dir, &self.test_dir)
+        {
+            if let Ok(relative) = file.strip_prefix(src_dir) {
+                file = test_dir.join(relative);
+            }
+        }
+        if let (Some(output_dir), Some(name)) =
+            (&self.output_dir, file.file_name())
+        {
+            file = output_dir.join(name);
+        }
+        if !self.root_as_path {
+            return Ok(file);
+        }
+
+        let Some((_, Ast::Root(root))) =
+            parse_with_aliases(text, &keyword_aliases(&self.keyword_aliases))?
+                .into_iter()
+                .next()
+        else {
+            return Ok(file);
+        };
+        let Some(name) = root_file(&root.contract_name) else {
+            return Ok(file);
+        };
+
+        Ok(file.parent().map_or(name.clone(), |parent| parent.join(&name)))
+    }
+
+    /// Writes the index of the contracts scaffolded in `results` to `index`.
+    ///
+    /// The index is derived from the trees, so it is always overwritten.
+    fn write_index(
+        index: &Path,
+        results: &[anyhow::Result<Scaffolded>],
+        cfg: &Cli,
+    ) {
+        let contracts: Vec<_> = results
+            .iter()
+            .filter_map(|result| result.as_ref().ok())
+            .filter(|scaffolded| !is_stdin(&scaffolded.output))
+            .filter_map(|scaffolded| {
+                let contract = parse_contract(&scaffolded.formatted)?.name?;
+                Some((contract.name, scaffolded.output.clone()))
+            })
+            .collect();
+        let pragma = Config::from(cfg).pragma_version();
+        let rendered = render_index(index, &contracts, &pragma);
+        if let Err(err) = fs::write(index, rendered) {
+            eprintln!("{}: {err}", "warn".yellow());
+        }
+    }
+
     /// Writes the provided `text` to `file`.
     ///
     /// If the file doesn't exist it will create it. If it exists,
     /// and `--force-write` was not passed, it will skip writing to the file.
-    fn write_file(&self, text: &str, file: &PathBuf) {
+    ///
+    /// Returns whether the file was written.
+    fn write_file(&self, text: &str, file: &PathBuf) -> bool {
         // Don't overwrite files unless `--force-write` was passed.
         if file.exists() && !self.force_write {
             eprintln!(
@@ -120,22 +772,79 @@ impl Scaffold {
                 "    {} The corresponding `.t.sol` file already exists",
                 "=".blue()
             );
-            return;
+            return false;
+        }
+
+        if self.detect_manual_edits && file.exists() {
+            let edited = fs::read_to_string(file)
+                .map_or(true, |existing| is_manually_edited(&existing));
+            if edited {
+                eprintln!(
+                    "{}: Skipped emitting {:?}",
+                    "warn".yellow(),
+                    file.as_path().blue()
+                );
+                eprintln!(
+                    "    {} The corresponding `.t.sol` file was edited by hand",
+                    "=".blue()
+                );
+                return false;
+            }
         }
 
+        let text = if self.detect_manual_edits {
+            stamp(text)
+        } else {
+            text.to_owned()
+        };
+        if let Some(parent) = file.parent() {
+            if let Err(err) = fs::create_dir_all(parent) {
+                eprintln!("{}: {err}", "error".red());
+                return false;
+            }
+        }
         if let Err(err) = fs::write(file, text) {
             eprintln!("{}: {err}", "error".red());
+            return false;
         };
+
+        true
+    }
+
+    /// Reports the lints found in a tree file.
+    fn report_lints(&self, file: &Path, lints: &[Lint]) {
+        let file = self.display_path(file);
+        for lint in lints {
+            eprint!("{lint}");
+            eprintln!(
+                "   {} {}:{}",
+                "-->".blue(),
+                file.display(),
+                lint.span.start.line
+            );
+        }
+    }
+
+    /// Returns `file` as it should be printed, i.e. relative to the current
+    /// working directory with `--relative-paths`, or `<stdin>` for `-`.
+    fn display_path(&self, file: &Path) -> PathBuf {
+        if is_stdin(file) {
+            PathBuf::from("<stdin>")
+        } else if self.relative_paths {
+            relative_to_cwd(file)
+        } else {
+            file.to_path_buf()
+        }
     }
 
     /// Reports errors that occurred during file processing.
     ///
     /// This method prints error messages for each file that failed to process,
     /// along with a summary of the total number of failed files.
-    fn report_errors(errors: &[(&Path, anyhow::Error)]) {
+    fn report_errors(&self, errors: &[(&Path, anyhow::Error)]) {
         for (file, err) in errors {
             eprintln!("{err}");
-            eprintln!("file: {}", file.display());
+            eprintln!("file: {}", self.display_path(file).display());
         }
 
         eprintln!(
@@ -0,0 +1,95 @@
+//! Defines the `bulloak tree` command.
+//!
+//! This command reconstructs the tree of an existing Solidity test file,
+//! which makes it easier to adopt `bulloak` in a repo that already has tests.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use bulloak_foundry::{config::Config, reverse};
+use clap::Parser;
+use owo_colors::OwoColorize;
+use serde::{Deserialize, Serialize};
+
+/// Generate tree specs from existing Solidity tests.
+#[doc(hidden)]
+#[derive(Debug, Parser, Clone, Serialize, Deserialize)]
+pub struct Tree {
+    /// The set of Solidity files to generate from.
+    ///
+    /// Each tree file will be named after its matching Solidity file, e.g.
+    /// `Foo.t.sol` turns into `Foo.tree`.
+    pub files: Vec<PathBuf>,
+    /// Whether to write to files instead of stdout.
+    ///
+    /// This will write the output for each input file next to it if the
+    /// output file doesn't already exist. To overwrite, use `--force-write`
+    /// together with `--write-files`.
+    #[arg(short = 'w', long, group = "file-handling", default_value_t = false)]
+    pub write_files: bool,
+    /// When `--write-files` is passed, use `--force-write` to
+    /// overwrite the output files.
+    #[arg(
+        short = 'f',
+        long,
+        requires = "file-handling",
+        default_value_t = false
+    )]
+    pub force_write: bool,
+    /// The prefix of the tests that get marked with `[fuzz]`.
+    #[arg(long, value_name = "PREFIX", default_value = "testFuzz_")]
+    pub fuzz_prefix: String,
+}
+
+impl Tree {
+    /// Entrypoint for `bulloak tree`.
+    pub(crate) fn run(&self) {
+        let cfg = Config {
+            fuzz_prefix: self.fuzz_prefix.clone(),
+            ..Config::default()
+        };
+        let mut failed = false;
+        for file in &self.files {
+            if let Err(err) = self.process_file(file, &cfg) {
+                eprintln!("{}: {err}", "error".red());
+                eprintln!("file: {}", file.display());
+                failed = true;
+            }
+        }
+
+        if failed {
+            std::process::exit(1);
+        }
+    }
+
+    /// Reconstructs the tree of `file` and prints it or writes it next to
+    /// `file`.
+    fn process_file(&self, file: &Path, cfg: &Config) -> anyhow::Result<()> {
+        let src = fs::read_to_string(file)?;
+        let tree = reverse::reconstruct(&src, cfg)?;
+        if !self.write_files {
+            print!("{tree}");
+            return Ok(());
+        }
+
+        // `Foo.t.sol` turns into `Foo.tree`, and `Foo.sol` too.
+        let output = file.with_extension("").with_extension("tree");
+        if output.exists() && !self.force_write {
+            eprintln!(
+                "{}: Skipped emitting {:?}",
+                "warn".yellow(),
+                output.as_path().blue()
+            );
+            eprintln!(
+                "    {} The corresponding `.tree` file already exists",
+                "=".blue()
+            );
+            return Ok(());
+        }
+
+        fs::write(&output, tree)?;
+        Ok(())
+    }
+}
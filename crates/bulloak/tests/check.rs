@@ -1,4 +1,6 @@
-use std::env;
+//! Integration tests for `bulloak check`.
+
+use std::{env, fs};
 
 use common::{cmd, get_binary_path};
 use owo_colors::OwoColorize;
@@ -77,6 +79,489 @@ fn checks_modifiers_skipped_issue_81() {
     ));
 }
 
+#[test]
+fn checks_files_with_imports() {
+    let cwd = env::current_dir().unwrap();
+    let binary_path = get_binary_path();
+    let tree_path = cwd.join("tests").join("check").join("imports.tree");
+
+    let output = cmd(&binary_path, "check", &tree_path, &[]);
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+
+    assert_eq!("", stderr);
+    assert!(
+        stdout.contains("All checks completed successfully! No issues found.")
+    );
+}
+
+#[test]
+fn checks_extra_test_functions() {
+    let cwd = env::current_dir().unwrap();
+    let binary_path = get_binary_path();
+    let tree_path = cwd.join("tests").join("check").join("strict_extras.tree");
+
+    let output = cmd(&binary_path, "check", &tree_path, &[]);
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(
+        stdout.contains("All checks completed successfully! No issues found.")
+    );
+
+    let output = cmd(&binary_path, "check", &tree_path, &["--strict-extras"]);
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains(
+        r#"function "test_WhenFirstArgIsBiggerThanSecondArg" is not generated by the tree"#
+    ));
+    assert!(!stderr.contains("hashHelper"));
+    assert!(stderr.contains("1 check failed"));
+}
+
+#[test]
+fn checks_unused_modifiers() {
+    let cwd = env::current_dir().unwrap();
+    let binary_path = get_binary_path();
+    let tree_path =
+        cwd.join("tests").join("check").join("unused_modifier.tree");
+
+    // Unused modifiers are only reported when asked to.
+    let output = cmd(&binary_path, "check", &tree_path, &[]);
+    assert!(output.status.success());
+
+    let args = ["--check-unused-modifiers"];
+    let output = cmd(&binary_path, "check", &tree_path, &args);
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains(
+        r#"modifier "whenFirstArgIsBiggerThanSecondArg" is not used by any function"#
+    ));
+    assert!(stderr.contains("unused_modifier.t.sol:9"));
+    assert!(!stderr.contains("onlyOwner"));
+    assert!(stderr.contains("1 check failed"));
+}
+
+#[test]
+fn checks_sorted_modifiers() {
+    let cwd = env::current_dir().unwrap();
+    let binary_path = get_binary_path();
+    let tree_path = cwd.join("tests").join("check").join("sort_modifiers.tree");
+
+    let output = cmd(&binary_path, "check", &tree_path, &["--sort-modifiers"]);
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(
+        stdout.contains("All checks completed successfully! No issues found.")
+    );
+
+    let output = cmd(&binary_path, "check", &tree_path, &[]);
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains(
+        "incorrect position for function `test_TransferRevertWhen_TheAmountIsZero`"
+    ));
+}
+
+#[test]
+fn checks_ignore_natspec_directives() {
+    let cwd = env::current_dir().unwrap();
+    let binary_path = get_binary_path();
+    let tree_path = cwd.join("tests").join("check").join("natspec.tree");
+
+    let output = cmd(&binary_path, "check", &tree_path, &[]);
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(
+        stdout.contains("All checks completed successfully! No issues found.")
+    );
+}
+
+#[test]
+fn checks_comment_order() {
+    let cwd = env::current_dir().unwrap();
+    let binary_path = get_binary_path();
+    let tree_path = cwd.join("tests").join("check").join("comment_order.tree");
+
+    // Comment order is only checked on demand.
+    let output = cmd(&binary_path, "check", &tree_path, &[]);
+    assert!(output.status.success());
+
+    let args = ["--check-comment-order"];
+    let output = cmd(&binary_path, "check", &tree_path, &args);
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains(
+        r#"comment "it should emit a {Transfer} event." is out of order in function "test_WhenTheAmountIsNotZero""#
+    ));
+    assert!(stderr.contains("comment_order.t.sol:7"));
+    assert!(stderr.contains("1 check failed"));
+
+    // The line is right even if the body opens at the start of a line.
+    let tmp_path = env::temp_dir().join("bulloak_comment_order_brace");
+    let _ = fs::remove_dir_all(&tmp_path);
+    fs::create_dir_all(&tmp_path).unwrap();
+    let sol = fs::read_to_string(tree_path.with_extension("t.sol")).unwrap();
+    let sol = sol.replace("external {", "external\n{");
+    fs::write(tmp_path.join("comment_order.t.sol"), sol).unwrap();
+    fs::copy(&tree_path, tmp_path.join("comment_order.tree")).unwrap();
+    let tree_path = tmp_path.join("comment_order.tree");
+    let output = cmd(&binary_path, "check", &tree_path, &args);
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("comment_order.t.sol:8"));
+
+    fs::remove_dir_all(&tmp_path).unwrap();
+}
+
+#[test]
+fn checks_scaffolded_fixtures_with_the_default_rules() {
+    let cwd = env::current_dir().unwrap();
+    let binary_path = get_binary_path();
+    let tests_path = cwd.join("tests").join("scaffold");
+    let trees = [
+        "basic.tree",
+        "complex.tree",
+        "multiple_roots.tree",
+        "hash_pair.tree",
+        "spurious_comments.tree",
+        "descriptions.tree",
+        "comments.tree",
+        "nested_value_sets.tree",
+    ];
+
+    for tree_name in trees {
+        let tree_path = tests_path.join(tree_name);
+        let sol_path = tree_path.with_extension("t.sol");
+        let expected = fs::read_to_string(&sol_path).unwrap();
+
+        let output = cmd(&binary_path, "check", &tree_path, &[]);
+        assert!(output.status.success(), "{tree_name} failed the check");
+        assert_eq!(expected, fs::read_to_string(&sol_path).unwrap());
+    }
+}
+
+#[test]
+fn checks_names_scaffolded_before_capitalizing_punctuated_words() {
+    let cwd = env::current_dir().unwrap();
+    let binary_path = get_binary_path();
+    let tree_path =
+        cwd.join("tests").join("check").join("capitalized_names.tree");
+
+    // The Solidity file was scaffolded when `"amount"` wasn't capitalized.
+    let output = cmd(&binary_path, "check", &tree_path, &[]);
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(!output.status.success());
+    assert!(stderr
+        .contains(r#"function "test_WhenAmountIsZero" is missing in .sol"#));
+
+    let output = cmd(&binary_path, "check", &tree_path, &["--fix", "--stdout"]);
+    let actual = String::from_utf8(output.stdout).unwrap();
+    assert!(actual.contains("function test_WhenAmountIsZero() external {"));
+    assert!(actual.contains("function test_WhenamountIsZero() external {"));
+}
+
+#[test]
+fn checks_modifier_order() {
+    let cwd = env::current_dir().unwrap();
+    let binary_path = get_binary_path();
+    let tree_path = cwd.join("tests").join("check").join("modifier_order.tree");
+
+    // Modifier order is only checked on demand.
+    let output = cmd(&binary_path, "check", &tree_path, &[]);
+    assert!(output.status.success());
+
+    let args = ["--check-modifier-order"];
+    let output = cmd(&binary_path, "check", &tree_path, &args);
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains(
+        r#"modifiers of function "test_WhenTheRecipientIsNotZero" are applied out of order"#
+    ));
+    assert!(stderr.contains(
+        r#"consider applying the modifiers as "givenTheCallerIsTheOwner whenTheAmountIsNotZero""#
+    ));
+    assert!(stderr.contains("modifier_order.t.sol:13"));
+    assert!(stderr.contains("1 check failed"));
+}
+
+#[test]
+fn exits_successfully_with_exit_zero() {
+    let cwd = env::current_dir().unwrap();
+    let binary_path = get_binary_path();
+    let tree_path = cwd.join("tests").join("check").join("comment_order.tree");
+
+    let args = ["--check-comment-order"];
+    let output = cmd(&binary_path, "check", &tree_path, &args);
+    assert!(!output.status.success());
+
+    let args = ["--check-comment-order", "--exit-zero"];
+    let output = cmd(&binary_path, "check", &tree_path, &args);
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("1 check failed"));
+    assert!(output.status.success());
+}
+
+#[test]
+fn stops_at_the_first_failing_file_with_fail_fast() {
+    let cwd = env::current_dir().unwrap();
+    let binary_path = get_binary_path();
+    let tree_path = cwd.join("tests").join("check").join("comment_order.tree");
+    let other_path = cwd.join("tests").join("check").join("issue_81.tree");
+    let other_path = other_path.to_str().unwrap();
+
+    let args = [other_path, "--check-comment-order"];
+    let output = cmd(&binary_path, "check", &tree_path, &args);
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("issue_81.tree"));
+
+    let args = [other_path, "--check-comment-order", "--fail-fast"];
+    let output = cmd(&binary_path, "check", &tree_path, &args);
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(!output.status.success());
+    assert!(stderr.contains("1 file left unchecked"));
+    assert!(stderr.contains("1 check failed"));
+    assert!(!stderr.contains("issue_81.tree"));
+}
+
+#[test]
+fn checks_pragma_mismatch() {
+    let cwd = env::current_dir().unwrap();
+    let binary_path = get_binary_path();
+    let tree_path =
+        cwd.join("tests").join("check").join("pragma_mismatch.tree");
+
+    // The pragma is only checked on demand.
+    let output = cmd(&binary_path, "check", &tree_path, &[]);
+    assert!(output.status.success());
+
+    let args = ["--check-pragma", "-s", "0.8.20"];
+    let output = cmd(&binary_path, "check", &tree_path, &args);
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains(
+        r#"pragma "^0.8.0" doesn't match the configured version "0.8.20""#
+    ));
+    assert!(stderr.contains("pragma_mismatch.t.sol:2"));
+
+    let args = ["--check-pragma", "--pragma-style", "caret"];
+    let output = cmd(&binary_path, "check", &tree_path, &args);
+    assert!(output.status.success());
+}
+
+#[test]
+fn checks_reverts_last() {
+    let cwd = env::current_dir().unwrap();
+    let binary_path = get_binary_path();
+    let tree_path = cwd.join("tests").join("check").join("reverts_last.tree");
+
+    let output = cmd(&binary_path, "check", &tree_path, &["--reverts-last"]);
+    assert!(output.status.success());
+
+    let output = cmd(&binary_path, "check", &tree_path, &[]);
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains(
+        "incorrect position for function `test_RevertWhen_TheCallerIsNotTheOwner`"
+    ));
+    assert!(stderr.contains("3 checks failed"));
+}
+
+#[test]
+fn checks_trees_grouped_by_keyword() {
+    let cwd = env::current_dir().unwrap();
+    let binary_path = get_binary_path();
+    let tree_path =
+        cwd.join("tests").join("check").join("group_by_keyword.tree");
+
+    let output =
+        cmd(&binary_path, "check", &tree_path, &["--group-by-keyword"]);
+    assert!(output.status.success());
+
+    let args = ["--fix", "--stdout", "--group-by-keyword"];
+    let output = cmd(&binary_path, "check", &tree_path, &args);
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let sol_path = tree_path.with_extension("t.sol");
+    assert!(stdout.contains(&fs::read_to_string(sol_path).unwrap()));
+
+    let output = cmd(&binary_path, "check", &tree_path, &[]);
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains(
+        "incorrect position for function `test_WhenTheCallerDeposits`"
+    ));
+}
+
+#[test]
+fn checks_roots_named_like_paths() {
+    let binary_path = get_binary_path();
+    let tmp_path = env::temp_dir().join("bulloak_check_root_as_path");
+    let _ = fs::remove_dir_all(&tmp_path);
+    fs::create_dir_all(&tmp_path).unwrap();
+    let tree_path = tmp_path.join("foo.tree");
+    fs::write(&tree_path, "token/Foo_Test\n└── it should work.\n").unwrap();
+    cmd(&binary_path, "scaffold", &tree_path, &["-w", "--root-as-path"]);
+
+    let output = cmd(&binary_path, "check", &tree_path, &["--root-as-path"]);
+    assert!(output.status.success());
+
+    let sol_path = tmp_path.join("token").join("Foo_Test.t.sol");
+    let expected = fs::read_to_string(&sol_path).unwrap();
+    let args = ["--fix", "--root-as-path"];
+    let output = cmd(&binary_path, "check", &tree_path, &args);
+    assert!(output.status.success());
+    assert_eq!(expected, fs::read_to_string(&sol_path).unwrap());
+
+    let output = cmd(&binary_path, "check", &tree_path, &[]);
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("the tree is missing its matching Solidity file"));
+
+    fs::remove_dir_all(&tmp_path).unwrap();
+}
+
+#[test]
+fn checks_trees_against_a_shared_sol_file() {
+    let cwd = env::current_dir().unwrap();
+    let binary_path = get_binary_path();
+    let tests_path = cwd.join("tests").join("check");
+    let tree_path = tests_path.join("shared_deposit.tree");
+    let other_tree = tests_path.join("shared_withdraw.tree");
+    let sol_path = tests_path.join("shared.t.sol");
+
+    let args =
+        [other_tree.to_str().unwrap(), "--sol", sol_path.to_str().unwrap()];
+    let output = cmd(&binary_path, "check", &tree_path, &args);
+    assert!(output.status.success());
+
+    let tree_path = tests_path.join("unsorted.tree");
+    let args = ["--sol", sol_path.to_str().unwrap()];
+    let output = cmd(&binary_path, "check", &tree_path, &args);
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr
+        .contains(r#"function "test_WhenFirstArgIsZero" is missing in .sol"#));
+    assert!(!output.status.success());
+}
+
+#[test]
+fn checks_only_function_names() {
+    let cwd = env::current_dir().unwrap();
+    let binary_path = get_binary_path();
+    let tests_path = cwd.join("tests").join("check");
+
+    for tree_name in ["comment_order.tree", "unsorted.tree"] {
+        let tree_path = tests_path.join(tree_name);
+        let output =
+            cmd(&binary_path, "check", &tree_path, &["--check-names-only"]);
+        assert!(output.status.success());
+    }
+
+    let tree_path = tests_path.join("extra_codegen_tree.tree");
+    let output =
+        cmd(&binary_path, "check", &tree_path, &["--check-names-only"]);
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr
+        .contains(r#"function "test_ShouldNeverRevert" is missing in .sol"#));
+    assert!(stderr.contains("2 checks failed"));
+}
+
+#[test]
+fn diffs_two_solidity_files_against_a_tree() {
+    let cwd = env::current_dir().unwrap();
+    let binary_path = get_binary_path();
+    let tree_path =
+        cwd.join("tests").join("check").join("invalid_sol_structure.tree");
+    let old = tree_path.with_extension("t.sol");
+    let new = env::temp_dir().join("bulloak_diff_sol.t.sol");
+    let scaffolded = cmd(&binary_path, "scaffold", &tree_path, &[]).stdout;
+    fs::write(&new, scaffolded).unwrap();
+
+    let (old, new) = (old.to_str().unwrap(), new.to_str().unwrap());
+    let output =
+        cmd(&binary_path, "check", &tree_path, &["--diff-sol", old, new]);
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(output.status.success());
+    assert!(stdout.contains(&format!("{old}: 4 issues")));
+    assert!(stdout.contains(&format!("{new}: 0 issues")));
+    assert!(stdout.contains(&format!("Only in {old}:")));
+    assert!(!stdout.contains(&format!("Only in {new}:")));
+
+    let output =
+        cmd(&binary_path, "check", &tree_path, &["--diff-sol", new, old]);
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(!output.status.success());
+    assert!(stderr.contains(&format!("{old} introduced 4 issues")));
+}
+
+#[test]
+fn skips_files_whose_tree_hash_matches() {
+    let cwd = env::current_dir().unwrap();
+    let binary_path = get_binary_path();
+    let tmp = env::temp_dir().join("bulloak_tree_hash");
+    fs::create_dir_all(&tmp).unwrap();
+    let tree_path = tmp.join("hashed.tree");
+    let sol_path = tmp.join("hashed.t.sol");
+    let tree = cwd.join("tests").join("check").join("extra_codegen_sol.tree");
+    fs::copy(tree, &tree_path).unwrap();
+
+    let scaffolded =
+        cmd(&binary_path, "scaffold", &tree_path, &["--tree-hash"]).stdout;
+    let scaffolded = String::from_utf8(scaffolded).unwrap();
+    assert!(scaffolded.contains("// @bulloak:tree-hash "));
+    // Move the last function to the top so that only the fast path lets the
+    // check pass.
+    let first = scaffolded.find("    function ").unwrap();
+    let start = scaffolded.rfind("    function ").unwrap();
+    let end = scaffolded[start..].find("}\n").unwrap() + start + 2;
+    let reordered = format!(
+        "{}{}\n{}{}",
+        &scaffolded[..first],
+        &scaffolded[start..end],
+        &scaffolded[first..start - 1],
+        &scaffolded[end..]
+    );
+    fs::write(&sol_path, &reordered).unwrap();
+
+    let output = cmd(&binary_path, "check", &tree_path, &[]);
+    assert!(output.status.success());
+
+    // Deleted functions are still reported on the fast path.
+    let removed = format!("{}{}", &scaffolded[..start], &scaffolded[end..]);
+    fs::write(&sol_path, removed).unwrap();
+    let output = cmd(&binary_path, "check", &tree_path, &[]);
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(!output.status.success());
+    assert!(stderr.contains("is missing in .sol"));
+
+    fs::write(&sol_path, &reordered).unwrap();
+    let tree = fs::read_to_string(&tree_path).unwrap();
+    fs::write(&tree_path, format!("{tree}\n")).unwrap();
+    let output = cmd(&binary_path, "check", &tree_path, &[]);
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(!output.status.success());
+    assert!(stderr.contains("incorrect position for function"));
+}
+
+#[test]
+fn prints_relative_paths() {
+    let cwd = env::current_dir().unwrap();
+    let binary_path = get_binary_path();
+    let tree_path =
+        cwd.join("tests").join("check").join("invalid_sol_structure.tree");
+
+    let output = cmd(&binary_path, "check", &tree_path, &[]);
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains(&cwd.display().to_string()));
+
+    let output = cmd(&binary_path, "check", &tree_path, &["--relative-paths"]);
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(!stderr.contains(&cwd.display().to_string()));
+    assert!(stderr.contains("tests/check/invalid_sol_structure.t.sol:"));
+}
+
+#[test]
+fn checks_file_names() {
+    let cwd = env::current_dir().unwrap();
+    let binary_path = get_binary_path();
+    let tree_path =
+        cwd.join("tests").join("check").join("extra_codegen_sol.tree");
+
+    let output = cmd(&binary_path, "check", &tree_path, &["--check-file-name"]);
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(!output.status.success());
+    assert!(stderr.contains(
+        r#"contract "HashPairTest" doesn't match the name of its file "extra_codegen_sol""#
+    ));
+    assert!(stderr
+        .contains(r#"consider renaming the file to "HashPairTest.t.sol""#));
+}
+
 #[test]
 fn checks_missing_sol_file() {
     let cwd = env::current_dir().unwrap();
@@ -216,6 +701,47 @@ contract ContractName {
     assert!(actual.contains("1 issue fixed."));
 }
 
+#[test]
+fn lists_fixed_and_skipped_violations() {
+    let cwd = env::current_dir().unwrap();
+    let binary_path = get_binary_path();
+    let tests_path = cwd.join("tests").join("check");
+    let tree_path = tests_path.join("contract_names_mismatch.tree");
+    let other = tests_path.join("modifier_order.tree");
+
+    let args = [
+        other.to_str().unwrap(),
+        "--check-modifier-order",
+        "--fix",
+        "--stdout",
+    ];
+    let output = cmd(&binary_path, "check", &tree_path, &args);
+    let actual = String::from_utf8(output.stdout).unwrap();
+    assert!(actual.contains(
+        r#": contract "ContractName" is missing in .sol -- found "ADifferentName" instead"#
+    ));
+    assert!(actual.contains(
+        r#": modifiers of function "test_WhenTheRecipientIsNotZero" are applied out of order"#
+    ));
+    assert!(actual.contains("1 issue fixed."));
+    assert!(actual.contains("1 issue can't be fixed automatically."));
+}
+
+#[test]
+fn fixes_pragma_mismatch() {
+    let cwd = env::current_dir().unwrap();
+    let binary_path = get_binary_path();
+    let tree_path =
+        cwd.join("tests").join("check").join("pragma_mismatch.tree");
+
+    let args = ["--check-pragma", "-s", "0.8.20", "--fix", "--stdout"];
+    let output = cmd(&binary_path, "check", &tree_path, &args);
+
+    let actual = String::from_utf8(output.stdout).unwrap();
+    assert!(actual.contains("pragma solidity 0.8.20;\n"));
+    assert!(actual.contains("1 issue fixed."));
+}
+
 #[test]
 fn fixes_contract_missing() {
     let cwd = env::current_dir().unwrap();
@@ -252,6 +778,31 @@ fn fixes_extra_codegen_tree() {
     assert!(actual.contains("2 issues fixed."));
 }
 
+#[test]
+fn backs_up_files_before_fixing_them() {
+    let cwd = env::current_dir().unwrap();
+    let binary_path = get_binary_path();
+    let tests_path = cwd.join("tests").join("check");
+    let tmp_path = env::temp_dir().join("bulloak_backup");
+    fs::create_dir_all(&tmp_path).unwrap();
+    let tree_path = tmp_path.join("extra_codegen_tree.tree");
+    let sol_path = tmp_path.join("extra_codegen_tree.t.sol");
+    fs::copy(tests_path.join("extra_codegen_tree.tree"), &tree_path).unwrap();
+    fs::copy(tests_path.join("extra_codegen_tree.t.sol"), &sol_path).unwrap();
+    let original = fs::read_to_string(&sol_path).unwrap();
+
+    let output = cmd(&binary_path, "check", &tree_path, &["--fix", "--backup"]);
+    assert!(output.status.success());
+
+    let backup =
+        fs::read_to_string(tmp_path.join("extra_codegen_tree.t.sol.bak"));
+    assert_eq!(backup.unwrap(), original);
+    let fixed = fs::read_to_string(&sol_path).unwrap();
+    assert!(fixed.contains("test_ShouldNeverRevert"));
+
+    fs::remove_dir_all(&tmp_path).unwrap();
+}
+
 #[test]
 fn fixes_extra_fn_plus_wrong_order() {
     let cwd = env::current_dir().unwrap();
@@ -460,3 +1011,64 @@ contract CancelTest {
     assert!(actual.contains(expected));
     assert!(actual.contains("4 issues fixed."));
 }
+
+#[test]
+fn prunes_stale_tests_with_fix() {
+    let cwd = env::current_dir().unwrap();
+    let binary_path = get_binary_path();
+    let tree_path = cwd.join("tests").join("check").join("strict_extras.tree");
+
+    let output = cmd(
+        &binary_path,
+        "check",
+        &tree_path,
+        &["--fix", "--stdout", "--prune", "--yes"],
+    );
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(!stdout.contains("test_WhenFirstArgIsBiggerThanSecondArg()"));
+    assert!(!stdout.contains("This test is stale"));
+    assert!(!stdout.contains("along with these comments"));
+    assert!(stdout.contains("test_WhenFirstArgIsSmallerThanSecondArg()"));
+    assert!(stdout.contains("hashHelper"));
+    assert!(stdout.contains("1 issue fixed."));
+
+    let output = cmd(
+        &binary_path,
+        "check",
+        &tree_path,
+        &["--fix", "--stdout", "--prune"],
+    );
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("test_WhenFirstArgIsBiggerThanSecondArg()"));
+    assert!(stdout.contains("1 issue can't be fixed automatically."));
+}
+
+#[test]
+fn reports_violations_as_json() {
+    let cwd = env::current_dir().unwrap();
+    let binary_path = get_binary_path();
+    let tree_path = cwd.join("tests").join("check").join("strict_extras.tree");
+
+    let args = ["--strict-extras", "--format", "json"];
+    let output = cmd(&binary_path, "check", &tree_path, &args);
+    assert!(!output.status.success());
+    let report: serde_json::Value =
+        serde_json::from_slice(&output.stdout).unwrap();
+    let violations = report.as_array().unwrap();
+    assert_eq!(violations.len(), 1);
+    let violation = &violations[0];
+    assert_eq!(violation["type"], "extra_function");
+    assert_eq!(violation["code"], "B507");
+    assert_eq!(violation["tree"], tree_path.to_str().unwrap());
+    assert_eq!(
+        violation["file"],
+        tree_path.with_extension("t.sol").to_str().unwrap()
+    );
+    assert_eq!(violation["line"], 15);
+    assert_eq!(violation["column"], 3);
+    assert_eq!(violation["fixable"], false);
+
+    let output = cmd(&binary_path, "check", &tree_path, &["--format", "json"]);
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8(output.stdout).unwrap().trim(), "[]");
+}
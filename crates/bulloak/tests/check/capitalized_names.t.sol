@@ -0,0 +1,9 @@
+// SPDX-License-Identifier: UNLICENSED
+pragma solidity 0.8.0;
+
+contract TransferTest {
+    function test_WhenamountIsZero() external {
+        // it should not transfer anything.
+    }
+}
+
@@ -0,0 +1,11 @@
+// SPDX-License-Identifier: UNLICENSED
+pragma solidity 0.8.0;
+
+contract CommentOrderTest {
+    function test_WhenTheAmountIsNotZero() external {
+        // Arrange the transfer.
+        // it should emit a {Transfer} event.
+        // it should transfer the amount.
+        // it should return true.
+    }
+}
@@ -0,0 +1,14 @@
+// SPDX-License-Identifier: UNLICENSED
+pragma solidity 0.8.0;
+
+contract GroupByKeywordTest {
+    // ==================== GIVEN ====================
+    function test_GivenTheVaultIsPaused() external {
+        // it should reject deposits.
+    }
+
+    // ==================== WHEN ====================
+    function test_WhenTheCallerDeposits() external {
+        // it should mint shares.
+    }
+}
@@ -0,0 +1,17 @@
+// SPDX-License-Identifier: UNLICENSED
+pragma solidity 0.8.0;
+
+contract ModifierOrderTest {
+    modifier givenTheCallerIsTheOwner() {
+        _;
+    }
+
+    modifier whenTheAmountIsNotZero() {
+        _;
+    }
+
+    function test_WhenTheRecipientIsNotZero() external whenTheAmountIsNotZero givenTheCallerIsTheOwner {
+        // it should transfer the amount.
+    }
+}
+
@@ -0,0 +1,8 @@
+// SPDX-License-Identifier: UNLICENSED
+pragma solidity ^0.8.0;
+
+contract PragmaTest {
+    function test_ShouldWork() external {
+        // it should work.
+    }
+}
@@ -0,0 +1,25 @@
+// SPDX-License-Identifier: UNLICENSED
+pragma solidity 0.8.0;
+
+contract RevertsLastTest {
+    modifier whenTheCallerIsTheOwner() {
+        _;
+    }
+
+    function test_WhenTheAmountIsValid() external whenTheCallerIsTheOwner {
+        // it should transfer the amount.
+    }
+
+    function test_RevertWhen_TheCallerIsNotTheOwner() external {
+        // it should revert.
+    }
+
+    function test_RevertWhen_TheAmountIsZero() external whenTheCallerIsTheOwner {
+        // it should revert.
+    }
+
+    function test_RevertWhen_TheAmountIsTooBig() external whenTheCallerIsTheOwner {
+        // it should revert.
+    }
+}
+
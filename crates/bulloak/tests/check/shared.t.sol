@@ -0,0 +1,20 @@
+// SPDX-License-Identifier: UNLICENSED
+pragma solidity 0.8.0;
+
+contract VaultTest {
+    function test_RevertWhen_WithdrawingMoreThanTheBalance() external {
+        // it should revert.
+    }
+
+    function test_WhenWithdrawingAtMostTheBalance() external {
+        // it should decrease the balance.
+    }
+
+    function test_RevertWhen_DepositingZero() external {
+        // it should revert.
+    }
+
+    function test_WhenDepositingAPositiveAmount() external {
+        // it should increase the balance.
+    }
+}
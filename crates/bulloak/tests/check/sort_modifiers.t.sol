@@ -0,0 +1,25 @@
+// SPDX-License-Identifier: UNLICENSED
+pragma solidity 0.8.0;
+
+contract Token {
+    modifier givenTheSpenderIsNotBlocked() {
+        _;
+    }
+
+    modifier whenTheSenderIsNotBlocked() {
+        _;
+    }
+
+    function test_TransferRevertWhen_TheAmountIsZero() external whenTheSenderIsNotBlocked {
+        // It should revert.
+    }
+
+    function test_TransferWhenTheAmountIsNotZero() external whenTheSenderIsNotBlocked {
+        // It should emit a transfer event.
+    }
+
+    function test_ApproveRevertWhen_TheAmountIsZero() external givenTheSpenderIsNotBlocked {
+        // It should revert.
+    }
+}
+
@@ -0,0 +1,24 @@
+// SPDX-License-Identifier: UNLICENSED
+pragma solidity 0.8.0;
+
+contract HashPairTest {
+    modifier whenFirstArgIsSmallerThanSecondArg() {
+        _;
+    }
+
+    modifier whenFirstArgIsBiggerThanSecondArg() {
+        _;
+    }
+
+    modifier onlyOwner() {
+        _;
+    }
+
+    function test_WhenFirstArgIsZero() external whenFirstArgIsSmallerThanSecondArg {
+        // It should do something.
+    }
+
+    function test_WhenFirstArgIsNotZero() external whenFirstArgIsSmallerThanSecondArg {
+        // It should match the result of `keccak256(abi.encodePacked(a,b))`.
+    }
+}
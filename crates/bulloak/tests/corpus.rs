@@ -0,0 +1,38 @@
+//! Integration tests for `bulloak corpus`.
+
+use std::{env, fs};
+
+use common::{cmd, get_binary_path};
+use pretty_assertions::assert_eq;
+
+mod common;
+
+#[test]
+fn reports_trees_that_fail_to_parse() {
+    let cwd = env::current_dir().unwrap();
+    let binary_path = get_binary_path();
+    let dir = cwd.join("tests").join("check");
+    let report_path = env::temp_dir().join("bulloak_corpus_report.json");
+
+    let report = report_path.to_str().unwrap();
+    let output = cmd(&binary_path, "corpus", &dir, &["--output", report]);
+    assert!(output.status.success());
+
+    let report: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&report_path).unwrap())
+            .unwrap();
+    fs::remove_file(&report_path).unwrap();
+    let failures = report.as_array().unwrap();
+    assert_eq!(failures.len(), 1);
+    let failure = &failures[0];
+    assert_eq!(
+        failure["path"].as_str().unwrap(),
+        dir.join("invalid.tree").to_str().unwrap()
+    );
+    assert_eq!(
+        failure["text"].as_str().unwrap(),
+        fs::read_to_string(dir.join("invalid.tree")).unwrap()
+    );
+    assert_eq!(failure["diagnostics"][0]["code"], "B101");
+    assert_eq!(failure["diagnostics"][0]["line"], 3);
+}
@@ -0,0 +1,40 @@
+//! Integration tests for `bulloak init`.
+
+use std::{env, fs};
+
+use common::{cmd, get_binary_path};
+use pretty_assertions::assert_eq;
+
+mod common;
+
+#[test]
+fn writes_a_starter_tree() {
+    let binary_path = get_binary_path();
+    let tmp_path = env::temp_dir().join("bulloak_init");
+    fs::create_dir_all(&tmp_path).unwrap();
+    let name = tmp_path.join("Vault");
+    let tree_path = tmp_path.join("Vault.tree");
+    let _ = fs::remove_file(&tree_path);
+
+    let output = cmd(&binary_path, "init", &name, &[]);
+    assert!(output.status.success());
+    let tree = fs::read_to_string(&tree_path).unwrap();
+    assert!(tree.starts_with("Vault\n"));
+
+    // The starter tree can be scaffolded right away.
+    let output = cmd(&binary_path, "scaffold", &tree_path, &[]);
+    let actual = String::from_utf8(output.stdout).unwrap();
+    assert!(actual.contains("contract Vault {"));
+
+    // Existing files are only overwritten with `--force`.
+    fs::write(&tree_path, "Edited").unwrap();
+    let output = cmd(&binary_path, "init", &name, &[]);
+    assert!(!output.status.success());
+    assert_eq!("Edited", fs::read_to_string(&tree_path).unwrap());
+
+    let output = cmd(&binary_path, "init", &name, &["--force"]);
+    assert!(output.status.success());
+    assert_eq!(tree, fs::read_to_string(&tree_path).unwrap());
+
+    fs::remove_dir_all(&tmp_path).unwrap();
+}
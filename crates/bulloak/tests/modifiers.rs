@@ -0,0 +1,41 @@
+//! Integration tests for `bulloak modifiers`.
+
+use std::env;
+
+use common::{cmd, get_binary_path};
+use pretty_assertions::assert_eq;
+
+mod common;
+
+#[test]
+fn prints_modifiers_in_application_order() {
+    let cwd = env::current_dir().unwrap();
+    let binary_path = get_binary_path();
+    let tree_path =
+        cwd.join("tests").join("scaffold").join("modifier_comments.tree");
+
+    let output = cmd(&binary_path, "modifiers", &tree_path, &[]);
+    assert!(output.status.success());
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let file = tree_path.display();
+    let expected = format!(
+        "{file}:3 test_RevertWhen_TheCallerIsNotTheOwner givenTheVaultIsOpen
+{file}:6 test_RevertWhen_TheAmountIsZero givenTheVaultIsOpen -> whenTheCallerIsTheOwner
+{file}:8 test_WhenTheAmountIsNotZero givenTheVaultIsOpen -> whenTheCallerIsTheOwner
+"
+    );
+    assert_eq!(expected, actual);
+}
+
+#[test]
+fn reports_invalid_trees() {
+    let cwd = env::current_dir().unwrap();
+    let binary_path = get_binary_path();
+    let tree_path = cwd.join("tests").join("check").join("invalid.tree");
+
+    let output = cmd(&binary_path, "modifiers", &tree_path, &[]);
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("invalid.tree"));
+}
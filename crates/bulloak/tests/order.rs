@@ -0,0 +1,51 @@
+//! Integration tests for `bulloak order`.
+
+use std::{env, fs};
+
+use common::{cmd, get_binary_path};
+
+mod common;
+
+#[test]
+fn reorders_functions_to_match_the_tree() {
+    let cwd = env::current_dir().unwrap();
+    let binary_path = get_binary_path();
+    let tests_path = cwd.join("tests").join("check");
+    let tmp = env::temp_dir().join("bulloak_order");
+    fs::create_dir_all(&tmp).unwrap();
+    let tree_path = tmp.join("unsorted.tree");
+    let sol_path = tmp.join("unsorted.t.sol");
+    fs::copy(tests_path.join("unsorted.tree"), &tree_path).unwrap();
+    fs::copy(tests_path.join("unsorted.t.sol"), &sol_path).unwrap();
+
+    let output = cmd(&binary_path, "order", &tree_path, &[]);
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(output.status.success());
+    assert!(stdout.contains("Moved 1 function"));
+
+    let output = cmd(&binary_path, "check", &tree_path, &[]);
+    assert!(output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(!stderr.contains("incorrect position"));
+
+    let output = cmd(&binary_path, "order", &tree_path, &[]);
+    assert!(output.status.success());
+    assert!(output.stdout.is_empty());
+}
+
+#[test]
+fn skips_files_with_missing_functions() {
+    let cwd = env::current_dir().unwrap();
+    let binary_path = get_binary_path();
+    let tree_path =
+        cwd.join("tests").join("check").join("invalid_sol_structure.tree");
+    let before = fs::read_to_string(tree_path.with_extension("t.sol")).unwrap();
+
+    let output = cmd(&binary_path, "order", &tree_path, &[]);
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(!output.status.success());
+    assert!(stderr.contains("run `bulloak check --fix"));
+
+    let after = fs::read_to_string(tree_path.with_extension("t.sol")).unwrap();
+    assert_eq!(before, after);
+}
@@ -1,4 +1,10 @@
-use std::{env, fs};
+//! Integration tests for `bulloak scaffold`.
+
+use std::{
+    env, fs,
+    io::Write,
+    process::{Command, Stdio},
+};
 
 use common::{cmd, get_binary_path};
 use owo_colors::OwoColorize;
@@ -19,6 +25,15 @@ fn scaffolds_trees() {
         "hash_pair.tree",
         "revert_when.tree",
         "spurious_comments.tree",
+        "assembly.tree",
+        "gas.tree",
+        "descriptions.tree",
+        "value_sets.tree",
+        "nested_value_sets.tree",
+        "warp_roll.tree",
+        "types.tree",
+        "ascii_connectors.tree",
+        "comments.tree",
     ];
 
     for tree_name in trees {
@@ -35,6 +50,34 @@ fn scaffolds_trees() {
     }
 }
 
+#[test]
+fn scaffolds_files_in_the_order_they_are_passed() {
+    let cwd = env::current_dir().unwrap();
+    let binary_path = get_binary_path();
+    let tests_path = cwd.join("tests").join("scaffold");
+    let trees = ["complex.tree", "basic.tree", "hash_pair.tree"];
+
+    let args: Vec<_> = trees[1..].iter().map(|t| tests_path.join(t)).collect();
+    let args: Vec<_> = args.iter().map(|a| a.to_str().unwrap()).collect();
+    let output =
+        cmd(&binary_path, "scaffold", &tests_path.join(trees[0]), &args);
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected: Vec<_> = trees
+        .iter()
+        .map(|t| {
+            let sol = tests_path.join(t).with_extension("t.sol");
+            fs::read_to_string(sol).unwrap().trim().to_owned()
+        })
+        .collect();
+    let actual: Vec<_> = actual
+        .split("// SPDX-License-Identifier")
+        .skip(1)
+        .map(|sol| format!("// SPDX-License-Identifier{}", sol.trim_end()))
+        .collect();
+    assert_eq!(expected, actual);
+}
+
 #[test]
 fn scaffolds_trees_with_vm_skip() {
     let cwd = env::current_dir().unwrap();
@@ -90,6 +133,984 @@ fn scaffolds_trees_with_skip_modifiers() {
     }
 }
 
+#[test]
+fn scaffolds_trees_with_pragma_style() {
+    let cwd = env::current_dir().unwrap();
+    let binary_path = get_binary_path();
+    let tree_path = cwd.join("tests").join("scaffold").join("basic.tree");
+    let styles = [
+        ("verbatim", "pragma solidity 0.8.0;"),
+        ("exact", "pragma solidity =0.8.0;"),
+        ("caret", "pragma solidity ^0.8.0;"),
+    ];
+
+    for (style, expected) in styles {
+        let args = ["--pragma-style", style];
+        let output = cmd(&binary_path, "scaffold", &tree_path, &args);
+        let actual = String::from_utf8(output.stdout).unwrap();
+
+        assert!(actual.contains(expected));
+    }
+}
+
+#[test]
+fn scaffolds_trees_with_descriptions_as_todos() {
+    let cwd = env::current_dir().unwrap();
+    let binary_path = get_binary_path();
+    let tests_path = cwd.join("tests").join("scaffold");
+    let tree_path = tests_path.join("descriptions.tree");
+    let args = ["--descriptions-as-todos"];
+
+    let output = cmd(&binary_path, "scaffold", &tree_path, &args);
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected =
+        fs::read_to_string(tests_path.join("descriptions_todos.t.sol"))
+            .unwrap();
+    assert_eq!(expected.trim(), actual.trim());
+}
+
+#[test]
+fn scaffolds_trees_without_pragma() {
+    let cwd = env::current_dir().unwrap();
+    let binary_path = get_binary_path();
+    let tree_path = cwd.join("tests").join("scaffold").join("basic.tree");
+
+    let output = cmd(&binary_path, "scaffold", &tree_path, &["--no-pragma"]);
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    assert!(!actual.contains("pragma solidity"));
+    assert!(actual.starts_with(
+        "// SPDX-License-Identifier: UNLICENSED\ncontract HashPairTestSanitize {"
+    ));
+}
+
+#[test]
+fn scaffolds_trees_with_imports() {
+    let cwd = env::current_dir().unwrap();
+    let binary_path = get_binary_path();
+    let tree_path = cwd.join("tests").join("scaffold").join("basic.tree");
+    let args = ["--import", "test/Constants.sol", "--import", "test/Utils.sol"];
+
+    let output = cmd(&binary_path, "scaffold", &tree_path, &args);
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    assert!(actual.contains(
+        "pragma solidity 0.8.0;\n\nimport \"test/Constants.sol\";\nimport \"test/Utils.sol\";\n\ncontract"
+    ));
+}
+
+#[test]
+fn scaffolds_trees_with_labels() {
+    let cwd = env::current_dir().unwrap();
+    let binary_path = get_binary_path();
+    let tree_path = cwd.join("tests").join("scaffold").join("labels.tree");
+    let args = ["--label", "alice", "--label", "bob"];
+
+    let output = cmd(&binary_path, "scaffold", &tree_path, &args);
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected =
+        fs::read_to_string(tree_path.with_extension("t.sol")).unwrap();
+    assert_eq!(expected.trim(), actual.trim());
+}
+
+#[test]
+fn scaffolds_trees_with_body_templates() {
+    let cwd = env::current_dir().unwrap();
+    let binary_path = get_binary_path();
+    let tree_path =
+        cwd.join("tests").join("scaffold").join("body_templates.tree");
+    let args = [
+        "--body-template",
+        "test_Revert=vm.expectRevert();",
+        "--body-template",
+        r#"test_=emit log("{{name}}");"#,
+    ];
+
+    let output = cmd(&binary_path, "scaffold", &tree_path, &args);
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected =
+        fs::read_to_string(tree_path.with_extension("t.sol")).unwrap();
+    assert_eq!(expected.trim(), actual.trim());
+}
+
+#[test]
+fn scaffolds_tests_with_a_default_body_statement() {
+    let cwd = env::current_dir().unwrap();
+    let binary_path = get_binary_path();
+    let tree_path = cwd.join("tests").join("scaffold").join("basic.tree");
+
+    let args = ["--default-body", "_test();"];
+    let output = cmd(&binary_path, "scaffold", &tree_path, &args);
+    let actual = String::from_utf8(output.stdout).unwrap();
+    assert!(actual.contains(
+        "    function test_ShouldNeverRevert() external {\n        _test();\n        // It should never revert.\n"
+    ));
+}
+
+#[test]
+fn rejects_invalid_default_body_statements() {
+    let cwd = env::current_dir().unwrap();
+    let binary_path = get_binary_path();
+    let tree_path = cwd.join("tests").join("scaffold").join("basic.tree");
+
+    let args = ["--default-body", "foo("];
+    let output = cmd(&binary_path, "scaffold", &tree_path, &args);
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(!output.status.success());
+    assert!(stderr.contains("invalid Solidity statement: foo("));
+    assert!(!stderr.contains("panicked"));
+}
+
+#[test]
+fn rejects_invalid_import_paths() {
+    let cwd = env::current_dir().unwrap();
+    let binary_path = get_binary_path();
+    let tree_path = cwd.join("tests").join("scaffold").join("basic.tree");
+
+    for path in ["a\"b", "a\nb"] {
+        let output =
+            cmd(&binary_path, "scaffold", &tree_path, &["--import", path]);
+        let stderr = String::from_utf8(output.stderr).unwrap();
+        assert!(!output.status.success());
+        assert!(stderr.contains("invalid import path"));
+        assert!(!stderr.contains("panicked"));
+    }
+}
+
+#[test]
+fn scaffolds_trees_with_sorted_modifiers() {
+    let cwd = env::current_dir().unwrap();
+    let binary_path = get_binary_path();
+    let tree_path =
+        cwd.join("tests").join("scaffold").join("sort_modifiers.tree");
+
+    let output =
+        cmd(&binary_path, "scaffold", &tree_path, &["--sort-modifiers"]);
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected =
+        fs::read_to_string(tree_path.with_extension("t.sol")).unwrap();
+    assert_eq!(expected.trim(), actual.trim());
+}
+
+#[test]
+fn scaffolds_trees_with_natspec_directives() {
+    let cwd = env::current_dir().unwrap();
+    let binary_path = get_binary_path();
+    let tree_path = cwd.join("tests").join("scaffold").join("natspec.tree");
+
+    let output = cmd(&binary_path, "scaffold", &tree_path, &[]);
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected =
+        fs::read_to_string(tree_path.with_extension("t.sol")).unwrap();
+    assert_eq!(expected.trim(), actual.trim());
+}
+
+#[test]
+fn lists_trees() {
+    let cwd = env::current_dir().unwrap();
+    let binary_path = get_binary_path();
+    let tree_path = cwd.join("tests").join("scaffold").join("hash_pair.tree");
+
+    let output = cmd(&binary_path, "scaffold", &tree_path, &["--list-trees"]);
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let file = tree_path.display();
+    let expected = format!(
+        "{file}:1-6 Utils::hashPair\n{file}:9-14 Utils::min\n{file}:17-22 Utils::max\n"
+    );
+    assert_eq!(expected, actual);
+}
+
+#[test]
+fn scaffolds_trees_with_spec_refs() {
+    let cwd = env::current_dir().unwrap();
+    let binary_path = get_binary_path();
+    let tree_path = cwd.join("tests").join("scaffold").join("hash_pair.tree");
+
+    let output = cmd(&binary_path, "scaffold", &tree_path, &["--spec-refs"]);
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let file = tree_path.display();
+    assert!(actual.contains(&format!(
+        "    /// @dev spec: {file}:L2\n    function test_HashPairShouldNeverRevert()"
+    )));
+    // Lines are relative to the whole file, even with multiple trees.
+    assert!(actual.contains(&format!(
+        "    /// @dev spec: {file}:L21\n    function test_MaxWhenFirstArgIsBiggerThanSecondArg()"
+    )));
+}
+
+#[test]
+fn scaffolds_trees_with_fuzz_markers() {
+    let cwd = env::current_dir().unwrap();
+    let binary_path = get_binary_path();
+    let tree_path = cwd.join("tests").join("scaffold").join("fuzz.tree");
+
+    let output = cmd(&binary_path, "scaffold", &tree_path, &[]);
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected =
+        fs::read_to_string(tree_path.with_extension("t.sol")).unwrap();
+    assert_eq!(expected.trim(), actual.trim());
+
+    let output = cmd(
+        &binary_path,
+        "scaffold",
+        &tree_path,
+        &["--fuzz-prefix", "testFuzzing_"],
+    );
+    let actual = String::from_utf8(output.stdout).unwrap();
+    assert!(
+        actual.contains("function testFuzzing_RevertWhen_TheAmountIsZero()")
+    );
+}
+
+#[test]
+fn scaffolds_fuzz_tests_with_params() {
+    let cwd = env::current_dir().unwrap();
+    let binary_path = get_binary_path();
+    let tree_path = cwd.join("tests").join("scaffold").join("fuzz_params.tree");
+
+    let output = cmd(&binary_path, "scaffold", &tree_path, &[]);
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected =
+        fs::read_to_string(tree_path.with_extension("t.sol")).unwrap();
+    assert_eq!(expected.trim(), actual.trim());
+}
+
+#[test]
+fn scaffolds_trees_with_keyword_aliases() {
+    let cwd = env::current_dir().unwrap();
+    let binary_path = get_binary_path();
+    let tree_path =
+        cwd.join("tests").join("scaffold").join("keyword_aliases.tree");
+
+    let output = cmd(
+        &binary_path,
+        "scaffold",
+        &tree_path,
+        &["--keyword-aliases", "if=when,scenario=given"],
+    );
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected =
+        fs::read_to_string(tree_path.with_extension("t.sol")).unwrap();
+    assert_eq!(expected.trim(), actual.trim());
+}
+
+#[test]
+fn scaffolds_fuzz_tests_with_typed_params() {
+    let cwd = env::current_dir().unwrap();
+    let binary_path = get_binary_path();
+    let tree_path =
+        cwd.join("tests").join("scaffold").join("typed_fuzz_params.tree");
+
+    let output = cmd(&binary_path, "scaffold", &tree_path, &[]);
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected =
+        fs::read_to_string(tree_path.with_extension("t.sol")).unwrap();
+    assert_eq!(expected.trim(), actual.trim());
+}
+
+#[test]
+fn scaffolds_invariant_tests_with_setup() {
+    let cwd = env::current_dir().unwrap();
+    let binary_path = get_binary_path();
+    let tree_path = cwd.join("tests").join("scaffold").join("invariant.tree");
+
+    let output = cmd(&binary_path, "scaffold", &tree_path, &["--emit-setup"]);
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected =
+        fs::read_to_string(tree_path.with_extension("t.sol")).unwrap();
+    assert_eq!(expected.trim(), actual.trim());
+}
+
+#[test]
+fn scaffolds_trees_with_modifier_comments() {
+    let cwd = env::current_dir().unwrap();
+    let binary_path = get_binary_path();
+    let tree_path =
+        cwd.join("tests").join("scaffold").join("modifier_comments.tree");
+
+    let output =
+        cmd(&binary_path, "scaffold", &tree_path, &["--modifier-comments"]);
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected =
+        fs::read_to_string(tree_path.with_extension("t.sol")).unwrap();
+    assert_eq!(expected.trim(), actual.trim());
+}
+
+#[test]
+fn scaffolds_trees_with_smart_descriptions() {
+    let cwd = env::current_dir().unwrap();
+    let binary_path = get_binary_path();
+    let tree_path =
+        cwd.join("tests").join("scaffold").join("smart_descriptions.tree");
+
+    let output =
+        cmd(&binary_path, "scaffold", &tree_path, &["--smart-descriptions"]);
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected =
+        fs::read_to_string(tree_path.with_extension("t.sol")).unwrap();
+    assert_eq!(expected.trim(), actual.trim());
+}
+
+#[test]
+fn scaffolds_trees_with_comments_above() {
+    let cwd = env::current_dir().unwrap();
+    let binary_path = get_binary_path();
+    let tree_path =
+        cwd.join("tests").join("scaffold").join("comments_above.tree");
+
+    let output = cmd(
+        &binary_path,
+        "scaffold",
+        &tree_path,
+        &["--comments-above", "--vm-skip"],
+    );
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected =
+        fs::read_to_string(tree_path.with_extension("t.sol")).unwrap();
+    assert_eq!(expected.trim(), actual.trim());
+}
+
+#[test]
+fn scaffolds_trees_with_generated_markers() {
+    let cwd = env::current_dir().unwrap();
+    let binary_path = get_binary_path();
+    let tree_path = cwd.join("tests").join("scaffold").join("basic.tree");
+
+    let output =
+        cmd(&binary_path, "scaffold", &tree_path, &["--mark-generated"]);
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    assert_eq!(actual.matches("    // @bulloak:generated\n").count(), 5);
+    assert!(actual.contains(
+        "    // @bulloak:generated\n    function test_ShouldNeverRevert() external {\n"
+    ));
+}
+
+#[test]
+fn scaffolds_payable_tests_with_receive() {
+    let cwd = env::current_dir().unwrap();
+    let binary_path = get_binary_path();
+    let tree_path = cwd.join("tests").join("scaffold").join("payable.tree");
+
+    let output = cmd(&binary_path, "scaffold", &tree_path, &["--emit-receive"]);
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected =
+        fs::read_to_string(tree_path.with_extension("t.sol")).unwrap();
+    assert_eq!(expected.trim(), actual.trim());
+}
+
+#[test]
+fn scaffolds_console_logs_for_traced_actions() {
+    let cwd = env::current_dir().unwrap();
+    let binary_path = get_binary_path();
+    let tree_path = cwd.join("tests").join("scaffold").join("trace.tree");
+
+    let output = cmd(&binary_path, "scaffold", &tree_path, &[]);
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected =
+        fs::read_to_string(tree_path.with_extension("t.sol")).unwrap();
+    assert_eq!(expected.trim(), actual.trim());
+}
+
+#[test]
+fn scaffolds_evm_version_from_root_marker() {
+    let cwd = env::current_dir().unwrap();
+    let binary_path = get_binary_path();
+    let tree_path = cwd.join("tests").join("scaffold").join("evm_version.tree");
+
+    let output = cmd(&binary_path, "scaffold", &tree_path, &[]);
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected =
+        fs::read_to_string(tree_path.with_extension("t.sol")).unwrap();
+    assert_eq!(expected.trim(), actual.trim());
+}
+
+#[test]
+fn scaffolds_contracts_extending_base_trees() {
+    let cwd = env::current_dir().unwrap();
+    let binary_path = get_binary_path();
+    let tree_path = cwd.join("tests").join("scaffold").join("extends.tree");
+
+    let output = cmd(&binary_path, "scaffold", &tree_path, &[]);
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected =
+        fs::read_to_string(tree_path.with_extension("t.sol")).unwrap();
+    assert_eq!(expected.trim(), actual.trim());
+}
+
+#[test]
+fn errors_when_trees_extend_themselves() {
+    let binary_path = get_binary_path();
+    let dir = env::temp_dir().join("bulloak_extends");
+    fs::create_dir_all(&dir).unwrap();
+    let tree_path = dir.join("Cycle.tree");
+    fs::write(&tree_path, "@extends Cycle.tree\nCycle\n└── it works.\n")
+        .unwrap();
+
+    let output = cmd(&binary_path, "scaffold", &tree_path, &[]);
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("extends itself through `@extends`"));
+}
+
+#[test]
+fn warns_about_trees_that_produce_empty_contracts() {
+    let binary_path = get_binary_path();
+    let dir = env::temp_dir().join("bulloak_empty_contract");
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("Base.tree"), "Base\n└── it works.\n").unwrap();
+    let tree_path = dir.join("Empty.tree");
+    fs::write(&tree_path, "@extends Base.tree\nEmpty\n└── it works.\n")
+        .unwrap();
+
+    let output = cmd(&binary_path, "scaffold", &tree_path, &[]);
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(output.status.success());
+    assert!(stderr.contains("contract \"Empty\" has no functions"));
+
+    let tree_path = dir.join("Base.tree");
+    let output = cmd(&binary_path, "scaffold", &tree_path, &[]);
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(!stderr.contains("has no functions"));
+}
+
+#[test]
+fn scaffolds_comments_with_forgefmt_directives() {
+    let cwd = env::current_dir().unwrap();
+    let binary_path = get_binary_path();
+    let tree_path = cwd.join("tests").join("scaffold").join("basic.tree");
+
+    let output =
+        cmd(&binary_path, "scaffold", &tree_path, &["--forgefmt-disable"]);
+    let actual = String::from_utf8(output.stdout).unwrap();
+    assert!(actual.contains(
+        "        // forgefmt: disable-next-line\n        // It should never revert.\n"
+    ));
+}
+
+#[test]
+fn scaffolds_assertions_against_expected_values() {
+    let cwd = env::current_dir().unwrap();
+    let binary_path = get_binary_path();
+    let tree_path = cwd.join("tests").join("scaffold").join("assertions.tree");
+
+    let output = cmd(&binary_path, "scaffold", &tree_path, &["--assertions"]);
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected =
+        fs::read_to_string(tree_path.with_extension("t.sol")).unwrap();
+    assert_eq!(expected.trim(), actual.trim());
+}
+
+#[test]
+fn scaffolds_no_revert_checks() {
+    let cwd = env::current_dir().unwrap();
+    let binary_path = get_binary_path();
+    let tree_path = cwd.join("tests").join("scaffold").join("no_revert.tree");
+
+    let output = cmd(&binary_path, "scaffold", &tree_path, &["--no-revert"]);
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected =
+        fs::read_to_string(tree_path.with_extension("t.sol")).unwrap();
+    assert_eq!(expected.trim(), actual.trim());
+}
+
+#[test]
+fn scaffolds_tests_inheriting_from_a_test_base() {
+    let cwd = env::current_dir().unwrap();
+    let binary_path = get_binary_path();
+    let tree_path = cwd.join("tests").join("scaffold").join("test_base.tree");
+
+    let args =
+        ["--assertions", "--test-base", "PRBTest=@prb/test/src/PRBTest.sol"];
+    let output = cmd(&binary_path, "scaffold", &tree_path, &args);
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected =
+        fs::read_to_string(tree_path.with_extension("t.sol")).unwrap();
+    assert_eq!(expected.trim(), actual.trim());
+}
+
+#[test]
+fn scaffolds_expect_reverts_for_custom_errors() {
+    let cwd = env::current_dir().unwrap();
+    let binary_path = get_binary_path();
+    let tree_path =
+        cwd.join("tests").join("scaffold").join("expect_revert.tree");
+
+    let output =
+        cmd(&binary_path, "scaffold", &tree_path, &["--expect-revert"]);
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected =
+        fs::read_to_string(tree_path.with_extension("t.sol")).unwrap();
+    assert_eq!(expected.trim(), actual.trim());
+}
+
+#[test]
+fn scaffolds_tests_grouped_by_keyword() {
+    let cwd = env::current_dir().unwrap();
+    let binary_path = get_binary_path();
+    let tree_path =
+        cwd.join("tests").join("scaffold").join("group_by_keyword.tree");
+
+    let output =
+        cmd(&binary_path, "scaffold", &tree_path, &["--group-by-keyword"]);
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected =
+        fs::read_to_string(tree_path.with_extension("t.sol")).unwrap();
+    assert_eq!(expected.trim(), actual.trim());
+}
+
+#[test]
+fn scaffolds_experimental_pragmas() {
+    let cwd = env::current_dir().unwrap();
+    let binary_path = get_binary_path();
+    let tree_path = cwd.join("tests").join("scaffold").join("basic.tree");
+
+    let args = ["--experimental-pragma", "SMTChecker"];
+    let output = cmd(&binary_path, "scaffold", &tree_path, &args);
+    let actual = String::from_utf8(output.stdout).unwrap();
+    assert!(actual
+        .contains("pragma solidity 0.8.0;\npragma experimental SMTChecker;\n"));
+
+    let args = ["--experimental-pragma", "SMTChecker", "--no-pragma"];
+    let output = cmd(&binary_path, "scaffold", &tree_path, &args);
+    let actual = String::from_utf8(output.stdout).unwrap();
+    assert!(!actual.contains("pragma"));
+}
+
+#[test]
+fn scaffolds_solhint_disable_directives() {
+    let cwd = env::current_dir().unwrap();
+    let binary_path = get_binary_path();
+    let tree_path = cwd.join("tests").join("scaffold").join("basic.tree");
+
+    let args = ["--solhint-disable", "no-empty-blocks,func-name-mixedcase"];
+    let output = cmd(&binary_path, "scaffold", &tree_path, &args);
+    let actual = String::from_utf8(output.stdout).unwrap();
+    assert!(actual.starts_with(
+        "// SPDX-License-Identifier: UNLICENSED\n\
+         // solhint-disable no-empty-blocks, func-name-mixedcase\n\
+         pragma solidity 0.8.0;\n"
+    ));
+
+    let output = cmd(&binary_path, "scaffold", &tree_path, &[]);
+    let actual = String::from_utf8(output.stdout).unwrap();
+    assert!(!actual.contains("solhint-disable"));
+}
+
+#[test]
+fn scaffolds_tests_with_aaa_skeleton() {
+    let cwd = env::current_dir().unwrap();
+    let binary_path = get_binary_path();
+    let tree_path = cwd.join("tests").join("scaffold").join("aaa.tree");
+
+    let output = cmd(&binary_path, "scaffold", &tree_path, &["--aaa"]);
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected =
+        fs::read_to_string(tree_path.with_extension("t.sol")).unwrap();
+    assert_eq!(expected.trim(), actual.trim());
+}
+
+#[test]
+fn scaffolds_reverts_last() {
+    let cwd = env::current_dir().unwrap();
+    let binary_path = get_binary_path();
+    let tree_path =
+        cwd.join("tests").join("scaffold").join("reverts_last.tree");
+
+    let output = cmd(&binary_path, "scaffold", &tree_path, &["--reverts-last"]);
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected =
+        fs::read_to_string(tree_path.with_extension("t.sol")).unwrap();
+    assert_eq!(expected.trim(), actual.trim());
+}
+
+#[test]
+fn reports_artifacts_as_json() {
+    let cwd = env::current_dir().unwrap();
+    let binary_path = get_binary_path();
+    let tree_path = cwd.join("tests").join("scaffold").join("basic.tree");
+
+    let output =
+        cmd(&binary_path, "scaffold", &tree_path, &["--format", "json"]);
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    assert!(actual.starts_with(r#"{"input":"#));
+    assert!(actual.contains(r#""output":"#));
+    assert!(actual.contains(
+        r#""written":false,"contract":"HashPairTestSanitize","functions":4}"#
+    ));
+    assert!(!actual.contains("pragma"));
+}
+
+#[test]
+fn warns_when_function_names_are_too_long() {
+    let cwd = env::current_dir().unwrap();
+    let binary_path = get_binary_path();
+    let tree_path = cwd.join("tests").join("scaffold").join("basic.tree");
+
+    let output = cmd(&binary_path, "scaffold", &tree_path, &[]);
+    let actual = String::from_utf8(output.stderr).unwrap();
+    assert!(!actual.contains("exceeding the limit"));
+
+    let args = ["--max-name-length", "30"];
+    let output = cmd(&binary_path, "scaffold", &tree_path, &args);
+    let actual = String::from_utf8(output.stderr).unwrap();
+    assert!(actual.contains(
+        r#"function name "test_WhenFirstArgIsSmallerThanSecondArg" is 39 characters long, exceeding the limit of 30"#
+    ));
+    assert!(output.status.success());
+}
+
+#[test]
+fn skips_manually_edited_files() {
+    let cwd = env::current_dir().unwrap();
+    let binary_path = get_binary_path();
+    let tests_path = cwd.join("tests").join("scaffold");
+    let tmp_path = env::temp_dir().join("bulloak_detect_manual_edits");
+    fs::create_dir_all(&tmp_path).unwrap();
+    let tree_path = tmp_path.join("basic.tree");
+    let sol_path = tmp_path.join("basic.t.sol");
+    fs::copy(tests_path.join("basic.tree"), &tree_path).unwrap();
+    let _ = fs::remove_file(&sol_path);
+
+    let args = ["-wf", "--detect-manual-edits"];
+    cmd(&binary_path, "scaffold", &tree_path, &args);
+    let generated = fs::read_to_string(&sol_path).unwrap();
+    // The stamp is stored next to the tree hash, on a single line.
+    assert_eq!(generated.matches("// @bulloak:tree-hash ").count(), 1);
+    assert!(!generated.contains("bulloak-hash"));
+
+    // Untouched files are overwritten.
+    let output = cmd(&binary_path, "scaffold", &tree_path, &args);
+    let actual = String::from_utf8(output.stderr).unwrap();
+    assert!(!actual.contains("edited by hand"));
+
+    // Edited files are not.
+    let edited = generated.replace("It should never revert.", "Edited.");
+    fs::write(&sol_path, &edited).unwrap();
+    let output = cmd(&binary_path, "scaffold", &tree_path, &args);
+    let actual = String::from_utf8(output.stderr).unwrap();
+    assert!(actual.contains("edited by hand"));
+    assert_eq!(edited, fs::read_to_string(&sol_path).unwrap());
+
+    fs::remove_dir_all(&tmp_path).unwrap();
+}
+
+#[test]
+fn mirrors_src_dir_into_test_dir() {
+    let cwd = env::current_dir().unwrap();
+    let binary_path = get_binary_path();
+    let tests_path = cwd.join("tests").join("scaffold");
+    let tmp_path = env::temp_dir().join("bulloak_mirror");
+    let src_dir = tmp_path.join("src");
+    let test_dir = tmp_path.join("test");
+    fs::create_dir_all(src_dir.join("token")).unwrap();
+    let _ = fs::remove_dir_all(&test_dir);
+    let tree_path = src_dir.join("token").join("basic.tree");
+    fs::copy(tests_path.join("basic.tree"), &tree_path).unwrap();
+
+    let args = [
+        "-w",
+        "--src-dir",
+        src_dir.to_str().unwrap(),
+        "--test-dir",
+        test_dir.to_str().unwrap(),
+    ];
+    cmd(&binary_path, "scaffold", &tree_path, &args);
+
+    let expected = fs::read_to_string(tests_path.join("basic.t.sol")).unwrap();
+    let sol_path = test_dir.join("token").join("basic.t.sol");
+    let actual = fs::read_to_string(sol_path).unwrap();
+    assert_eq!(expected.trim(), actual.trim());
+    assert!(!src_dir.join("token").join("basic.t.sol").exists());
+
+    fs::remove_dir_all(&tmp_path).unwrap();
+}
+
+#[test]
+fn scaffolds_trees_from_stdin() {
+    let cwd = env::current_dir().unwrap();
+    let binary_path = get_binary_path();
+    let tests_path = cwd.join("tests").join("scaffold");
+    let scaffold = |args: &[&str], tree: &str| {
+        let mut child = Command::new(&binary_path)
+            .arg("scaffold")
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .unwrap();
+        child.stdin.take().unwrap().write_all(tree.as_bytes()).unwrap();
+        child.wait_with_output().unwrap()
+    };
+
+    let tree = fs::read_to_string(tests_path.join("basic.tree")).unwrap();
+    let expected = fs::read_to_string(tests_path.join("basic.t.sol")).unwrap();
+    // `--write-files` doesn't apply, since there is no path to write to.
+    let output = scaffold(&["-", "-w"], &tree);
+    let actual = String::from_utf8(output.stdout).unwrap();
+    assert!(output.status.success());
+    assert_eq!(expected.trim(), actual.trim());
+
+    let output = scaffold(&["-"], "Foo\n└── when something happens\n");
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(!output.status.success());
+    assert!(stderr.contains("file: <stdin>"));
+}
+
+#[test]
+fn writes_files_to_the_output_dir() {
+    let cwd = env::current_dir().unwrap();
+    let binary_path = get_binary_path();
+    let tests_path = cwd.join("tests").join("scaffold");
+    let tmp_path = env::temp_dir().join("bulloak_output_dir");
+    let _ = fs::remove_dir_all(&tmp_path);
+    let specs_dir = tmp_path.join("specs");
+    let output_dir = tmp_path.join("test").join("unit");
+    fs::create_dir_all(&specs_dir).unwrap();
+    let tree_path = specs_dir.join("basic.tree");
+    fs::copy(tests_path.join("basic.tree"), &tree_path).unwrap();
+
+    let args = ["-w", "--output-dir", output_dir.to_str().unwrap()];
+    cmd(&binary_path, "scaffold", &tree_path, &args);
+
+    let expected = fs::read_to_string(tests_path.join("basic.t.sol")).unwrap();
+    let actual = fs::read_to_string(output_dir.join("basic.t.sol")).unwrap();
+    assert_eq!(expected.trim(), actual.trim());
+    assert!(!specs_dir.join("basic.t.sol").exists());
+
+    // Existing files are still skipped without `--force-write`.
+    fs::write(output_dir.join("basic.t.sol"), "").unwrap();
+    let output = cmd(&binary_path, "scaffold", &tree_path, &args);
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("Skipped emitting"));
+    let actual = fs::read_to_string(output_dir.join("basic.t.sol")).unwrap();
+    assert!(actual.is_empty());
+
+    fs::remove_dir_all(&tmp_path).unwrap();
+}
+
+#[test]
+fn writes_roots_named_like_paths_to_nested_files() {
+    let binary_path = get_binary_path();
+    let tmp_path = env::temp_dir().join("bulloak_root_as_path");
+    let _ = fs::remove_dir_all(&tmp_path);
+    fs::create_dir_all(&tmp_path).unwrap();
+    let tree_path = tmp_path.join("transfer.tree");
+    fs::write(&tree_path, "token/transfer/Foo_Test\n└── it should work.\n")
+        .unwrap();
+
+    cmd(&binary_path, "scaffold", &tree_path, &["-w", "--root-as-path"]);
+
+    let sol_path =
+        tmp_path.join("token").join("transfer").join("Foo_Test.t.sol");
+    let actual = fs::read_to_string(sol_path).unwrap();
+    assert!(actual.contains("contract Foo_Test {"));
+    assert!(!tmp_path.join("transfer.t.sol").exists());
+
+    fs::remove_dir_all(&tmp_path).unwrap();
+}
+
+#[test]
+fn reads_options_from_config_profiles() {
+    let binary_path = get_binary_path();
+    let tmp_path = env::temp_dir().join("bulloak_profiles");
+    fs::create_dir_all(&tmp_path).unwrap();
+    fs::write(tmp_path.join("foo.tree"), "Foo\n└── it should work.\n").unwrap();
+    fs::write(
+        tmp_path.join("bulloak.toml"),
+        r#"[profile.default]
+solidity-version = "0.8.20"
+
+[profile.ci]
+vm-skip = true
+import = ["test/Utils.sol"]
+"#,
+    )
+    .unwrap();
+    let scaffold = |args: &[&str]| {
+        Command::new(&binary_path)
+            .current_dir(&tmp_path)
+            .args(args)
+            .output()
+            .unwrap()
+    };
+
+    let output = scaffold(&["scaffold", "foo.tree"]);
+    let actual = String::from_utf8(output.stdout).unwrap();
+    assert!(actual.contains("pragma solidity 0.8.20;"));
+    assert!(!actual.contains("vm.skip(true);"));
+
+    // The selected profile builds on the default one.
+    let output = scaffold(&["--profile", "ci", "scaffold", "foo.tree"]);
+    let actual = String::from_utf8(output.stdout).unwrap();
+    assert!(actual.contains("pragma solidity 0.8.20;"));
+    assert!(actual.contains("vm.skip(true);"));
+    assert!(actual.contains("import \"test/Utils.sol\";"));
+
+    // Flags override the config file.
+    let output = scaffold(&["scaffold", "foo.tree", "-s", "0.8.1"]);
+    let actual = String::from_utf8(output.stdout).unwrap();
+    assert!(actual.contains("pragma solidity 0.8.1;"));
+
+    let output = scaffold(&["--profile", "nope", "scaffold", "foo.tree"]);
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("profile `nope` not found in bulloak.toml"));
+
+    fs::remove_dir_all(&tmp_path).unwrap();
+}
+
+#[test]
+fn rejects_invalid_body_templates() {
+    let binary_path = get_binary_path();
+    let tmp_path = env::temp_dir().join("bulloak_body_templates");
+    fs::create_dir_all(&tmp_path).unwrap();
+    fs::write(tmp_path.join("foo.tree"), "Foo\n└── it should work.\n").unwrap();
+    let scaffold = |args: &[&str]| {
+        Command::new(&binary_path)
+            .current_dir(&tmp_path)
+            .args(args)
+            .output()
+            .unwrap()
+    };
+
+    let output =
+        scaffold(&["scaffold", "foo.tree", "--body-template", "test_=x("]);
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(!output.status.success());
+    assert!(stderr.contains("invalid Solidity statement: x("));
+
+    // Templates from the config file are reported too, rather than dropped.
+    fs::write(
+        tmp_path.join("bulloak.toml"),
+        "[profile.default]\nbody-template = [\"test_=x(\"]\n",
+    )
+    .unwrap();
+    let output = scaffold(&["scaffold", "foo.tree"]);
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(!output.status.success());
+    assert!(stderr.contains("invalid Solidity statement: x("));
+    assert!(!stderr.contains("panicked"));
+
+    fs::remove_dir_all(&tmp_path).unwrap();
+}
+
+#[test]
+fn discovers_config_files_in_parent_directories() {
+    let binary_path = get_binary_path();
+    let tmp_path = env::temp_dir().join("bulloak_discovery");
+    let nested_path = tmp_path.join("test").join("unit");
+    fs::create_dir_all(&nested_path).unwrap();
+    fs::write(nested_path.join("foo.tree"), "Foo\n└── it should work.\n")
+        .unwrap();
+    fs::write(
+        tmp_path.join("bulloak.toml"),
+        r#"[profile.default]
+solidity-version = "0.8.20"
+"#,
+    )
+    .unwrap();
+    let scaffold = || {
+        Command::new(&binary_path)
+            .current_dir(&nested_path)
+            .args(["scaffold", "foo.tree"])
+            .output()
+            .unwrap()
+    };
+
+    let output = scaffold();
+    let actual = String::from_utf8(output.stdout).unwrap();
+    assert!(actual.contains("pragma solidity 0.8.20;"));
+
+    fs::write(
+        tmp_path.join("bulloak.toml"),
+        r#"[profile.default]
+solidity-versions = "0.8.20"
+"#,
+    )
+    .unwrap();
+    let output = scaffold();
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(!output.status.success());
+    assert!(stderr.contains(
+        "unknown option `solidity-versions` in profile `default` of ../../bulloak.toml"
+    ));
+    assert!(stderr.contains("command line flags override the options"));
+
+    fs::remove_dir_all(&tmp_path).unwrap();
+}
+
+#[test]
+fn prints_the_effective_config() {
+    let binary_path = get_binary_path();
+    let tmp_path = env::temp_dir().join("bulloak_print_config");
+    fs::create_dir_all(&tmp_path).unwrap();
+    fs::write(
+        tmp_path.join("bulloak.toml"),
+        r#"[profile.default]
+solidity-version = "0.8.20"
+vm-skip = true
+"#,
+    )
+    .unwrap();
+
+    let output = Command::new(&binary_path)
+        .current_dir(&tmp_path)
+        .args(["scaffold", "foo.tree", "-s", "0.8.1", "--print-config"])
+        .output()
+        .unwrap();
+    let actual = String::from_utf8(output.stdout).unwrap();
+    assert!(output.status.success());
+    assert!(actual.contains(r#"solidity_version: "0.8.1","#));
+    assert!(actual.contains("emit_vm_skip: true,"));
+    // The command doesn't run, so the missing tree isn't an error.
+    assert!(!actual.contains("pragma solidity"));
+
+    fs::remove_dir_all(&tmp_path).unwrap();
+}
+
+#[test]
+fn sets_the_indentation_of_the_emitted_code() {
+    let binary_path = get_binary_path();
+    let print_config = |args: &[&str]| {
+        let output = Command::new(&binary_path)
+            .args(["scaffold", "foo.tree", "--print-config"])
+            .args(args)
+            .output()
+            .unwrap();
+        String::from_utf8(output.stdout).unwrap()
+    };
+
+    assert!(print_config(&[]).contains("indent: 2,"));
+    assert!(print_config(&["--indent", "4"]).contains("indent: 4,"));
+}
+
 #[test]
 fn skips_trees_when_file_exists() {
     let cwd = env::current_dir().unwrap();
@@ -154,3 +1175,70 @@ fn errors_when_root_contract_identifier_is_missing_multiple_roots() {
         assert!(actual.contains("contract name missing at tree root #1"));
     }
 }
+
+#[test]
+fn warns_when_roots_are_duplicated() {
+    let cwd = env::current_dir().unwrap();
+    let binary_path = get_binary_path();
+    let tree_path =
+        cwd.join("tests").join("scaffold").join("duplicate_roots.tree");
+
+    let output = cmd(&binary_path, "scaffold", &tree_path, &[]);
+    let actual = String::from_utf8(output.stderr).unwrap();
+    assert!(
+        actual.contains(r#"the root "Vault::deposit" appears more than once"#)
+    );
+    assert!(output.status.success());
+
+    let args = ["--deny-duplicate-roots"];
+    let output = cmd(&binary_path, "scaffold", &tree_path, &args);
+    let actual = String::from_utf8(output.stderr).unwrap();
+    assert!(
+        actual.contains("tree root #2 duplicates the root 'Vault::deposit'")
+    );
+}
+
+#[test]
+fn emits_an_index_of_the_generated_contracts() {
+    let cwd = env::current_dir().unwrap();
+    let binary_path = get_binary_path();
+    let tests_path = cwd.join("tests").join("scaffold");
+    let tmp = env::temp_dir().join("bulloak_emit_index");
+    fs::create_dir_all(&tmp).unwrap();
+    let basic = tests_path.join("basic.tree");
+    let complex = tests_path.join("complex.tree").display().to_string();
+
+    let index = tmp.join("Index.sol");
+    let args = [complex.as_str(), "--emit-index", index.to_str().unwrap()];
+    let output = cmd(&binary_path, "scaffold", &basic, &args);
+    assert!(output.status.success());
+    let actual = fs::read_to_string(&index).unwrap();
+    assert_eq!(
+        actual,
+        format!(
+            r#"// SPDX-License-Identifier: UNLICENSED
+pragma solidity 0.8.0;
+
+import {{HashPairTestSanitize}} from "{}";
+import {{CancelTest}} from "{}";
+"#,
+            tests_path.join("basic.t.sol").display(),
+            tests_path.join("complex.t.sol").display()
+        )
+    );
+
+    let index = tests_path.join("index.md");
+    let args = [complex.as_str(), "--emit-index", index.to_str().unwrap()];
+    let output = cmd(&binary_path, "scaffold", &basic, &args);
+    assert!(output.status.success());
+    let actual = fs::read_to_string(&index).unwrap();
+    fs::remove_file(&index).unwrap();
+    assert_eq!(
+        actual,
+        "| Contract | File |
+| --- | --- |
+| `HashPairTestSanitize` | `./basic.t.sol` |
+| `CancelTest` | `./complex.t.sol` |
+"
+    );
+}
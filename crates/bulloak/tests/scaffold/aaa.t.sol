@@ -0,0 +1,22 @@
+// SPDX-License-Identifier: UNLICENSED
+pragma solidity 0.8.0;
+
+contract AaaTest {
+    function test_RevertWhen_TheCallerIsNotTheOwner() external {
+        // it should revert.
+
+        // Arrange
+        // Act
+        // Assert
+    }
+
+    function test_WhenTheCallerIsTheOwner() external {
+        // it should update the owner.
+        // it should emit an {OwnerChanged} event.
+
+        // Arrange
+        // Act
+        // Assert
+    }
+}
+
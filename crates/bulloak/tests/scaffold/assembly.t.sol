@@ -0,0 +1,24 @@
+// SPDX-License-Identifier: UNLICENSED
+pragma solidity 0.8.0;
+
+contract AssemblyTest {
+    function test_ShouldNeverRevert() external {
+        // It should never revert.
+        assembly {}
+    }
+
+    modifier whenTheCallerIsTheOwner() {
+        _;
+    }
+
+    function test_WhenTheAmountIsZero() external whenTheCallerIsTheOwner {
+        // It should do nothing.
+        assembly {}
+    }
+
+    function test_WhenTheAmountIsNotZero() external whenTheCallerIsTheOwner {
+        // It should transfer the amount.
+        assembly {}
+    }
+}
+
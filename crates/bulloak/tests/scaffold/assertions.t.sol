@@ -0,0 +1,18 @@
+// SPDX-License-Identifier: UNLICENSED
+pragma solidity 0.8.0;
+
+import {Test} from "forge-std/Test.sol";
+
+contract Vault is Test {
+    function test_WhenTheAmountIsZero() external {
+        // it should return 0.
+        assertEq(actual, 0);
+    }
+
+    function test_WhenTheAmountIsTooLarge() external {
+        // it should equal MAX_DEPOSIT.
+        assertEq(actual, MAX_DEPOSIT);
+        // it should emit an event.
+    }
+}
+
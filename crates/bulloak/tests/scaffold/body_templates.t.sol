@@ -0,0 +1,28 @@
+// SPDX-License-Identifier: UNLICENSED
+pragma solidity 0.8.0;
+
+import {Test} from "forge-std/Test.sol";
+
+contract BodyTemplatesTest is Test {
+    function test_ShouldNeverRevert() external {
+        // It should never revert.
+        emit log("test_ShouldNeverRevert");
+    }
+
+    modifier whenTheAmountIsZero() {
+        _;
+    }
+
+    function test_RevertWhen_TheAmountIsZero() external whenTheAmountIsZero {
+        // It should revert.
+        vm.expectRevert();
+    }
+
+    function test_Gas_WhenTheAmountIsNotZero() external whenTheAmountIsZero {
+        // It should transfer the amount.
+        emit log("test_Gas_WhenTheAmountIsNotZero");
+        vm.startSnapshotGas("WhenTheAmountIsNotZero");
+        vm.stopSnapshotGas();
+    }
+}
+
@@ -0,0 +1,26 @@
+// SPDX-License-Identifier: UNLICENSED
+pragma solidity 0.8.0;
+
+// The vault suite.
+// Covers deposits.
+
+contract Vault_Test {
+    function test_RevertWhen_TheAmountIsZero() external {
+        // See issue #42.
+        // it should revert
+        // With `ZeroAmount`.
+    }
+
+    modifier whenTheAmountIsValid() {
+        _;
+    }
+
+    function test_WhenTheAmountIsValid() external whenTheAmountIsValid {
+        // it should deposit
+        // And emit {Deposit}.
+    }
+
+    function test_RevertGiven_TheVaultIsPaused() external whenTheAmountIsValid {
+        // it should revert
+    }
+}
@@ -0,0 +1,18 @@
+// SPDX-License-Identifier: UNLICENSED
+pragma solidity 0.8.0;
+
+import {Test} from "forge-std/Test.sol";
+
+contract CommentsAboveTest is Test {
+    // it should revert.
+    function test_RevertWhen_TheCallerIsNotTheOwner() external {
+        vm.skip(true);
+    }
+
+    // it should transfer the amount.
+    // it should emit a {Transfer} event.
+    function test_WhenTheCallerIsTheOwner() external {
+        vm.skip(true);
+    }
+}
+
@@ -0,0 +1,11 @@
+// SPDX-License-Identifier: UNLICENSED
+pragma solidity 0.8.0;
+
+contract DescriptionsTest {
+    function test_WhenTheCallerIsTheOwner() external {
+        // It should transfer the amount.
+        // TODO: Deal some tokens to the owner.
+        // TODO: Check the balances after the transfer.
+    }
+}
+
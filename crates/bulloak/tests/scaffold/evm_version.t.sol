@@ -0,0 +1,14 @@
+// SPDX-License-Identifier: UNLICENSED
+pragma solidity 0.8.0;
+
+/// forge-config: default.evm_version = "cancun"
+contract VaultTest {
+    function test_RevertWhen_TheCallerIsNotTheOwner() external {
+        // it should revert.
+    }
+
+    function test_WhenTheCallerIsTheOwner() external {
+        // it should use transient storage.
+    }
+}
+
@@ -0,0 +1,21 @@
+// SPDX-License-Identifier: UNLICENSED
+pragma solidity 0.8.0;
+
+import {Test} from "forge-std/Test.sol";
+
+contract Vault is Test {
+    function test_WhenTheCallerIsNotTheOwner() external {
+        // it should revert with Unauthorized(caller).
+        vm.expectRevert(abi.encodeWithSelector(Unauthorized.selector, caller));
+    }
+
+    function test_WhenTheVaultIsPaused() external {
+        // it should revert with `Paused`.
+        vm.expectRevert(Paused.selector);
+    }
+
+    function test_WhenTheCallerIsTheOwner() external {
+        // it should withdraw the funds.
+    }
+}
+
@@ -0,0 +1,11 @@
+// SPDX-License-Identifier: UNLICENSED
+pragma solidity 0.8.0;
+
+import {VaultBase} from "./extends_base.t.sol";
+
+contract Vault is VaultBase {
+    function test_WhenTheCallerIsTheOwner() external {
+        // it should update the state.
+    }
+}
+
@@ -0,0 +1,9 @@
+// SPDX-License-Identifier: UNLICENSED
+pragma solidity 0.8.0;
+
+contract VaultBase {
+    function test_RevertWhen_TheCallerIsNotTheOwner() external {
+        // it should revert.
+    }
+}
+
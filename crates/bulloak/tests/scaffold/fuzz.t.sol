@@ -0,0 +1,13 @@
+// SPDX-License-Identifier: UNLICENSED
+pragma solidity 0.8.0;
+
+contract FuzzTest {
+    function testFuzz_RevertWhen_TheAmountIsZero() external {
+        // it should revert.
+    }
+
+    function testFuzz_WhenTheAmountIsNotZero() external {
+        // it should transfer the amount.
+    }
+}
+
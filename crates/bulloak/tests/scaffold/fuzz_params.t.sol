@@ -0,0 +1,17 @@
+// SPDX-License-Identifier: UNLICENSED
+pragma solidity 0.8.0;
+
+contract FuzzParamsTest {
+    modifier givenAmountX() {
+        _;
+    }
+
+    function testFuzz_RevertWhen_FeeFExceedsTheAmount(uint256 amount, uint256 fee) external givenAmountX {
+        // it should revert.
+    }
+
+    function testFuzz_WhenFeeFDoesNotExceedTheAmount(uint256 amount, uint256 fee) external givenAmountX {
+        // it should transfer the difference.
+    }
+}
+
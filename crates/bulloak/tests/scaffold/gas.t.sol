@@ -0,0 +1,20 @@
+// SPDX-License-Identifier: UNLICENSED
+pragma solidity 0.8.0;
+
+import {Test} from "forge-std/Test.sol";
+
+contract GasTest is Test {
+    function test_Gas_ShouldTransferCheaply() external {
+        // It should transfer cheaply.
+        vm.startSnapshotGas("ShouldTransferCheaply");
+        vm.stopSnapshotGas();
+    }
+
+    function test_Gas_WhenTheAmountIsNotZero() external {
+        // It should transfer the amount.
+        // It should emit an event.
+        vm.startSnapshotGas("WhenTheAmountIsNotZero");
+        vm.stopSnapshotGas();
+    }
+}
+
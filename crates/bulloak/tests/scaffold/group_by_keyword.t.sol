@@ -0,0 +1,27 @@
+// SPDX-License-Identifier: UNLICENSED
+pragma solidity 0.8.0;
+
+contract Vault {
+    function test_ShouldNeverRevert() external {
+        // it should never revert.
+    }
+
+    modifier givenTheVaultIsPaused() {
+        _;
+    }
+
+    // ==================== GIVEN ====================
+    function test_GivenTheVaultIsPaused() external givenTheVaultIsPaused {
+        // it should reject deposits.
+    }
+
+    // ==================== WHEN ====================
+    function test_WhenTheCallerIsTheOwner() external givenTheVaultIsPaused {
+        // it should unpause the vault.
+    }
+
+    function test_WhenTheCallerDeposits() external {
+        // it should mint shares.
+    }
+}
+
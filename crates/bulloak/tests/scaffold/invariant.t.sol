@@ -0,0 +1,19 @@
+// SPDX-License-Identifier: UNLICENSED
+pragma solidity 0.8.0;
+
+import {Test} from "forge-std/Test.sol";
+
+contract InvariantTest is Test {
+    function setUp() external {
+        targetContract(address(0));
+    }
+
+    function invariant_ShouldNeverChangeTheTotalSupply() external {
+        // it should never change the total supply.
+    }
+
+    function test_WhenAUserDeposits() external {
+        // it should increase their balance.
+    }
+}
+
@@ -0,0 +1,13 @@
+// SPDX-License-Identifier: UNLICENSED
+pragma solidity 0.8.0;
+
+contract AliasTest {
+    function test_RevertGiven_TheVaultIsPaused() external {
+        // it should revert.
+    }
+
+    function test_WhenTheCallerIsTheOwner() external {
+        // it should work.
+    }
+}
+
@@ -0,0 +1,27 @@
+// SPDX-License-Identifier: UNLICENSED
+pragma solidity 0.8.0;
+
+import {Test} from "forge-std/Test.sol";
+
+contract LabelsTest is Test {
+    function test_ShouldLetAliceDeposit() external {
+        // It should let alice deposit.
+        vm.label(alice, "alice");
+    }
+
+    modifier whenBobIsTheOwner() {
+        _;
+    }
+
+    function test_WhenBobIsTheOwner() external whenBobIsTheOwner {
+        // It should let bob withdraw.
+        vm.label(bob, "bob");
+    }
+
+    function test_WhenAliceIsApproved() external whenBobIsTheOwner {
+        // It should transfer to alice.
+        vm.label(alice, "alice");
+        vm.label(bob, "bob");
+    }
+}
+
@@ -0,0 +1,27 @@
+// SPDX-License-Identifier: UNLICENSED
+pragma solidity 0.8.0;
+
+contract ModifierCommentsTest {
+    // given the vault is open
+    modifier givenTheVaultIsOpen() {
+        _;
+    }
+
+    function test_RevertWhen_TheCallerIsNotTheOwner() external givenTheVaultIsOpen {
+        // it should revert.
+    }
+
+    // when the caller is the owner
+    modifier whenTheCallerIsTheOwner() {
+        _;
+    }
+
+    function test_RevertWhen_TheAmountIsZero() external givenTheVaultIsOpen whenTheCallerIsTheOwner {
+        // it should revert.
+    }
+
+    function test_WhenTheAmountIsNotZero() external givenTheVaultIsOpen whenTheCallerIsTheOwner {
+        // it should deposit the amount.
+    }
+}
+
@@ -0,0 +1,49 @@
+// SPDX-License-Identifier: UNLICENSED
+pragma solidity 0.8.0;
+
+contract Vault {
+    modifier givenTheStatusIsActive() {
+        _;
+    }
+
+    function test_WhenTheCallerIsTheOwner_Active() external givenTheStatusIsActive {
+        // it should withdraw.
+    }
+
+    modifier whenTheCallerIsNotTheOwner() {
+        _;
+    }
+
+    function test_RevertWhen_TheCallerIsNotTheOwner_Active()
+        external
+        givenTheStatusIsActive
+        whenTheCallerIsNotTheOwner
+    {
+        // it should revert.
+    }
+
+    function test_WhenTheCallerIsApproved_Active() external givenTheStatusIsActive whenTheCallerIsNotTheOwner {
+        // it should withdraw.
+    }
+
+    modifier givenTheStatusIsPaused() {
+        _;
+    }
+
+    function test_WhenTheCallerIsTheOwner_Paused() external givenTheStatusIsPaused {
+        // it should withdraw.
+    }
+
+    function test_RevertWhen_TheCallerIsNotTheOwner_Paused()
+        external
+        givenTheStatusIsPaused
+        whenTheCallerIsNotTheOwner
+    {
+        // it should revert.
+    }
+
+    function test_WhenTheCallerIsApproved_Paused() external givenTheStatusIsPaused whenTheCallerIsNotTheOwner {
+        // it should withdraw.
+    }
+}
+
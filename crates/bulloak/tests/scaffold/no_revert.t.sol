@@ -0,0 +1,18 @@
+// SPDX-License-Identifier: UNLICENSED
+pragma solidity 0.8.0;
+
+import {Test} from "forge-std/Test.sol";
+
+contract Vault is Test {
+    function test_RevertWhen_TheVaultIsPaused() external {
+        // it should revert.
+    }
+
+    function test_WhenTheCallerIsTheOwner() external {
+        // it never reverts.
+        (bool success,) = address(target).call(data);
+        assertTrue(success, "should not revert");
+        // it should withdraw the funds.
+    }
+}
+
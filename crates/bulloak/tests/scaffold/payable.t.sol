@@ -0,0 +1,23 @@
+// SPDX-License-Identifier: UNLICENSED
+pragma solidity 0.8.0;
+
+contract VaultTest {
+    function test_WhenTheCallerSendsETH() external payable {
+        // it should credit the caller.
+    }
+
+    modifier whenTheCallerSendsNoETH() {
+        _;
+    }
+
+    function test_WhenTheCallerSendsNoETH() external payable whenTheCallerSendsNoETH {
+        // it should do nothing.
+    }
+
+    function test_RevertWhen_TheVaultIsPaused() external whenTheCallerSendsNoETH {
+        // it should revert.
+    }
+
+    receive() external payable {}
+}
+
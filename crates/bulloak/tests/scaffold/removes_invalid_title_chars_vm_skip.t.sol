@@ -1,6 +1,9 @@
 // SPDX-License-Identifier: UNLICENSED
 pragma solidity 0.8.0;
 
+// https://github.com/alexfertel/bulloak/issues/52
+// https://github.com/alexfertel/bulloak/issues/58
+
 import {Test} from "forge-std/Test.sol";
 
 contract Foo is Test {
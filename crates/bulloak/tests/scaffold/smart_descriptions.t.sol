@@ -0,0 +1,15 @@
+// SPDX-License-Identifier: UNLICENSED
+pragma solidity 0.8.0;
+
+import {Test} from "forge-std/Test.sol";
+
+contract SmartDescriptionsTest is Test {
+    function test_WhenTheCallerDeposits() external {
+        // It should update the balance.
+        vm.expectCall(address(oracle), data);
+        vm.expectEmit();
+        emit Deposit();
+        //     Stores the amount.
+    }
+}
+
@@ -1,9 +1,12 @@
 // SPDX-License-Identifier: UNLICENSED
 pragma solidity 0.8.0;
 
+// This comment works
+
 contract HashPairTest {
     function test_ShouldNeverRevert() external {
         // It should never revert.
+        // This comment also works
     }
 
     function test_WhenFirstArgIsSmallerThanSecondArg() external {
@@ -0,0 +1,18 @@
+// SPDX-License-Identifier: UNLICENSED
+pragma solidity 0.8.0;
+
+import {PRBTest} from "@prb/test/src/PRBTest.sol";
+import {console} from "forge-std/console.sol";
+
+contract Vault is PRBTest {
+    function test_RevertWhen_TheVaultIsPaused() external {
+        // it should revert.
+        console.log("it should revert.");
+    }
+
+    function test_WhenTheCallerIsTheOwner() external {
+        // it should return 42.
+        assertEq(actual, 42);
+    }
+}
+
@@ -0,0 +1,17 @@
+// SPDX-License-Identifier: UNLICENSED
+pragma solidity 0.8.0;
+
+import {Test, console} from "forge-std/Test.sol";
+
+contract VaultTest is Test {
+    function test_RevertWhen_TheCallerIsNotTheOwner() external {
+        // it should revert.
+    }
+
+    function test_WhenTheCallerIsTheOwner() external {
+        // it should update the "owner" state.
+        console.log("it should update the \"owner\" state.");
+        // it should emit an event.
+    }
+}
+
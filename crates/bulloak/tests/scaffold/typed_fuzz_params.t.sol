@@ -0,0 +1,17 @@
+// SPDX-License-Identifier: UNLICENSED
+pragma solidity 0.8.0;
+
+contract TypedFuzzParamsTest {
+    modifier givenPayloadP() {
+        _;
+    }
+
+    function testFuzz_RevertWhen_NameNIsEmpty(bytes memory payload, string memory name) external givenPayloadP {
+        // it should revert.
+    }
+
+    function testFuzz_WhenFeeFExceedsTheAmount(bytes memory payload, uint256 fee) external givenPayloadP {
+        // it should transfer the difference.
+    }
+}
+
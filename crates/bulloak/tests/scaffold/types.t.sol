@@ -0,0 +1,20 @@
+// SPDX-License-Identifier: UNLICENSED
+pragma solidity 0.8.0;
+
+/// @title Vault Tests
+contract VaultTest {
+    enum Status {
+        Active,
+        Paused
+    }
+
+    struct Position {
+        uint256 amount;
+        address owner;
+    }
+
+    function test_RevertWhen_TheStatusIsPaused() external {
+        // it should revert.
+    }
+}
+
@@ -0,0 +1,21 @@
+// SPDX-License-Identifier: UNLICENSED
+pragma solidity 0.8.0;
+
+contract Vault {
+    function test_GivenTheStatusIsActive() external {
+        // it should emit an event.
+    }
+
+    function test_GivenTheStatusIsPaused() external {
+        // it should emit an event.
+    }
+
+    function test_GivenTheStatusIsClosed() external {
+        // it should emit an event.
+    }
+
+    function test_RevertWhen_TheCallerIsNotTheOwner() external {
+        // it should revert.
+    }
+}
+
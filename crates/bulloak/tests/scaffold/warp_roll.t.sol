@@ -0,0 +1,26 @@
+// SPDX-License-Identifier: UNLICENSED
+pragma solidity 0.8.0;
+
+import {Test} from "forge-std/Test.sol";
+
+contract Vault is Test {
+    modifier givenTheLockPeriodHasPassed() {
+        _;
+    }
+
+    function test_GivenTheLockPeriodHasPassed() external givenTheLockPeriodHasPassed {
+        // it should allow withdrawals.
+        vm.warp(1000);
+    }
+
+    function test_WhenTheBlockIsOld() external givenTheLockPeriodHasPassed {
+        // it should unlock.
+        vm.warp(1000);
+        vm.roll(100);
+    }
+
+    function test_RevertWhen_TheCallerIsNotTheOwner() external {
+        // it should revert.
+    }
+}
+
@@ -0,0 +1,49 @@
+//! Integration tests for `bulloak tree`.
+
+use std::{env, fs};
+
+use common::{cmd, get_binary_path};
+use pretty_assertions::assert_eq;
+
+mod common;
+
+#[test]
+fn reconstructs_trees_from_solidity_files() {
+    let cwd = env::current_dir().unwrap();
+    let binary_path = get_binary_path();
+    let tests_path = cwd.join("tests").join("tree");
+    let sol_path = tests_path.join("vault.t.sol");
+
+    let output = cmd(&binary_path, "tree", &sol_path, &[]);
+    let actual = String::from_utf8(output.stdout).unwrap();
+    let expected = fs::read_to_string(tests_path.join("vault.tree")).unwrap();
+    assert_eq!(expected.trim(), actual.trim());
+}
+
+#[test]
+fn round_trips_with_write_files() {
+    let cwd = env::current_dir().unwrap();
+    let binary_path = get_binary_path();
+    let tmp_path = env::temp_dir().join("bulloak_tree");
+    fs::create_dir_all(&tmp_path).unwrap();
+    let sol_path = tmp_path.join("complex.t.sol");
+    let tree_path = tmp_path.join("complex.tree");
+    let _ = fs::remove_file(&tree_path);
+    fs::copy(
+        cwd.join("tests").join("scaffold").join("complex.t.sol"),
+        &sol_path,
+    )
+    .unwrap();
+
+    let output = cmd(&binary_path, "tree", &sol_path, &["--write-files"]);
+    assert!(output.status.success());
+    assert!(tree_path.exists());
+
+    let output = cmd(&binary_path, "check", &tree_path, &[]);
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(
+        stdout.contains("All checks completed successfully! No issues found.")
+    );
+
+    fs::remove_dir_all(&tmp_path).unwrap();
+}
@@ -0,0 +1,26 @@
+// SPDX-License-Identifier: UNLICENSED
+pragma solidity 0.8.0;
+
+contract VaultTest {
+    function test_ShouldNeverRevert() external {
+        // It should never revert.
+    }
+
+    modifier whenTheCallerIsTheOwner() {
+        _;
+    }
+
+    function test_WhenTheCallerIsTheOwner() external whenTheCallerIsTheOwner {
+        // It should transfer the amount.
+        //     Deal some tokens to the owner.
+    }
+
+    function test_RevertWhen_TheAmountIsZero() external whenTheCallerIsTheOwner {
+        // It should revert.
+    }
+
+    function testFuzz_GivenTheVaultIsPaused() external {
+        // It should emit a {Paused} event.
+    }
+}
+
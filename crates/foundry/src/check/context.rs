@@ -1,11 +1,11 @@
 //! Defines the context in which rule-checking occurs.
 
 use std::{
-    ffi::OsStr,
     fs,
     path::{Path, PathBuf},
 };
 
+use bulloak_syntax::{parse_with_aliases, Ast};
 use forge_fmt::{
     format, Comments, FormatterConfig, FormatterError, InlineConfig, Parsed,
 };
@@ -17,6 +17,7 @@ use crate::{
     config::Config,
     hir::{self, Hir},
     scaffold::emitter::Emitter,
+    sol,
 };
 
 /// The context in which rule-checking happens.
@@ -30,6 +31,8 @@ use crate::{
 pub struct Context {
     /// The path to the tree file.
     pub tree: PathBuf,
+    /// The hash of the contents of the tree file.
+    pub tree_hash: String,
     /// The high-level intermediate representation
     /// of the bulloak tree.
     pub hir: Hir,
@@ -53,15 +56,18 @@ impl Context {
     pub fn new(tree: PathBuf, cfg: &Config) -> Result<Self, Violation> {
         let tree_path_cow = tree.to_string_lossy();
         let tree_contents = try_read_to_string(&tree)?;
-        let hir = crate::hir::translate(&tree_contents, &Config::default())
-            .map_err(|e| {
-                Violation::new(
-                    ViolationKind::ParsingFailed(e),
-                    Location::File(tree_path_cow.into_owned()),
-                )
-            })?;
+        let cfg = &Config { tree_path: Some(tree.clone()), ..cfg.clone() };
+        let hir = crate::hir::translate(&tree_contents, cfg).map_err(|e| {
+            Violation::new(
+                ViolationKind::ParsingFailed(e),
+                Location::File(tree_path_cow.into_owned()),
+            )
+        })?;
 
-        let sol = get_path_with_ext(&tree, "t.sol")?;
+        let sol = match &cfg.shared_sol {
+            Some(sol) => sol.clone(),
+            None => get_sol_path(&tree, &tree_contents, cfg)?,
+        };
         let src = try_read_to_string(&sol)?;
         let parsed = forge_fmt::parse(&src).map_err(|_| {
             let sol_filename = sol.to_string_lossy().into_owned();
@@ -75,7 +81,17 @@ impl Context {
 
         let pt = parsed.pt.clone();
         let comments = parsed.comments;
-        Ok(Context { tree, hir, sol, src, pt, comments, cfg: cfg.clone() })
+        let tree_hash = sol::hash(&tree_contents);
+        Ok(Context {
+            tree,
+            tree_hash,
+            hir,
+            sol,
+            src,
+            pt,
+            comments,
+            cfg: cfg.clone(),
+        })
     }
 
     /// Updates this `Context` with the result of parsing a Solidity file.
@@ -136,13 +152,26 @@ impl Context {
     }
 }
 
-fn get_path_with_ext(
-    path: impl AsRef<Path>,
-    ext: impl AsRef<OsStr>,
+/// Returns the path of the Solidity file of `tree`, which is `tree` with a
+/// `.t.sol` extension or, with `--root-as-path`, the path in its root name,
+/// relative to the directory of `tree`.
+fn get_sol_path(
+    path: &Path,
+    text: &str,
+    cfg: &Config,
 ) -> Result<PathBuf, Violation> {
-    let path = path.as_ref();
-    let mut sol = path.to_path_buf();
-    sol.set_extension(ext);
+    let root_file = cfg
+        .root_as_path
+        .then(|| parse_with_aliases(text, &cfg.keyword_aliases).ok())
+        .flatten()
+        .and_then(|asts| match asts.into_iter().next() {
+            Some((_, Ast::Root(root))) => hir::root_file(&root.contract_name),
+            _ => None,
+        });
+    let sol = match root_file {
+        Some(file) => path.parent().map_or(file.clone(), |dir| dir.join(&file)),
+        None => path.with_extension("t.sol"),
+    };
 
     if !sol.exists() {
         let filename = path.to_string_lossy().into_owned();
@@ -1,14 +1,17 @@
 //! Location utilities.
-use std::fmt;
+use std::{fmt, path::Path};
+
+use super::utils::relative_to_cwd;
 
 type Filename = String;
 type Line = usize;
+type Column = usize;
 
 /// A code location.
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum Location {
-    /// A code location inside a file.
-    Code(Filename, Line),
+    /// A code location inside a file, with its 1-based line and column.
+    Code(Filename, Line, Column),
     /// A file name.
     File(Filename),
 }
@@ -17,7 +20,21 @@ impl Location {
     /// Returns the filename of this code location.
     pub fn file(&self) -> String {
         match self {
-            Location::Code(file, _) | Location::File(file) => file.clone(),
+            Location::Code(file, ..) | Location::File(file) => file.clone(),
+        }
+    }
+
+    /// Returns this location with its filename relative to the current
+    /// working directory.
+    #[must_use]
+    pub fn relative(self) -> Self {
+        let relative =
+            |file: &str| relative_to_cwd(Path::new(file)).display().to_string();
+        match self {
+            Location::Code(file, line, column) => {
+                Location::Code(relative(&file), line, column)
+            }
+            Location::File(file) => Location::File(relative(&file)),
         }
     }
 }
@@ -25,7 +42,7 @@ impl Location {
 impl fmt::Display for Location {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            Location::Code(filename, line) => write!(f, "{filename}:{line}"),
+            Location::Code(filename, line, _) => write!(f, "{filename}:{line}"),
             Location::File(name) => write!(f, "{name}"),
         }
     }
@@ -4,6 +4,28 @@
 //! - All spec-generated functions & modifiers are present in the output file.
 //! - The order of the spec-generated functions & modifiers matches the output
 //!   file.
+//! - Optionally, there are no test functions in the output file that the spec
+//!   doesn't generate.
+//! - With `--check-unused-modifiers`, there are no modifiers following
+//!   bulloak's naming in the output file that aren't applied to any function.
+//! - With `--check-comment-order`, the action comments inside each test
+//!   function appear in the same order as the actions in the spec.
+//! - With `--check-modifier-order`, the modifiers applied to each test function
+//!   appear in the same order as the conditions they come from are nested in
+//!   the spec.
+//! - Optionally, the pragma directive matches the configured Solidity version.
+//! - Optionally, the Solidity file is named like its contract.
+//!
+//! When every tree is checked against a shared Solidity file, only the presence
+//! of the functions and, if asked for, the order of their comments and
+//! modifiers are enforced, since the file also holds the functions of other
+//! trees.
+//!
+//! Files that store the hash of their tree, see `bulloak scaffold
+//! --tree-hash`, are skipped while the tree stays the same.
+//!
+//! When only names are checked, the presence of the contract and its functions
+//! is all that is enforced.
 //!
 //! Matching is name-based, which means that two functions are considered the
 //! same if:
@@ -20,11 +42,11 @@ use super::{Checker, Context};
 use crate::{
     check::{
         location::Location,
-        utils::offset_to_line,
+        utils::{file_stem, offset_to_line_column},
         violation::{Violation, ViolationKind},
     },
     hir::{self, Hir},
-    sol::{find_contract, find_matching_fn},
+    sol::{find_contract, find_matching_fn, find_pragma, stored_tree_hash},
 };
 
 /// An implementation of a structural matching rule.
@@ -37,6 +59,19 @@ pub struct StructuralMatcher;
 impl Checker for StructuralMatcher {
     fn check(ctx: &Context) -> Vec<Violation> {
         let mut violations = vec![];
+        if ctx.cfg.check_pragma && !ctx.cfg.check_names_only {
+            violations.extend(check_pragma(ctx));
+        }
+        if ctx.cfg.check_file_name && ctx.cfg.shared_sol.is_none() {
+            violations.extend(check_file_name(ctx));
+        }
+
+        // Files that store the hash of the current tree were generated from
+        // it, so only the presence of their functions is checked, which
+        // still catches deleted tests. Shared files hold the functions of
+        // other trees too, so their hash is never trusted.
+        let is_unchanged = ctx.cfg.shared_sol.is_none()
+            && stored_tree_hash(&ctx.src) == Some(ctx.tree_hash.as_str());
 
         // We support multiple trees per .tree file, but they are combined into
         // a single HIR during the hir::translate step when creating the context
@@ -72,6 +107,13 @@ impl Checker for StructuralMatcher {
         let contract_hir = contract_hir.unwrap();
         let contract_sol = contract_sol.unwrap();
         if let Hir::ContractDefinition(contract_hir) = contract_hir {
+            if ctx.cfg.check_names_only || is_unchanged {
+                let (mut missing, _) =
+                    check_fns_presence(contract_hir, &contract_sol, ctx);
+                violations.append(&mut missing);
+                return violations;
+            }
+
             violations.append(&mut check_contract_names(
                 contract_hir,
                 &contract_sol,
@@ -88,6 +130,49 @@ impl Checker for StructuralMatcher {
     }
 }
 
+/// Returns the location of the byte `offset` of the Solidity file.
+fn sol_location(ctx: &Context, offset: usize) -> Location {
+    let (line, column) = offset_to_line_column(&ctx.src, offset);
+    Location::Code(ctx.sol.to_string_lossy().into_owned(), line, column)
+}
+
+/// Checks that the pragma directive of the Solidity file matches the
+/// configured Solidity version.
+///
+/// Files without a pragma directive are fine, since they may be meant to be
+/// pasted into another file.
+fn check_pragma(ctx: &Context) -> Option<Violation> {
+    let (loc, version) = find_pragma(&ctx.pt)?;
+    let expected = ctx.cfg.pragma_version();
+    let normalize = |v: &str| v.split_whitespace().collect::<String>();
+    if normalize(&version) == normalize(&expected) {
+        return None;
+    }
+
+    Some(Violation::new(
+        ViolationKind::PragmaMismatch(expected, version),
+        sol_location(ctx, loc.start()),
+    ))
+}
+
+/// Checks that the Solidity file is named like the contract of the tree.
+///
+/// Shared Solidity files hold the contracts of many trees, so they are never
+/// checked.
+fn check_file_name(ctx: &Context) -> Option<Violation> {
+    let contract = ctx.hir.find_contract()?;
+    let contract_name = sanitize(&contract.identifier);
+    let stem = file_stem(&ctx.sol)?;
+    if stem == contract_name {
+        return None;
+    }
+
+    Some(Violation::new(
+        ViolationKind::FileNameMismatch(contract_name, stem.to_owned()),
+        Location::File(ctx.sol.as_path().to_string_lossy().into_owned()),
+    ))
+}
+
 /// Checks that contract names match.
 fn check_contract_names(
     contract_hir: &hir::ContractDefinition,
@@ -105,10 +190,7 @@ fn check_contract_names(
                     contract_name,
                     identifier.name.clone(),
                 ),
-                Location::Code(
-                    ctx.sol.as_path().to_string_lossy().into_owned(),
-                    offset_to_line(&ctx.src, contract_sol.loc.start()),
-                ),
+                sol_location(ctx, contract_sol.loc.start()),
             );
             violations.push(violation);
         }
@@ -117,14 +199,15 @@ fn check_contract_names(
     violations
 }
 
-/// Checks that function structures match between the HIR and the Solidity AST.
-/// i.e. that all the functions are present in the output file in the right
-/// order. This could be better, currently it is O(N^2).
-fn check_fns_structure(
+/// Checks that all the functions of the HIR are present in the Solidity AST.
+///
+/// Returns a violation per missing function, along with the indices of the
+/// functions that were found in both the HIR and the Solidity contract.
+fn check_fns_presence(
     contract_hir: &hir::ContractDefinition,
     contract_sol: &pt::ContractDefinition,
     ctx: &Context,
-) -> Vec<Violation> {
+) -> (Vec<Violation>, Vec<(usize, usize)>) {
     let mut violations = Vec::new();
 
     // Check that hir functions are present in the solidity contract. Store
@@ -156,6 +239,7 @@ fn check_fns_structure(
                         Location::Code(
                             ctx.tree.to_string_lossy().into_owned(),
                             fn_hir.span.start.line,
+                            fn_hir.span.start.column,
                         ),
                     ))
                 }
@@ -163,9 +247,57 @@ fn check_fns_structure(
         };
     }
 
+    (violations, present_fn_indices)
+}
+
+/// Checks that function structures match between the HIR and the Solidity AST.
+/// i.e. that all the functions are present in the output file in the right
+/// order. This could be better, currently it is O(N^2).
+fn check_fns_structure(
+    contract_hir: &hir::ContractDefinition,
+    contract_sol: &pt::ContractDefinition,
+    ctx: &Context,
+) -> Vec<Violation> {
+    let (mut violations, present_fn_indices) =
+        check_fns_presence(contract_hir, contract_sol, ctx);
+
+    // A shared Solidity file holds the functions of other trees too, so only
+    // presence is checked against it.
+    let is_subset = ctx.cfg.shared_sol.is_some();
+    if ctx.cfg.strict_extras && !is_subset {
+        violations.append(&mut check_extra_fns(
+            contract_hir,
+            contract_sol,
+            ctx,
+        ));
+    }
+
+    if ctx.cfg.check_unused_modifiers && !is_subset {
+        violations.append(&mut check_unused_modifiers(contract_sol, ctx));
+    }
+
+    for &(hir_idx, sol_idx) in &present_fn_indices {
+        if let (
+            Hir::FunctionDefinition(fn_hir),
+            pt::ContractPart::FunctionDefinition(fn_sol),
+        ) = (&contract_hir.children[hir_idx], &contract_sol.parts[sol_idx])
+        {
+            if ctx.cfg.check_comment_order {
+                violations.extend(check_comment_order(fn_hir, fn_sol, ctx));
+            }
+            if ctx.cfg.check_modifier_order {
+                if let Some(violation) =
+                    check_modifier_order(fn_hir, fn_sol, ctx)
+                {
+                    violations.push(violation);
+                }
+            }
+        }
+    }
+
     // No matching constructs were found. We can just return, since
     // we already processed violations in the prev step.
-    if present_fn_indices.is_empty() {
+    if present_fn_indices.is_empty() || is_subset {
         return violations;
     }
 
@@ -197,10 +329,7 @@ fn check_fns_structure(
                         sol_idx,
                         hir_idx,
                     ),
-                    Location::Code(
-                        ctx.sol.clone().to_string_lossy().into_owned(),
-                        offset_to_line(&ctx.src, fn_sol.loc.start()),
-                    ),
+                    sol_location(ctx, fn_sol.loc.start()),
                 ));
             }
         }
@@ -208,3 +337,197 @@ fn check_fns_structure(
 
     violations
 }
+
+/// Checks that every test function in the Solidity contract corresponds to a
+/// function generated from the spec.
+///
+/// Only functions whose name starts with `test` are considered, since any
+/// other function could be a helper that the spec knows nothing about.
+fn check_extra_fns(
+    contract_hir: &hir::ContractDefinition,
+    contract_sol: &pt::ContractDefinition,
+    ctx: &Context,
+) -> Vec<Violation> {
+    let hir_fns: BTreeSet<&str> = contract_hir
+        .children
+        .iter()
+        .filter_map(|child| match child {
+            Hir::FunctionDefinition(fn_hir) => Some(fn_hir.identifier.as_str()),
+            _ => None,
+        })
+        .collect();
+
+    contract_sol
+        .parts
+        .iter()
+        .filter_map(|part| match part {
+            pt::ContractPart::FunctionDefinition(fn_sol)
+                if matches!(fn_sol.ty, pt::FunctionTy::Function) =>
+            {
+                fn_sol.name.as_ref().map(|name| (fn_sol, &name.name))
+            }
+            _ => None,
+        })
+        .filter(|(_, name)| {
+            name.starts_with("test") && !hir_fns.contains(name.as_str())
+        })
+        .map(|(fn_sol, name)| {
+            Violation::new(
+                ViolationKind::ExtraFunction(name.clone()),
+                sol_location(ctx, fn_sol.loc.start()),
+            )
+        })
+        .collect()
+}
+
+/// Checks that the action comments in the body of `fn_sol` appear in the same
+/// order as the actions of `fn_hir`.
+///
+/// Comments that don't match any action are ignored, as are actions whose
+/// comment is missing. Returns a violation pointing at the first comment that
+/// is out of order, if any.
+fn check_comment_order(
+    fn_hir: &hir::FunctionDefinition,
+    fn_sol: &pt::FunctionDefinition,
+    ctx: &Context,
+) -> Option<Violation> {
+    let Some(pt::Statement::Block { loc, .. }) = &fn_sol.body else {
+        return None;
+    };
+    let actions: Vec<&str> = fn_hir
+        .children
+        .iter()
+        .flatten()
+        .filter_map(|child| match child {
+            Hir::Comment(comment)
+                if matches!(comment.kind, hir::CommentKind::Action) =>
+            {
+                Some(comment.lexeme.trim())
+            }
+            _ => None,
+        })
+        .collect();
+
+    // The action comments in the body, along with the line and column they
+    // are at.
+    let body = ctx.src.get(loc.start()..loc.end())?;
+    let (body_line, _) = offset_to_line_column(&ctx.src, loc.start());
+    let comments: Vec<(usize, usize, &str)> = body
+        .lines()
+        .enumerate()
+        .filter_map(|(idx, line)| {
+            let indented = line.trim_start();
+            let text = indented.trim_end().strip_prefix("//")?;
+            let column = line.len() - indented.len() + 1;
+            (!text.starts_with('/')).then_some((
+                body_line + idx,
+                column,
+                text.trim(),
+            ))
+        })
+        .filter(|(_, _, text)| actions.contains(text))
+        .collect();
+    let expected = actions
+        .iter()
+        .filter(|action| comments.iter().any(|(_, _, text)| text == *action));
+
+    comments
+        .iter()
+        .zip(expected)
+        .find(|((_, _, found), expected)| found != *expected)
+        .map(|((line, column, found), _)| {
+            Violation::new(
+                ViolationKind::CommentOrderMismatch(
+                    (*found).to_owned(),
+                    fn_hir.identifier.clone(),
+                ),
+                Location::Code(
+                    ctx.sol.as_path().to_string_lossy().into_owned(),
+                    *line,
+                    *column,
+                ),
+            )
+        })
+}
+
+/// Checks that the modifiers applied to `fn_sol` appear in the same order as
+/// the modifiers of `fn_hir`, i.e. from the outermost condition to the
+/// innermost one.
+///
+/// Modifiers that the spec doesn't apply are ignored, as are the ones of the
+/// spec that are missing from `fn_sol`.
+fn check_modifier_order(
+    fn_hir: &hir::FunctionDefinition,
+    fn_sol: &pt::FunctionDefinition,
+    ctx: &Context,
+) -> Option<Violation> {
+    let expected = fn_hir.modifiers.as_ref()?;
+    let applied: Vec<&str> = fn_sol
+        .attributes
+        .iter()
+        .filter_map(|attribute| match attribute {
+            pt::FunctionAttribute::BaseOrModifier(_, base) => {
+                base.name.identifiers.last().map(|id| id.name.as_str())
+            }
+            _ => None,
+        })
+        .filter(|name| expected.iter().any(|modifier| modifier == name))
+        .collect();
+    let expected_applied =
+        expected.iter().filter(|modifier| applied.contains(&modifier.as_str()));
+    if applied.iter().copied().eq(expected_applied.map(String::as_str)) {
+        return None;
+    }
+
+    Some(Violation::new(
+        ViolationKind::ModifierOrderMismatch(
+            fn_hir.identifier.clone(),
+            expected.join(" "),
+        ),
+        sol_location(ctx, fn_sol.loc.start()),
+    ))
+}
+
+/// Checks that every modifier in the Solidity contract that follows bulloak's
+/// naming is applied to at least one function.
+///
+/// Only modifiers whose name starts with `when` or `given` are considered,
+/// since any other modifier could be a helper that the spec knows nothing
+/// about.
+fn check_unused_modifiers(
+    contract_sol: &pt::ContractDefinition,
+    ctx: &Context,
+) -> Vec<Violation> {
+    let fns_sol = contract_sol.parts.iter().filter_map(|part| match part {
+        pt::ContractPart::FunctionDefinition(fn_sol) => Some(fn_sol),
+        _ => None,
+    });
+
+    let used: BTreeSet<&str> = fns_sol
+        .clone()
+        .flat_map(|fn_sol| fn_sol.attributes.iter())
+        .filter_map(|attribute| match attribute {
+            pt::FunctionAttribute::BaseOrModifier(_, base) => {
+                base.name.identifiers.last().map(|id| id.name.as_str())
+            }
+            _ => None,
+        })
+        .collect();
+
+    fns_sol
+        .filter(|fn_sol| matches!(fn_sol.ty, pt::FunctionTy::Modifier))
+        .filter_map(|fn_sol| {
+            fn_sol.name.as_ref().map(|name| (fn_sol, &name.name))
+        })
+        .filter(|(_, name)| {
+            (name.starts_with("when") || name.starts_with("given"))
+                && !used.contains(name.as_str())
+        })
+        .map(|(fn_sol, name)| {
+            Violation::new(
+                ViolationKind::UnusedModifier(name.clone()),
+                sol_location(ctx, fn_sol.loc.start()),
+            )
+        })
+        .collect()
+}
@@ -1,24 +1,41 @@
 //! Check module utilities.
 
-/// Converts the start offset of a `Loc` to `(line, col)`. Modified from <https://github.com/foundry-rs/foundry/blob/45b9dccdc8584fb5fbf55eb190a880d4e3b0753f/fmt/src/helpers.rs#L54-L70>
+use std::{
+    env,
+    path::{Path, PathBuf},
+};
+
+/// Converts the start offset of a `Loc` to `(line, col)`, both starting at
+/// `1`. The column counts characters, not bytes.
 pub fn offset_to_line_column(content: &str, start: usize) -> (usize, usize) {
     debug_assert!(content.len() > start);
 
-    // first line is `1`
-    let mut line_counter = 1;
-    for (offset, c) in content.chars().enumerate() {
-        if c == '\n' {
-            line_counter += 1;
-        }
-        if offset > start {
-            return (line_counter, offset - start);
-        }
-    }
-
-    unreachable!("content.len() > start")
+    let before = &content[..start];
+    let line = before.matches('\n').count() + 1;
+    let column = before.chars().rev().take_while(|&c| c != '\n').count() + 1;
+    (line, column)
 }
 
 /// Returns the line where a byte offset is found.
 pub fn offset_to_line(content: &str, start: usize) -> usize {
     offset_to_line_column(content, start).0
 }
+
+/// Returns `path` relative to the current working directory.
+///
+/// Paths outside of the current working directory are returned as is.
+#[must_use]
+pub fn relative_to_cwd(path: &Path) -> PathBuf {
+    env::current_dir()
+        .ok()
+        .and_then(|cwd| path.strip_prefix(cwd).ok().map(Path::to_path_buf))
+        .unwrap_or_else(|| path.to_path_buf())
+}
+
+/// Returns the name of the file at `path` up to its first extension, e.g.
+/// `Foo` for `test/Foo.t.sol`.
+#[must_use]
+pub fn file_stem(path: &Path) -> Option<&str> {
+    let name = path.file_name()?.to_str()?;
+    name.split('.').next()
+}
@@ -1,7 +1,7 @@
 //! Defines a rule-checking error object.
 use std::{borrow::Cow, collections::HashSet, fmt};
 
-use bulloak_syntax::FrontendError;
+use bulloak_syntax::{Diagnostic, FrontendError, Position, Span};
 use forge_fmt::{
     parse,
     solang_ext::{CodeLocationExt, SafeUnwrap},
@@ -17,7 +17,7 @@ use super::{context::Context, location::Location};
 use crate::{
     config::Config,
     hir::{self, Hir},
-    sol::{self, find_contract, find_matching_fn},
+    sol::{self, find_contract, find_matching_fn, find_pragma},
 };
 
 /// An error that occurred while checking specification rules between
@@ -41,6 +41,13 @@ impl Violation {
     pub fn is_fixable(&self) -> bool {
         self.kind.is_fixable()
     }
+
+    /// Returns this violation with the filename of its location relative to
+    /// the current working directory.
+    #[must_use]
+    pub fn relative(self) -> Self {
+        Self { kind: self.kind, location: self.location.relative() }
+    }
 }
 
 /// The type of an error that occurred while checking specification rules
@@ -84,11 +91,69 @@ pub enum ViolationKind {
     #[error("function \"{}\" is missing in .sol", .0.identifier.clone())]
     MatchingFunctionMissing(hir::FunctionDefinition, usize),
 
+    /// Found a test function that doesn't correspond to any branch of the
+    /// tree.
+    ///
+    /// (sol function name)
+    #[error("function \"{0}\" is not generated by the tree")]
+    ExtraFunction(String),
+
+    /// Found a modifier following bulloak's naming that isn't applied to
+    /// any function.
+    ///
+    /// (sol modifier name)
+    #[error("modifier \"{0}\" is not used by any function")]
+    UnusedModifier(String),
+
+    /// An action comment appears out of order in the body of a test.
+    ///
+    /// (comment, function name)
+    #[error("comment \"{0}\" is out of order in function \"{1}\"")]
+    CommentOrderMismatch(String, String),
+
+    /// The modifiers of a test are applied in a different order than the
+    /// conditions they come from are nested in.
+    ///
+    /// (function name, expected modifiers)
+    #[error("modifiers of function \"{0}\" are applied out of order")]
+    ModifierOrderMismatch(String, String),
+
+    /// The pragma directive doesn't match the configured Solidity version.
+    ///
+    /// (configured version, sol version)
+    #[error("pragma \"{1}\" doesn't match the configured version \"{0}\"")]
+    PragmaMismatch(String, String),
+
+    /// The Solidity file isn't named like its contract.
+    ///
+    /// (contract name, file stem)
+    #[error("contract \"{0}\" doesn't match the name of its file \"{1}\"")]
+    FileNameMismatch(String, String),
+
     /// The parsing of a tree or a Solidity file failed.
     #[error("{}", format_frontend_error(.0))]
     ParsingFailed(#[from] anyhow::Error),
 }
 
+impl From<&Violation> for Diagnostic {
+    fn from(violation: &Violation) -> Self {
+        // Violations only know the position they start at, if any.
+        let span = match violation.location {
+            Location::Code(_, line, column) => {
+                let position = Position::new(0, line, column);
+                Span::new(position, position)
+            }
+            Location::File(_) => Span::default(),
+        };
+
+        Diagnostic::error(
+            violation.kind.code(),
+            violation.kind.to_string(),
+            span,
+        )
+    }
+}
+
 impl fmt::Display for Violation {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         writeln!(f, "{}: {}", "warn".yellow(), self.kind)?;
@@ -135,6 +200,60 @@ fn format_frontend_error(error: &anyhow::Error) -> String {
 }
 
 impl ViolationKind {
+    /// Returns the stable code of this kind of violation.
+    #[must_use]
+    pub const fn code(&self) -> &'static str {
+        match self {
+            ViolationKind::ContractMissing(_) => "B501",
+            ViolationKind::ContractNameNotMatches(_, _) => "B502",
+            ViolationKind::SolidityFileMissing(_) => "B503",
+            ViolationKind::FileUnreadable => "B504",
+            ViolationKind::FunctionOrderMismatch(_, _, _) => "B505",
+            ViolationKind::MatchingFunctionMissing(_, _) => "B506",
+            ViolationKind::ExtraFunction(_) => "B507",
+            ViolationKind::UnusedModifier(_) => "B508",
+            ViolationKind::CommentOrderMismatch(_, _) => "B510",
+            ViolationKind::PragmaMismatch(_, _) => "B511",
+            ViolationKind::FileNameMismatch(_, _) => "B512",
+            ViolationKind::ModifierOrderMismatch(_, _) => "B513",
+            ViolationKind::ParsingFailed(_) => "B509",
+        }
+    }
+
+    /// Returns the stable name of this kind of violation, e.g.
+    /// `matching_function_missing`.
+    ///
+    /// Unlike the message, the name doesn't change between releases, so it
+    /// can be relied on by tools that consume `bulloak check`'s output.
+    #[must_use]
+    pub const fn name(&self) -> &'static str {
+        match self {
+            ViolationKind::ContractMissing(_) => "contract_missing",
+            ViolationKind::ContractNameNotMatches(_, _) => {
+                "contract_name_not_matches"
+            }
+            ViolationKind::SolidityFileMissing(_) => "solidity_file_missing",
+            ViolationKind::FileUnreadable => "file_unreadable",
+            ViolationKind::FunctionOrderMismatch(_, _, _) => {
+                "function_order_mismatch"
+            }
+            ViolationKind::MatchingFunctionMissing(_, _) => {
+                "matching_function_missing"
+            }
+            ViolationKind::ExtraFunction(_) => "extra_function",
+            ViolationKind::UnusedModifier(_) => "unused_modifier",
+            ViolationKind::CommentOrderMismatch(_, _) => {
+                "comment_order_mismatch"
+            }
+            ViolationKind::PragmaMismatch(_, _) => "pragma_mismatch",
+            ViolationKind::FileNameMismatch(_, _) => "file_name_mismatch",
+            ViolationKind::ModifierOrderMismatch(_, _) => {
+                "modifier_order_mismatch"
+            }
+            ViolationKind::ParsingFailed(_) => "parsing_failed",
+        }
+    }
+
     /// Whether this violation kind is fixable.
     pub fn is_fixable(&self) -> bool {
         matches!(
@@ -143,6 +262,7 @@ impl ViolationKind {
                 | ViolationKind::ContractNameNotMatches(_, _)
                 | ViolationKind::FunctionOrderMismatch(_, _, _)
                 | ViolationKind::MatchingFunctionMissing(_, _)
+                | ViolationKind::PragmaMismatch(_, _)
         )
     }
 
@@ -164,6 +284,29 @@ impl ViolationKind {
             ViolationKind::FunctionOrderMismatch(_, _, _) => {
                 "consider reordering the function in the file".into()
             }
+            ViolationKind::ExtraFunction(_) => {
+                "consider removing the function or adding its branch to the tree"
+                    .into()
+            }
+            ViolationKind::UnusedModifier(_) => {
+                "consider removing the modifier or applying it to a function"
+                    .into()
+            }
+            ViolationKind::CommentOrderMismatch(_, _) => {
+                "consider reordering the comments to match the tree".into()
+            }
+            ViolationKind::ModifierOrderMismatch(_, expected) => {
+                format!(r#"consider applying the modifiers as "{expected}""#)
+                    .into()
+            }
+            ViolationKind::PragmaMismatch(version, _) => {
+                format!("consider changing the pragma to \"solidity {version}\"")
+                    .into()
+            }
+            ViolationKind::FileNameMismatch(name, _) => {
+                format!(r#"consider renaming the file to "{name}.t.sol""#)
+                    .into()
+            }
             _ => return None,
         };
 
@@ -174,9 +317,26 @@ impl ViolationKind {
     pub fn fix(&self, mut ctx: Context) -> Context {
         match self {
             ViolationKind::ContractMissing(_) => {
+                let sentinel = sol::sentinel(&ctx.hir);
                 let pt = sol::Translator::new(&Config::default())
+                    .with_sentinel(&sentinel)
                     .translate(&ctx.hir);
-                let source = sol::Formatter::new().emit(pt.clone());
+                let source = sol::Formatter::new()
+                    .with_sentinel(&sentinel)
+                    .emit(pt.clone());
+                let parsed =
+                    parse(&source).expect("should parse Solidity string");
+                ctx.from_parsed(parsed)
+            }
+            ViolationKind::PragmaMismatch(version, _) => {
+                let Some((loc, _)) = find_pragma(&ctx.pt) else {
+                    return ctx;
+                };
+                let mut source = ctx.src.clone();
+                source.replace_range(
+                    loc.start()..loc.end(),
+                    &format!("pragma solidity {version}"),
+                );
                 let parsed =
                     parse(&source).expect("should parse Solidity string");
                 ctx.from_parsed(parsed)
@@ -404,3 +564,48 @@ pub fn fix_order(
     let parsed = parse(&source).expect("should parse solidity string");
     ctx.from_parsed(parsed)
 }
+
+/// Removes the function named `name` from the Solidity contract in `ctx`.
+///
+/// The function is removed along with the comments right above it, e.g. its
+/// natspec, the indentation before it and the line break after it. Nothing
+/// changes if the contract has no such function.
+///
+/// # Panics
+/// If the resulting Solidity string fails to parse
+#[must_use]
+pub fn remove_function(name: &str, ctx: Context) -> Context {
+    let Some(contract_sol) = find_contract(&ctx.pt) else {
+        return ctx;
+    };
+    let Some(part) = contract_sol.parts.iter().find(|part| {
+        matches!(part, ContractPart::FunctionDefinition(f)
+            if f.name.safe_unwrap().name == name)
+    }) else {
+        return ctx;
+    };
+
+    let loc = part.loc();
+    let mut start = ctx.src[..loc.start()].trim_end_matches([' ', '\t']).len();
+    // Drop the comment lines attached to the function, stopping at the first
+    // line that isn't one, e.g. a blank line or the end of another item.
+    while let Some(line_end) = ctx.src[..start].strip_suffix('\n') {
+        let line_start = line_end.rfind('\n').map_or(0, |i| i + 1);
+        let line = line_end[line_start..].trim();
+        let is_comment = line.starts_with("//")
+            || line.starts_with("/*")
+            || line.starts_with('*');
+        if !is_comment {
+            break;
+        }
+        start = line_start;
+    }
+    let end = loc.end() + ctx.src[loc.end()..].len()
+        - ctx.src[loc.end()..]
+            .trim_start_matches([' ', '\t', '\r', '\n'])
+            .len();
+    let source = format!("{}{}", &ctx.src[..start], &ctx.src[end..]);
+
+    let parsed = parse(&source).expect("should parse solidity string");
+    ctx.from_parsed(parsed)
+}
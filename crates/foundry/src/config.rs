@@ -1,33 +1,764 @@
 //! `bulloak-core`'s configuration.
 
-use std::path::PathBuf;
+use std::{fmt, path::PathBuf, str::FromStr};
 
-use crate::constants::DEFAULT_SOL_VERSION;
+use bulloak_syntax::KeywordAliases;
+
+use crate::{
+    constants::{DEFAULT_SOL_VERSION, INTERNAL_DEFAULT_INDENTATION},
+    sol,
+};
 
 /// `bulloak-core`'s configuration.
 ///
 /// Note that configuration coming from the command line is aggregated to this
 /// struct only if it makes sense. For example, the `--fix` flag, doesn't make
 /// sense in the context of `bulloak-core`.
+///
+/// The default value of every field is documented below. These defaults are
+/// part of the public API: changing any of them changes the output of
+/// `bulloak` for users that don't pass the corresponding option, so it must
+/// be treated as a breaking change.
 #[derive(Debug, Clone)]
+#[allow(clippy::struct_excessive_bools)]
 pub struct Config {
     /// The set of tree files to work on.
+    ///
+    /// Defaults to no files.
     pub files: Vec<PathBuf>,
+    /// Custom words to recognize as keywords in the trees, mapped to the
+    /// keyword they stand for, e.g. `if` to `when`.
+    ///
+    /// Defaults to no aliases.
+    pub keyword_aliases: KeywordAliases,
     /// Whether to emit modifiers.
+    ///
+    /// Defaults to `false`, i.e. modifiers are emitted.
     pub skip_modifiers: bool,
     /// Sets a Solidity version for the test contracts.
+    ///
+    /// Defaults to [`DEFAULT_SOL_VERSION`].
     pub solidity_version: String,
+    /// How to render `solidity_version` in the pragma directive.
+    ///
+    /// Defaults to [`PragmaStyle::Verbatim`].
+    pub pragma_style: PragmaStyle,
+    /// The number of spaces each level of the code emitted before formatting
+    /// is indented by, e.g. the functions inserted by `bulloak check --fix`.
+    ///
+    /// Defaults to `2`.
+    pub indent: usize,
     /// Whether to add `vm.skip(true)` at the beginning of each test.
+    ///
+    /// Defaults to `false`.
     pub emit_vm_skip: bool,
+    /// The contract the test contracts inherit from whenever they need
+    /// cheatcodes or assertions.
+    ///
+    /// Defaults to [`TestBase::default`], i.e. forge-std's `Test`.
+    pub test_base: TestBase,
+    /// Whether to report test functions in the Solidity file that don't
+    /// correspond to any branch in the tree.
+    ///
+    /// Defaults to `false`.
+    pub strict_extras: bool,
+    /// The maximum length of a generated function name, if any.
+    ///
+    /// Longer names are reported as lint warnings. Defaults to `None`, i.e.
+    /// names are not checked.
+    pub max_name_length: Option<usize>,
+    /// The maximum number of words in a condition or action title, if any.
+    ///
+    /// Longer titles are reported as lint warnings. Defaults to `None`, i.e.
+    /// titles are not checked.
+    pub max_title_words: Option<usize>,
+    /// Named addresses to label with `vm.label` in every test that mentions
+    /// them.
+    ///
+    /// Defaults to no names.
+    pub labels: Vec<String>,
+    /// Whether to emit action descriptions as `// TODO:` comments.
+    ///
+    /// Defaults to `false`.
+    pub descriptions_as_todos: bool,
+    /// The paths of the files to import in the test contracts, e.g. a shared
+    /// constants file.
+    ///
+    /// Defaults to no imports.
+    pub imports: Vec<String>,
+    /// Whether to omit the pragma directive, e.g. when the generated code is
+    /// inserted in a file that already declares it.
+    ///
+    /// Defaults to `false`.
+    pub skip_pragma: bool,
+    /// The features to enable with a `pragma experimental` directive after the
+    /// version pragma, e.g. `SMTChecker`.
+    ///
+    /// They are omitted along with the version pragma when
+    /// [`Config::skip_pragma`] is set.
+    ///
+    /// Defaults to no features.
+    pub experimental_pragmas: Vec<String>,
+    /// The solhint rules to disable with a `// solhint-disable` directive
+    /// right after the license line, e.g. `no-empty-blocks`.
+    ///
+    /// Defaults to no rules, i.e. no directive is emitted.
+    pub solhint_disable: Vec<String>,
+    /// Templates to expand into the body of the tests whose name starts with
+    /// a given prefix.
+    ///
+    /// Defaults to no templates.
+    pub body_templates: Vec<BodyTemplate>,
+    /// A statement to emit at the top of the body of every test, e.g.
+    /// `_test();`.
+    ///
+    /// Defaults to `None`, i.e. test bodies only hold the action comments.
+    pub default_body_statement: Option<String>,
+    /// Whether to sort modifier definitions alphabetically when combining
+    /// the roots of a `.tree` file.
+    ///
+    /// Defaults to `false`, i.e. each modifier is defined right before the
+    /// first test that uses it.
+    pub sort_modifiers: bool,
+    /// The path of the tree file to link every generated function to, with a
+    /// `/// @dev spec: <path>:L<line>` comment.
+    ///
+    /// Defaults to `None`, i.e. no links are emitted.
+    pub spec_path: Option<PathBuf>,
+    /// The hash of the tree file to store in a
+    /// `// @bulloak:tree-hash <hash>` comment at the top of the generated
+    /// file.
+    ///
+    /// Defaults to `None`, i.e. no hash is stored.
+    pub tree_hash: Option<String>,
+    /// The prefix of the tests marked with `[fuzz]`.
+    ///
+    /// It composes with the rest of the name, e.g. a reverting fuzz test is
+    /// named `testFuzz_RevertWhen_...`. Defaults to `testFuzz_`.
+    pub fuzz_prefix: String,
+    /// Whether to emit a `setUp` function at the start of the test contract.
+    ///
+    /// Defaults to `false`.
+    pub emit_setup: bool,
+    /// Whether to emit the title of the condition a modifier comes from as a
+    /// comment above the modifier.
+    ///
+    /// Defaults to `false`.
+    pub modifier_comments: bool,
+    /// Whether to open every test with a comment per condition it's under,
+    /// e.g. `// GIVEN: the vault is paused`.
+    ///
+    /// Defaults to `false`.
+    pub condition_comments: bool,
+    /// Whether to emit the comments of every test above its signature
+    /// instead of inside its body.
+    ///
+    /// Defaults to `false`, i.e. comments go inside the body.
+    pub comments_above: bool,
+    /// Whether to emit a `// @bulloak:generated` comment above every
+    /// generated function, so that tools can tell them apart from
+    /// hand-written ones.
+    ///
+    /// Defaults to `false`.
+    pub mark_generated: bool,
+    /// Whether to emit a `receive() external payable {}` function when the
+    /// test contract has tests marked with `[payable]`.
+    ///
+    /// Defaults to `false`.
+    pub emit_receive: bool,
+    /// Whether to emit an `assertEq(actual, <expected>);` placeholder in the
+    /// tests whose action states the value it expects, e.g.
+    /// `it should equal MAX_UINT`.
+    ///
+    /// Defaults to `false`.
+    pub emit_assertions: bool,
+    /// Whether to emit a `vm.expectRevert` of the custom error in the tests
+    /// whose action reverts with one, e.g.
+    /// `it should revert with Unauthorized(caller)`.
+    ///
+    /// Defaults to `false`.
+    pub emit_expect_revert: bool,
+    /// Whether to emit a low-level call that must succeed in the tests whose
+    /// action never reverts, e.g. `it never reverts`.
+    ///
+    /// Defaults to `false`.
+    pub emit_no_revert: bool,
+    /// Whether to add an `// Arrange`, `// Act` and `// Assert` skeleton
+    /// below the comments of every test.
+    ///
+    /// Defaults to `false`.
+    pub aaa: bool,
+    /// Whether to label the descriptions of actions that have exactly
+    /// three of them with `Arrange:`, `Act:` and `Assert:`.
+    ///
+    /// Defaults to `false`.
+    pub aaa_from_descriptions: bool,
+    /// Whether to turn action descriptions that call a contract or emit an
+    /// event into `vm.expectCall` and `vm.expectEmit` stubs, e.g. `calls the
+    /// oracle` or `emits a {Transfer} event`.
+    ///
+    /// Defaults to `false`.
+    pub smart_descriptions: bool,
+    /// Whether to leave out the actions that repeat the title of an earlier
+    /// action of the same test.
+    ///
+    /// Defaults to `false`.
+    pub dedup_actions: bool,
+    /// Whether to precede every generated comment with a
+    /// `// forgefmt: disable-next-line` directive, so that forge-fmt keeps
+    /// it verbatim.
+    ///
+    /// Defaults to `false`.
+    pub forgefmt_disable: bool,
+    /// Whether `bulloak check` verifies that the pragma directive of each
+    /// Solidity file matches [`Config::pragma_version`].
+    ///
+    /// Defaults to `false`.
+    pub check_pragma: bool,
+    /// Whether to check that the contract of each tree is named like its
+    /// Solidity file, e.g. `contract Foo` in `Foo.t.sol`.
+    ///
+    /// Defaults to `false`.
+    pub check_file_name: bool,
+    /// Whether `bulloak check` reports the modifiers named like conditions,
+    /// e.g. `whenTheCallerIsTheOwner`, that no function applies.
+    ///
+    /// Defaults to `false`.
+    pub check_unused_modifiers: bool,
+    /// Whether `bulloak check` reports the action comments of a test that are
+    /// out of order with respect to the actions of the tree.
+    ///
+    /// Defaults to `false`.
+    pub check_comment_order: bool,
+    /// Whether `bulloak check` reports the modifiers of a test that are
+    /// applied out of order with respect to the conditions of the tree.
+    ///
+    /// Defaults to `false`.
+    pub check_modifier_order: bool,
+    /// Whether to emit revert tests after every other test of a contract.
+    ///
+    /// Defaults to `false`.
+    pub reverts_last: bool,
+    /// Whether to group the tests under `given` conditions and the tests
+    /// under `when` conditions into two sections, each opened by a banner
+    /// comment.
+    ///
+    /// Defaults to `false`.
+    pub group_by_keyword: bool,
+    /// Whether combining a tree file with two roots of the same name is an
+    /// error rather than a lint.
+    ///
+    /// Defaults to `false`.
+    pub deny_duplicate_roots: bool,
+    /// Whether to warn about actions that don't start with `it` or `It`.
+    ///
+    /// Defaults to `false`.
+    pub lint_it_keyword: bool,
+    /// Whether to warn about actions that repeat the title of an earlier
+    /// action of the same test.
+    ///
+    /// Defaults to `false`.
+    pub lint_duplicate_actions: bool,
+    /// The Solidity file `bulloak check` verifies every tree against, instead
+    /// of the `.t.sol` file next to each tree.
+    ///
+    /// Since a shared file may be specified by many trees, each tree only has
+    /// to match a subset of its functions, in any order.
+    ///
+    /// Defaults to `None`.
+    pub shared_sol: Option<PathBuf>,
+    /// Whether `bulloak check` only verifies that the contract and the
+    /// functions generated from the spec are present in the Solidity file.
+    ///
+    /// Defaults to `false`.
+    pub check_names_only: bool,
+    /// Whether the root name is a path, e.g. `token/Foo_Test`, whose
+    /// directories are left out of the contract name.
+    ///
+    /// Defaults to `false`.
+    pub root_as_path: bool,
+    /// The path of the tree file being translated.
+    ///
+    /// The paths in `@extends` directives are relative to its directory, or
+    /// to the working directory if it's not set.
+    ///
+    /// Defaults to `None`.
+    pub tree_path: Option<PathBuf>,
 }
 
 impl Default for Config {
+    /// Returns the default configuration.
+    ///
+    /// Keep this in sync with the defaults documented on each field of
+    /// [`Config`].
     fn default() -> Self {
         Self {
             files: vec![],
+            keyword_aliases: KeywordAliases::new(),
             solidity_version: DEFAULT_SOL_VERSION.to_owned(),
+            pragma_style: PragmaStyle::default(),
+            indent: INTERNAL_DEFAULT_INDENTATION,
             emit_vm_skip: false,
+            test_base: TestBase::default(),
             skip_modifiers: false,
+            strict_extras: false,
+            max_name_length: None,
+            max_title_words: None,
+            labels: vec![],
+            descriptions_as_todos: false,
+            imports: vec![],
+            skip_pragma: false,
+            experimental_pragmas: vec![],
+            solhint_disable: vec![],
+            body_templates: vec![],
+            default_body_statement: None,
+            sort_modifiers: false,
+            spec_path: None,
+            tree_hash: None,
+            fuzz_prefix: "testFuzz_".to_owned(),
+            emit_setup: false,
+            modifier_comments: false,
+            condition_comments: false,
+            comments_above: false,
+            mark_generated: false,
+            emit_receive: false,
+            emit_assertions: false,
+            emit_expect_revert: false,
+            emit_no_revert: false,
+            aaa: false,
+            aaa_from_descriptions: false,
+            smart_descriptions: false,
+            dedup_actions: false,
+            forgefmt_disable: false,
+            check_pragma: false,
+            check_file_name: false,
+            check_unused_modifiers: false,
+            check_comment_order: false,
+            check_modifier_order: false,
+            reverts_last: false,
+            group_by_keyword: false,
+            deny_duplicate_roots: false,
+            lint_it_keyword: false,
+            lint_duplicate_actions: false,
+            shared_sol: None,
+            check_names_only: false,
+            root_as_path: false,
+            tree_path: None,
+        }
+    }
+}
+
+impl Config {
+    /// Returns the version requirement to emit in the pragma directive,
+    /// i.e. `solidity_version` rendered according to `pragma_style`.
+    #[must_use]
+    pub fn pragma_version(&self) -> String {
+        self.pragma_style.apply(&self.solidity_version)
+    }
+}
+
+/// A statement to emit in the body of every test whose name starts with
+/// `prefix`.
+///
+/// Parsed from `<prefix>=<template>`, e.g. `test_Revert=vm.expectRevert();`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BodyTemplate {
+    /// The prefix of the function names this template applies to.
+    pub prefix: String,
+    /// The statement to emit. `{{name}}` is replaced by the function name.
+    pub template: String,
+}
+
+impl BodyTemplate {
+    /// Returns the template in `templates` that applies to the function named
+    /// `name`.
+    ///
+    /// When several prefixes match, the longest one wins.
+    #[must_use]
+    pub fn find<'a>(templates: &'a [Self], name: &str) -> Option<&'a Self> {
+        templates
+            .iter()
+            .filter(|template| name.starts_with(&template.prefix))
+            .max_by_key(|template| template.prefix.len())
+    }
+
+    /// Expands this template for the function named `name`.
+    #[must_use]
+    pub fn expand(&self, name: &str) -> String {
+        self.template.replace("{{name}}", name)
+    }
+}
+
+impl FromStr for BodyTemplate {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once('=') {
+            Some((prefix, template))
+                if !prefix.is_empty() && !template.trim().is_empty() =>
+            {
+                let template = Self {
+                    prefix: prefix.to_owned(),
+                    template: template.trim().to_owned(),
+                };
+                crate::sol::validate_statement(&template.expand(prefix))
+                    .map_err(|err| format!("invalid body template: {err}"))?;
+                Ok(template)
+            }
+            _ => Err(format!(
+                "invalid body template: {s}, expected <prefix>=<template>"
+            )),
+        }
+    }
+}
+
+/// A custom word to recognize as a keyword.
+///
+/// Parsed from `<word>=<keyword>`, e.g. `if=when`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeywordAlias {
+    /// The custom word, in lowercase.
+    pub word: String,
+    /// The keyword the word stands for, i.e. `when`, `given` or `it`.
+    pub keyword: String,
+}
+
+impl FromStr for KeywordAlias {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let Some((word, keyword)) = s.split_once('=') else {
+            return Err(format!(
+                "invalid keyword alias: {s}, expected <word>=<keyword>"
+            ));
+        };
+        let keyword = keyword.to_lowercase();
+        if !matches!(keyword.as_str(), "when" | "given" | "it") {
+            return Err(format!(
+                "invalid keyword alias: {s}, expected one of `when`, `given` or `it` as the keyword"
+            ));
+        }
+        let is_word = !word.is_empty() && !word.contains(char::is_whitespace);
+        if !is_word {
+            return Err(format!(
+                "invalid keyword alias: {s}, expected a single word to alias"
+            ));
         }
+
+        Ok(Self { word: word.to_lowercase(), keyword })
+    }
+}
+
+/// A contract that test contracts inherit from, such as forge-std's `Test`
+/// or `PRBTest`.
+///
+/// Parsed from `<name>=<path>`, e.g. `PRBTest=@prb/test/src/PRBTest.sol`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TestBase {
+    /// The name of the contract.
+    pub name: String,
+    /// The path to import the contract from.
+    pub path: String,
+}
+
+impl TestBase {
+    /// Whether this is forge-std's `Test`, which also exports `console`.
+    #[must_use]
+    pub fn is_forge_std(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+impl Default for TestBase {
+    /// Returns forge-std's `Test`.
+    fn default() -> Self {
+        Self { name: "Test".to_owned(), path: "forge-std/Test.sol".to_owned() }
+    }
+}
+
+impl FromStr for TestBase {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (name, path) = match s.split_once('=') {
+            Some((name, path)) if !name.is_empty() && !path.is_empty() => {
+                (name, path)
+            }
+            _ => {
+                return Err(format!(
+                    "invalid test base: {s}, expected <name>=<path>"
+                ))
+            }
+        };
+
+        let mut chars = name.chars();
+        let is_identifier = chars
+            .next()
+            .is_some_and(|c| c.is_ascii_alphabetic() || c == '_' || c == '$')
+            && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$');
+        if !is_identifier {
+            return Err(format!(
+                "invalid test base: {s}, {name} is not a valid contract name"
+            ));
+        }
+        sol::validate_import(path)?;
+
+        Ok(Self { name: name.to_owned(), path: path.to_owned() })
+    }
+}
+
+/// The style of the version requirement in the emitted pragma directive.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PragmaStyle {
+    /// Emit the version exactly as provided, e.g. `pragma solidity 0.8.0;`.
+    #[default]
+    Verbatim,
+    /// Pin the version with `=`, e.g. `pragma solidity =0.8.0;`.
+    Exact,
+    /// Allow compatible versions with `^`, e.g. `pragma solidity ^0.8.0;`.
+    Caret,
+}
+
+impl PragmaStyle {
+    /// Renders `version` according to this style.
+    ///
+    /// Versions that already carry an operator or describe a range, such as
+    /// `>=0.8.0 <0.9.0`, are always emitted verbatim.
+    #[must_use]
+    pub fn apply(self, version: &str) -> String {
+        let is_bare = version.starts_with(|c: char| c.is_ascii_digit());
+        match self {
+            Self::Exact if is_bare => format!("={version}"),
+            Self::Caret if is_bare => format!("^{version}"),
+            _ => version.to_owned(),
+        }
+    }
+}
+
+impl FromStr for PragmaStyle {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "verbatim" => Ok(Self::Verbatim),
+            "exact" => Ok(Self::Exact),
+            "caret" => Ok(Self::Caret),
+            _ => Err(format!("unknown pragma style: {s}")),
+        }
+    }
+}
+
+impl fmt::Display for PragmaStyle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let style = match self {
+            Self::Verbatim => "verbatim",
+            Self::Exact => "exact",
+            Self::Caret => "caret",
+        };
+        write!(f, "{style}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::{BodyTemplate, Config, KeywordAlias, PragmaStyle, TestBase};
+
+    #[test]
+    #[allow(clippy::too_many_lines)]
+    fn pins_default_values() {
+        // Changing any of these values is a breaking change. If you are
+        // updating this test, make sure to document the new default.
+        let Config {
+            files,
+            keyword_aliases,
+            skip_modifiers,
+            solidity_version,
+            pragma_style,
+            indent,
+            emit_vm_skip,
+            test_base,
+            strict_extras,
+            max_name_length,
+            max_title_words,
+            labels,
+            descriptions_as_todos,
+            imports,
+            skip_pragma,
+            experimental_pragmas,
+            solhint_disable,
+            body_templates,
+            default_body_statement,
+            sort_modifiers,
+            spec_path,
+            tree_hash,
+            fuzz_prefix,
+            emit_setup,
+            modifier_comments,
+            condition_comments,
+            comments_above,
+            mark_generated,
+            emit_receive,
+            emit_assertions,
+            emit_expect_revert,
+            emit_no_revert,
+            aaa,
+            aaa_from_descriptions,
+            smart_descriptions,
+            dedup_actions,
+            forgefmt_disable,
+            check_pragma,
+            check_file_name,
+            check_unused_modifiers,
+            check_comment_order,
+            check_modifier_order,
+            reverts_last,
+            group_by_keyword,
+            deny_duplicate_roots,
+            lint_it_keyword,
+            lint_duplicate_actions,
+            shared_sol,
+            check_names_only,
+            root_as_path,
+            tree_path,
+        } = Config::default();
+
+        assert!(files.is_empty());
+        assert!(keyword_aliases.is_empty());
+        assert!(!skip_modifiers);
+        assert_eq!(solidity_version, "0.8.0");
+        assert_eq!(pragma_style, PragmaStyle::Verbatim);
+        assert_eq!(indent, 2);
+        assert!(!emit_vm_skip);
+        assert_eq!(test_base.name, "Test");
+        assert_eq!(test_base.path, "forge-std/Test.sol");
+        assert!(!strict_extras);
+        assert_eq!(max_name_length, None);
+        assert_eq!(max_title_words, None);
+        assert!(labels.is_empty());
+        assert!(!descriptions_as_todos);
+        assert!(imports.is_empty());
+        assert!(!skip_pragma);
+        assert!(experimental_pragmas.is_empty());
+        assert!(solhint_disable.is_empty());
+        assert!(body_templates.is_empty());
+        assert_eq!(default_body_statement, None);
+        assert!(!sort_modifiers);
+        assert_eq!(spec_path, None);
+        assert_eq!(tree_hash, None);
+        assert_eq!(fuzz_prefix, "testFuzz_");
+        assert!(!emit_setup);
+        assert!(!modifier_comments);
+        assert!(!condition_comments);
+        assert!(!comments_above);
+        assert!(!mark_generated);
+        assert!(!emit_receive);
+        assert!(!emit_assertions);
+        assert!(!emit_expect_revert);
+        assert!(!emit_no_revert);
+        assert!(!aaa);
+        assert!(!aaa_from_descriptions);
+        assert!(!smart_descriptions);
+        assert!(!dedup_actions);
+        assert!(!forgefmt_disable);
+        assert!(!check_pragma);
+        assert!(!check_file_name);
+        assert!(!check_unused_modifiers);
+        assert!(!check_comment_order);
+        assert!(!check_modifier_order);
+        assert!(!reverts_last);
+        assert!(!group_by_keyword);
+        assert!(!deny_duplicate_roots);
+        assert!(!lint_it_keyword);
+        assert!(!lint_duplicate_actions);
+        assert_eq!(shared_sol, None);
+        assert!(!check_names_only);
+        assert!(!root_as_path);
+        assert_eq!(tree_path, None);
+    }
+
+    #[test]
+    fn parses_body_templates() {
+        let template: BodyTemplate =
+            "test_Revert=vm.expectRevert();".parse().unwrap();
+        assert_eq!(template.prefix, "test_Revert");
+        assert_eq!(template.template, "vm.expectRevert();");
+
+        assert!("test_Revert".parse::<BodyTemplate>().is_err());
+        assert!("=vm.expectRevert();".parse::<BodyTemplate>().is_err());
+        assert!("test_Revert=".parse::<BodyTemplate>().is_err());
+        assert!("test_=x(".parse::<BodyTemplate>().is_err());
+    }
+
+    #[test]
+    fn parses_keyword_aliases() {
+        let alias: KeywordAlias = "Scenario=Given".parse().unwrap();
+        assert_eq!(alias.word, "scenario");
+        assert_eq!(alias.keyword, "given");
+
+        assert!("if".parse::<KeywordAlias>().is_err());
+        assert!("if=unless".parse::<KeywordAlias>().is_err());
+        assert!("=when".parse::<KeywordAlias>().is_err());
+    }
+
+    #[test]
+    fn parses_test_bases() {
+        let base: TestBase =
+            "PRBTest=@prb/test/src/PRBTest.sol".parse().unwrap();
+        assert_eq!(base.name, "PRBTest");
+        assert_eq!(base.path, "@prb/test/src/PRBTest.sol");
+        assert!(!base.is_forge_std());
+        assert!(TestBase::default().is_forge_std());
+
+        assert!("PRBTest".parse::<TestBase>().is_err());
+        assert!("=@prb/test/src/PRBTest.sol".parse::<TestBase>().is_err());
+        assert!("PRB Test=@prb/test/src/PRBTest.sol"
+            .parse::<TestBase>()
+            .is_err());
+        assert!("1Test=@prb/test/src/PRBTest.sol".parse::<TestBase>().is_err());
+        assert!(r#"PRBTest=@prb/test"; //.sol"#.parse::<TestBase>().is_err());
+        assert!("PRBTest=@prb/test\n.sol".parse::<TestBase>().is_err());
+    }
+
+    #[test]
+    fn picks_the_longest_matching_body_template() {
+        let templates: Vec<BodyTemplate> = vec![
+            r#"test_=emit Log("{{name}}");"#.parse().unwrap(),
+            "test_Revert=vm.expectRevert();".parse().unwrap(),
+        ];
+
+        let name = "test_RevertWhen_Zero";
+        let template = BodyTemplate::find(&templates, name).unwrap();
+        assert_eq!(template.expand(name), "vm.expectRevert();");
+        let name = "test_WhenZero";
+        let template = BodyTemplate::find(&templates, name).unwrap();
+        assert_eq!(template.expand(name), r#"emit Log("test_WhenZero");"#);
+        assert!(BodyTemplate::find(&templates, "testFuzz_WhenZero").is_none());
+    }
+
+    #[test]
+    fn applies_pragma_styles() {
+        assert_eq!(PragmaStyle::Verbatim.apply("0.8.0"), "0.8.0");
+        assert_eq!(PragmaStyle::Exact.apply("0.8.0"), "=0.8.0");
+        assert_eq!(PragmaStyle::Caret.apply("0.8.0"), "^0.8.0");
+    }
+
+    #[test]
+    fn keeps_versions_with_operators() {
+        assert_eq!(PragmaStyle::Exact.apply("^0.8.0"), "^0.8.0");
+        assert_eq!(PragmaStyle::Caret.apply("=0.8.0"), "=0.8.0");
+        assert_eq!(
+            PragmaStyle::Caret.apply(">=0.8.0 <0.9.0"),
+            ">=0.8.0 <0.9.0"
+        );
+    }
+
+    #[test]
+    fn parses_pragma_styles() {
+        assert_eq!("exact".parse(), Ok(PragmaStyle::Exact));
+        assert_eq!("caret".parse(), Ok(PragmaStyle::Caret));
+        assert_eq!("verbatim".parse(), Ok(PragmaStyle::Verbatim));
+        assert!("loose".parse::<PragmaStyle>().is_err());
     }
 }
@@ -1,7 +1,7 @@
 //! Constants.
 
-/// Default indentation used internally.
-pub(crate) const INTERNAL_DEFAULT_INDENTATION: usize = 2;
+/// Default indentation used internally, before formatting.
+pub const INTERNAL_DEFAULT_INDENTATION: usize = 2;
 /// Default solidity version used internally.
 pub const DEFAULT_SOL_VERSION: &str = "0.8.0";
 /// The separator used between contract name and function name when parsing
@@ -10,3 +10,11 @@ pub const CONTRACT_IDENTIFIER_SEPARATOR: &str = "::";
 /// The separator used between trees when parsing `.tree` files with multiple
 /// trees.
 pub const TREES_SEPARATOR: &str = "\n\n";
+/// The comments of the Arrange / Act / Assert skeleton emitted with `--aaa`.
+pub(crate) const AAA_STEPS: [&str; 3] = ["Arrange", "Act", "Assert"];
+/// The comment that marks the functions generated by bulloak.
+pub const GENERATED_MARKER: &str = "@bulloak:generated";
+/// The comment that stores the hash of the tree a file was generated from.
+pub const TREE_HASH_MARKER: &str = "@bulloak:tree-hash";
+/// The directive that keeps forge-fmt from reflowing the next line.
+pub(crate) const FORGEFMT_DISABLE: &str = "forgefmt: disable-next-line";
@@ -2,7 +2,9 @@
 //! combiner.
 use std::{collections::HashSet, fmt, mem, result};
 
-use bulloak_syntax::{utils::upper_first_letter, FrontendError, Span};
+use bulloak_syntax::{
+    utils::upper_first_letter, Diagnostic, FrontendError, Span,
+};
 use thiserror::Error;
 
 use super::{ContractDefinition, Hir, Root};
@@ -71,13 +73,46 @@ pub enum ErrorKind {
     /// one of the tree roots.
     #[error("separator missing at tree root #{0}. Expected to find `::` between the contract name and the function name when multiple roots exist")]
     SeparatorMissing(Index),
+
+    /// Two tree roots have the same identifier, which merges their branches
+    /// into the same set of tests.
+    ///
+    /// Only reported when duplicate roots are denied, see
+    /// [`Combiner::with_duplicate_roots_denied`].
+    #[error("tree root #{1} duplicates the root '{0}'")]
+    DuplicateRoot(Identifier, Index),
+}
+
+impl ErrorKind {
+    /// Returns the stable code of this kind of error.
+    #[must_use]
+    pub const fn code(&self) -> &'static str {
+        match self {
+            ErrorKind::ContractNameMismatch { .. } => "B301",
+            ErrorKind::ContractNameMissing(_) => "B302",
+            ErrorKind::SeparatorMissing(_) => "B303",
+            ErrorKind::DuplicateRoot(_, _) => "B304",
+        }
+    }
+}
+
+impl From<&Error> for Diagnostic {
+    fn from(error: &Error) -> Self {
+        let kind = error.kind();
+        Diagnostic::error(kind.code(), kind.to_string(), *error.span())
+    }
 }
 
 /// A high-level intermediate representation (HIR) combiner.
 ///
 /// It takes a vector of HIRs and combines them into a single HIR
 /// by appending the function nodes to the root contract node.
-pub struct Combiner;
+pub struct Combiner {
+    /// Whether to sort the modifier definitions alphabetically.
+    sort_modifiers: bool,
+    /// Whether two roots with the same identifier are an error.
+    deny_duplicate_roots: bool,
+}
 
 impl Default for Combiner {
     fn default() -> Self {
@@ -89,7 +124,32 @@ impl Combiner {
     /// Creates a new combiner.
     #[must_use]
     pub fn new() -> Self {
-        Combiner {}
+        Combiner { sort_modifiers: false, deny_duplicate_roots: false }
+    }
+
+    /// Sets whether the combined contract lists its modifier definitions
+    /// alphabetically, before any test.
+    ///
+    /// By default, each modifier is kept right before the first test that
+    /// uses it, which depends on the order of the roots.
+    #[must_use]
+    pub fn with_sorted_modifiers(mut self, sort_modifiers: bool) -> Self {
+        self.sort_modifiers = sort_modifiers;
+        self
+    }
+
+    /// Sets whether combining two roots with the same identifier, e.g. two
+    /// `Foo::bar` roots, is an error.
+    ///
+    /// By default, their branches are merged as if they came from different
+    /// roots.
+    #[must_use]
+    pub fn with_duplicate_roots_denied(
+        mut self,
+        deny_duplicate_roots: bool,
+    ) -> Self {
+        self.deny_duplicate_roots = deny_duplicate_roots;
+        self
     }
 
     /// Combines the translated HIRs into a single HIR. HIRs are merged by
@@ -101,19 +161,28 @@ impl Combiner {
         text: &str,
         hirs: impl Iterator<Item = Hir>,
     ) -> Result<Hir> {
-        CombinerI::new(text).combine(hirs)
+        CombinerI::new(text, self.sort_modifiers, self.deny_duplicate_roots)
+            .combine(hirs)
     }
 }
 
 struct CombinerI<'t> {
     /// The input text.
     text: &'t str,
+    /// Whether to sort the modifier definitions alphabetically.
+    sort_modifiers: bool,
+    /// Whether two roots with the same identifier are an error.
+    deny_duplicate_roots: bool,
 }
 
 impl<'t> CombinerI<'t> {
     /// Creates a new combiner.
-    fn new(text: &'t str) -> Self {
-        CombinerI { text }
+    fn new(
+        text: &'t str,
+        sort_modifiers: bool,
+        deny_duplicate_roots: bool,
+    ) -> Self {
+        CombinerI { text, sort_modifiers, deny_duplicate_roots }
     }
 
     /// Create a new error with the given span and error type.
@@ -126,6 +195,7 @@ impl<'t> CombinerI<'t> {
         // For `.tree` files with a single root, we don't need to do any work.
         let acc_contract = &mut ContractDefinition::default();
         let mut unique_modifiers = HashSet::new();
+        let mut unique_roots = HashSet::new();
 
         for (idx, hir) in hirs.into_iter().enumerate() {
             let Hir::Root(r) = hir else {
@@ -156,6 +226,18 @@ impl<'t> CombinerI<'t> {
                     ));
                 }
 
+                if !unique_roots.insert(contract.identifier.clone())
+                    && self.deny_duplicate_roots
+                {
+                    return Err(self.error(
+                        Span::default(),
+                        ErrorKind::DuplicateRoot(
+                            contract.identifier.clone(),
+                            idx + 1,
+                        ),
+                    ));
+                }
+
                 // If the accumulated identifier is empty, we're on the first
                 // contract.
                 if acc_contract.identifier.is_empty() {
@@ -171,6 +253,9 @@ impl<'t> CombinerI<'t> {
                         .collect();
                     let first_contract = ContractDefinition {
                         identifier: contract_name.to_owned(),
+                        natspec: contract.natspec,
+                        types: contract.types,
+                        bases: contract.bases,
                         children,
                     };
                     *acc_contract = first_contract;
@@ -193,10 +278,17 @@ impl<'t> CombinerI<'t> {
                     function_name,
                     &mut unique_modifiers,
                 );
+                acc_contract.natspec.extend(contract.natspec);
+                acc_contract.types.extend(contract.types);
+                acc_contract.bases.extend(contract.bases);
                 acc_contract.children.extend(children);
             }
         }
 
+        if self.sort_modifiers {
+            sort_modifiers(&mut acc_contract.children);
+        }
+
         let root = Root {
             children: vec![Hir::ContractDefinition(mem::take(acc_contract))],
         };
@@ -231,10 +323,33 @@ fn update_children(
 }
 
 /// Prefix the suffix of a test name.
+///
+/// The suffix is everything after the test prefix, i.e. after the first `_`,
+/// so that `testFuzz_` tests keep their prefix too.
 fn prefix_test_with(test_name: &str, prefix: &str) -> String {
     let capitalized_fn_name = upper_first_letter(prefix);
-    let test_suffix = test_name.trim_start_matches("test_");
-    format!("test_{capitalized_fn_name}{test_suffix}")
+    let (test_prefix, test_suffix) =
+        test_name.split_once('_').unwrap_or(("test", test_name));
+    format!("{test_prefix}_{capitalized_fn_name}{test_suffix}")
+}
+
+/// Moves the modifier definitions in `children` to the front, sorted by name.
+///
+/// The relative order of every other child is preserved.
+fn sort_modifiers(children: &mut Vec<Hir>) {
+    let (mut modifiers, rest): (Vec<_>, Vec<_>) =
+        mem::take(children).into_iter().partition(|child| {
+            matches!(child, Hir::FunctionDefinition(f) if f.is_modifier())
+        });
+    modifiers.sort_by(|a, b| match (a, b) {
+        (Hir::FunctionDefinition(a), Hir::FunctionDefinition(b)) => {
+            a.identifier.cmp(&b.identifier)
+        }
+        _ => unreachable!(),
+    });
+
+    children.extend(modifiers);
+    children.extend(rest);
 }
 
 fn collect_modifier(
@@ -292,6 +407,9 @@ mod tests {
     fn contract(identifier: String, children: Vec<Hir>) -> Hir {
         Hir::ContractDefinition(hir::ContractDefinition {
             identifier,
+            natspec: vec![],
+            types: vec![],
+            bases: vec![],
             children,
         })
     }
@@ -303,21 +421,49 @@ mod tests {
         modifiers: Option<Vec<String>>,
         children: Option<Vec<Hir>>,
     ) -> Hir {
+        // Combined test names hold the keyword of their condition, if any,
+        // after the function name.
+        let keyword = if identifier.contains("When") {
+            Some(hir::ConditionKeyword::When)
+        } else if identifier.contains("Given") {
+            Some(hir::ConditionKeyword::Given)
+        } else {
+            None
+        };
+
         Hir::FunctionDefinition(hir::FunctionDefinition {
             identifier,
             ty,
             span,
             modifiers,
+            params: vec![],
+            condition_title: None,
+            payable: false,
+            keyword,
             children,
         })
     }
 
+    fn modifier(identifier: &str, title: &str, span: Span) -> Hir {
+        Hir::FunctionDefinition(hir::FunctionDefinition {
+            identifier: identifier.to_owned(),
+            ty: hir::FunctionTy::Modifier,
+            span,
+            modifiers: None,
+            params: vec![],
+            condition_title: Some(title.to_owned()),
+            payable: false,
+            keyword: None,
+            children: None,
+        })
+    }
+
     fn statement(ty: hir::StatementType) -> Hir {
         Hir::Statement(hir::Statement { ty })
     }
 
     fn comment(lexeme: String) -> Hir {
-        Hir::Comment(hir::Comment { lexeme })
+        Hir::Comment(hir::Comment { lexeme, kind: hir::CommentKind::Action })
     }
 
     #[test]
@@ -351,6 +497,27 @@ bulloak error: contract name missing at tree root #2";
         }
     }
 
+    #[test]
+    fn errors_on_duplicate_roots_when_denied() {
+        let trees = [
+            "Contract::function\n└── when something bad happens\n   └── it should revert",
+            "Contract::function\n└── when something else happens\n   └── it should revert",
+        ];
+        let text = trees.join("\n\n");
+
+        let hirs = trees.iter().map(|tree| translate(tree).unwrap());
+        assert!(combine(&text, hirs).is_ok());
+
+        let hirs = trees.iter().map(|tree| translate(tree).unwrap());
+        let err = crate::hir::combiner::Combiner::new()
+            .with_duplicate_roots_denied(true)
+            .combine(&text, hirs)
+            .unwrap_err();
+        assert!(err.to_string().ends_with(
+            "tree root #2 duplicates the root 'Contract::function'"
+        ));
+    }
+
     #[test]
     fn skips_non_function_children() {
         let trees = vec![
@@ -430,15 +597,13 @@ bulloak error: contract name missing at tree root #2";
             vec![contract(
                 "Contract".to_owned(),
                 vec![
-                    function(
-                        "whenSomethingBadHappens".to_owned(),
-                        hir::FunctionTy::Modifier,
+                    modifier(
+                        "whenSomethingBadHappens",
+                        "when something bad happens",
                         Span::new(
                             Position::new(20, 2, 1),
                             Position::new(133, 4, 28)
-                        ),
-                        None,
-                        None
+                        )
                     ),
                     function(
                         "test_Function1RevertGiven_SomethingElseHappens"
@@ -472,4 +637,73 @@ bulloak error: contract name missing at tree root #2";
             )]
         );
     }
+
+    #[test]
+    fn sorts_modifiers() {
+        let trees = [
+            "Contract::function1\n└── when zeta\n    └── when alpha\n        └── it should revert",
+            "Contract::function2\n└── when beta\n    └── when gamma\n        └── it should revert",
+        ];
+        let hirs = trees.iter().map(|tree| translate(tree).unwrap());
+
+        let text = trees.join("\n\n");
+        let hir = crate::hir::combiner::Combiner::new()
+            .with_sorted_modifiers(true)
+            .combine(&text, hirs)
+            .unwrap();
+        let Hir::Root(root) = hir else { unreachable!() };
+        let Hir::ContractDefinition(contract) = &root.children[0] else {
+            unreachable!()
+        };
+
+        let identifiers: Vec<_> = contract
+            .children
+            .iter()
+            .filter_map(|child| match child {
+                Hir::FunctionDefinition(f) => Some(f.identifier.as_str()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            identifiers,
+            vec![
+                "whenBeta",
+                "whenZeta",
+                "test_Function1RevertWhen_Alpha",
+                "test_Function2RevertWhen_Gamma",
+            ]
+        );
+    }
+
+    #[test]
+    fn prefixes_fuzz_tests() {
+        let trees = [
+            "Contract::function1\n└── when alpha [fuzz]\n    └── it should revert",
+            "Contract::function2\n└── when beta\n    └── it should work",
+        ];
+        let hirs = trees.iter().map(|tree| translate(tree).unwrap());
+
+        let text = trees.join("\n\n");
+        let hir = combine(&text, hirs).unwrap();
+        let Hir::Root(root) = hir else { unreachable!() };
+        let Hir::ContractDefinition(contract) = &root.children[0] else {
+            unreachable!()
+        };
+
+        let identifiers: Vec<_> = contract
+            .children
+            .iter()
+            .filter_map(|child| match child {
+                Hir::FunctionDefinition(f) => Some(f.identifier.as_str()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            identifiers,
+            vec![
+                "testFuzz_Function1RevertWhen_Alpha",
+                "test_Function2WhenBeta",
+            ]
+        );
+    }
 }
@@ -1,5 +1,7 @@
 //! Defines a high-level intermediate representation (HIR).
 
+use std::{borrow::Cow, fmt, path::Path};
+
 use bulloak_syntax::Span;
 
 /// A high-level intermediate representation (HIR) that describes
@@ -33,6 +35,81 @@ impl Default for Hir {
 }
 
 impl Hir {
+    /// Whether any statement in this HIR calls a Foundry cheatcode.
+    #[must_use]
+    pub fn uses_cheatcodes(&self) -> bool {
+        self.any_statement(&StatementType::uses_cheatcodes)
+    }
+
+    /// Whether any statement in this HIR logs with forge-std's `console`.
+    #[must_use]
+    pub fn uses_console(&self) -> bool {
+        self.any_statement(&|ty| matches!(ty, StatementType::ConsoleLog(_)))
+    }
+
+    /// Whether any statement in this HIR satisfies `predicate`.
+    fn any_statement(
+        &self,
+        predicate: &impl Fn(&StatementType) -> bool,
+    ) -> bool {
+        match self {
+            Hir::Root(root) => {
+                root.children.iter().any(|child| child.any_statement(predicate))
+            }
+            Hir::ContractDefinition(contract) => contract
+                .children
+                .iter()
+                .any(|child| child.any_statement(predicate)),
+            Hir::FunctionDefinition(function) => function
+                .children
+                .iter()
+                .flatten()
+                .any(|child| child.any_statement(predicate)),
+            Hir::Statement(statement) => predicate(&statement.ty),
+            Hir::Comment(_) => false,
+        }
+    }
+
+    /// Whether any text that ends up in the emitted Solidity, such as a
+    /// comment or a raw statement, contains `needle`.
+    #[must_use]
+    pub fn contains_text(&self, needle: &str) -> bool {
+        match self {
+            Hir::Root(root) => {
+                root.children.iter().any(|child| child.contains_text(needle))
+            }
+            Hir::ContractDefinition(contract) => {
+                contract.natspec.iter().any(|tag| tag.contains(needle))
+                    || contract
+                        .children
+                        .iter()
+                        .any(|child| child.contains_text(needle))
+            }
+            Hir::FunctionDefinition(function) => {
+                function
+                    .condition_title
+                    .as_ref()
+                    .is_some_and(|title| title.contains(needle))
+                    || function
+                        .children
+                        .iter()
+                        .flatten()
+                        .any(|child| child.contains_text(needle))
+            }
+            Hir::Comment(comment) => comment.lexeme.contains(needle),
+            Hir::Statement(statement) => match &statement.ty {
+                StatementType::VmLabel(text)
+                | StatementType::VmWarp(text)
+                | StatementType::VmRoll(text)
+                | StatementType::StartSnapshotGas(text)
+                | StatementType::Raw(text)
+                | StatementType::ConsoleLog(text)
+                | StatementType::AssertEq(text) => text.contains(needle),
+                _ => false,
+            },
+        }
+    }
+
     /// Returns the first contract object found starting from a root or a
     /// contract definition if it exists.
     #[must_use]
@@ -70,10 +147,44 @@ impl Root {
 pub struct ContractDefinition {
     /// The contract name.
     pub identifier: Identifier,
+    /// The natspec tags to emit above the contract, e.g.
+    /// `@title My Test Suite`.
+    pub natspec: Vec<String>,
+    /// The types to declare at the top of the contract, as declared by the
+    /// `@enum` and `@struct` directives of the tree, e.g.
+    /// `enum Status { Active, Paused }`.
+    pub types: Vec<String>,
+    /// The contracts this contract inherits from, as declared by the
+    /// `@extends` directives of the tree.
+    pub bases: Vec<BaseContract>,
     /// The children HIR nodes of this node.
     pub children: Vec<Hir>,
 }
 
+/// A contract generated from another tree that a contract inherits from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BaseContract {
+    /// The name of the base contract.
+    pub identifier: Identifier,
+    /// The path of the base tree, as written in the `@extends` directive.
+    pub path: String,
+}
+
+impl BaseContract {
+    /// Returns the path to import the base contract from, i.e. the path of
+    /// the Solidity file generated from the base tree.
+    #[must_use]
+    pub fn import_path(&self) -> String {
+        let path = Path::new(&self.path).with_extension("t.sol");
+        let path = path.to_string_lossy();
+        if path.starts_with('.') || path.starts_with('/') {
+            path.into_owned()
+        } else {
+            format!("./{path}")
+        }
+    }
+}
+
 /// A function's type.
 ///
 /// Currently, we only care about regular functions (tests)
@@ -92,6 +203,39 @@ impl Default for FunctionTy {
     }
 }
 
+/// The keyword a condition starts with, which tells state preconditions
+/// apart from the conditions of a call.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConditionKeyword {
+    /// A `given` condition, i.e. a state precondition.
+    Given,
+    /// A `when` condition, i.e. a condition of the call.
+    When,
+}
+
+impl ConditionKeyword {
+    /// Returns the keyword the condition titled `title` starts with.
+    #[must_use]
+    pub fn from_title(title: &str) -> Option<Self> {
+        let keyword = title.split_whitespace().next()?.to_lowercase();
+        match keyword.as_str() {
+            "given" => Some(Self::Given),
+            "when" => Some(Self::When),
+            _ => None,
+        }
+    }
+
+    /// Returns the banner that opens the section of the tests under
+    /// conditions with this keyword.
+    #[must_use]
+    pub const fn banner(self) -> &'static str {
+        match self {
+            Self::Given => "==================== GIVEN ====================",
+            Self::When => "==================== WHEN ====================",
+        }
+    }
+}
+
 /// A function definition HIR node.
 #[derive(Debug, Clone, PartialEq, Eq, Default)]
 pub struct FunctionDefinition {
@@ -107,6 +251,19 @@ pub struct FunctionDefinition {
     /// `None` if the function's type is
     /// `FunctionTy::Modifier`.
     pub modifiers: Option<Vec<Identifier>>,
+    /// The parameters of this function, e.g. the inputs of a fuzz test.
+    pub params: Vec<Parameter>,
+    /// The title of the condition this modifier was generated from.
+    ///
+    /// `None` if the function's type is `FunctionTy::Function`.
+    pub condition_title: Option<String>,
+    /// Whether this function can receive ETH, i.e. its branch was marked
+    /// with `[payable]`.
+    pub payable: bool,
+    /// The keyword of the condition this test was generated from.
+    ///
+    /// `None` for modifiers and for tests of actions at the root.
+    pub keyword: Option<ConditionKeyword>,
     /// The children HIR nodes of this node.
     pub children: Option<Vec<Hir>>,
 }
@@ -123,6 +280,62 @@ impl FunctionDefinition {
     pub fn is_function(&self) -> bool {
         matches!(self.ty, FunctionTy::Function)
     }
+
+    /// Whether this function is an invariant test, i.e. its name starts
+    /// with `invariant_`.
+    #[must_use]
+    pub fn is_invariant(&self) -> bool {
+        self.is_function() && self.identifier.starts_with("invariant_")
+    }
+
+    /// Whether this function is a test that expects a revert, i.e. its name
+    /// reads `test_Revert...` once the prefix is stripped.
+    ///
+    /// Gas benchmarks like `test_Gas_RevertWhen_...` are revert tests too.
+    #[must_use]
+    pub fn is_revert_test(&self) -> bool {
+        self.is_function()
+            && self.identifier.split_once('_').is_some_and(|(_, name)| {
+                name.strip_prefix("Gas_").unwrap_or(name).starts_with("Revert")
+            })
+    }
+
+    /// Returns the natspec tag that links this function to the line of the
+    /// tree at `spec_path` it was generated from.
+    #[must_use]
+    pub fn spec_ref(&self, spec_path: &Path) -> String {
+        format!("@dev spec: {}:L{}", spec_path.display(), self.span.start.line)
+    }
+}
+
+/// A parameter of a function.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Parameter {
+    /// The type of this parameter, e.g. `uint256`.
+    pub ty: String,
+    /// The name of this parameter.
+    pub name: Identifier,
+}
+
+impl Parameter {
+    /// Returns the data location of this parameter, i.e. `memory` for the
+    /// reference types that can be told apart by their name: `bytes`,
+    /// `string` and arrays.
+    #[must_use]
+    pub fn location(&self) -> Option<&'static str> {
+        let is_reference = matches!(self.ty.as_str(), "bytes" | "string")
+            || self.ty.ends_with(']');
+        is_reference.then_some("memory")
+    }
+}
+
+impl fmt::Display for Parameter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.location() {
+            Some(location) => write!(f, "{} {location} {}", self.ty, self.name),
+            None => write!(f, "{} {}", self.ty, self.name),
+        }
+    }
 }
 
 /// A comment node.
@@ -130,6 +343,42 @@ impl FunctionDefinition {
 pub struct Comment {
     /// The contract name.
     pub lexeme: String,
+    /// The tree element this comment was generated from.
+    pub kind: CommentKind,
+}
+
+impl Comment {
+    /// Returns the text to emit for this comment.
+    ///
+    /// When `descriptions_as_todos` is set, comments generated from action
+    /// descriptions are prefixed with `TODO:`.
+    #[must_use]
+    pub fn text(&self, descriptions_as_todos: bool) -> Cow<'_, str> {
+        match self.kind {
+            CommentKind::Description if descriptions_as_todos => {
+                format!("TODO: {}", self.lexeme.trim_start()).into()
+            }
+            _ => self.lexeme.as_str().into(),
+        }
+    }
+}
+
+/// The tree element a comment was generated from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CommentKind {
+    /// The title of an action, e.g. `// It should revert.`.
+    #[default]
+    Action,
+    /// The description of an action, i.e. the text nested under it.
+    Description,
+    /// The banner that opens a section of the contract, e.g.
+    /// `// ===== GIVEN =====`.
+    Banner,
+    /// The title of a condition a test is under, e.g.
+    /// `// GIVEN: the vault is paused`.
+    Condition,
+    /// A comment written in the tree itself, e.g. `// See issue #42.`.
+    Note,
 }
 
 /// The statements which are currently supported.
@@ -137,6 +386,78 @@ pub struct Comment {
 pub enum StatementType {
     /// The `vm.skip(true);` statement.
     VmSkip,
+    /// The `vm.label(<name>, "<name>");` statement.
+    VmLabel(String),
+    /// The `vm.warp(<timestamp>);` statement of a test marked with
+    /// `[warp:<timestamp>]`.
+    VmWarp(String),
+    /// The `vm.roll(<block>);` statement of a test marked with
+    /// `[roll:<block>]`.
+    VmRoll(String),
+    /// An empty `assembly {}` block.
+    Assembly,
+    /// The `vm.startSnapshotGas("<name>");` statement.
+    StartSnapshotGas(String),
+    /// The `vm.stopSnapshotGas();` statement.
+    StopSnapshotGas,
+    /// A statement expanded from a user-provided body template, emitted
+    /// verbatim.
+    Raw(String),
+    /// The `targetContract(address(0));` placeholder of an invariant suite.
+    TargetContract,
+    /// The `console.log("<text>");` statement of an action marked with
+    /// `[trace]`.
+    ConsoleLog(String),
+    /// The `assertEq(actual, <expected>);` placeholder of an action that
+    /// states the value it expects.
+    AssertEq(String),
+    /// The `vm.expectRevert` statement of an action that reverts with a
+    /// custom error.
+    ///
+    /// (error name, arguments)
+    ExpectRevert(String, Vec<String>),
+    /// The `(bool success,) = address(target).call(data);` placeholder of an
+    /// action that never reverts.
+    LowLevelCall,
+    /// The `assertTrue(success, ...);` check that follows a
+    /// [`StatementType::LowLevelCall`].
+    AssertSuccess,
+    /// The `vm.expectCall(address(<target>), data);` placeholder of a
+    /// description that calls a contract.
+    ExpectCall(String),
+    /// The `vm.expectEmit();` statement of a description that emits an
+    /// event, followed by a [`StatementType::Emit`].
+    ExpectEmit,
+    /// The `emit <event>();` placeholder of the event a description emits.
+    Emit(String),
+}
+
+impl StatementType {
+    /// Whether this statement calls a Foundry cheatcode or a forge-std
+    /// helper, which means the test contract needs to inherit from
+    /// forge-std's `Test`.
+    ///
+    /// Raw statements are assumed to use cheatcodes if they access `vm`.
+    #[must_use]
+    pub fn uses_cheatcodes(&self) -> bool {
+        match self {
+            Self::VmSkip
+            | Self::VmLabel(_)
+            | Self::VmWarp(_)
+            | Self::VmRoll(_)
+            | Self::StartSnapshotGas(_)
+            | Self::StopSnapshotGas
+            | Self::TargetContract
+            | Self::ConsoleLog(_)
+            | Self::AssertEq(_)
+            | Self::ExpectRevert(_, _)
+            | Self::AssertSuccess
+            | Self::ExpectCall(_)
+            | Self::ExpectEmit => true,
+            Self::Assembly | Self::LowLevelCall | Self::Emit(_) => false,
+            Self::Raw(text) => text.contains("vm."),
+        }
+    }
 }
 
 /// A statement node.
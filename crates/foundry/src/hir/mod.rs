@@ -9,9 +9,22 @@ pub use hir::*;
 pub mod translator;
 pub mod visitor;
 
-use bulloak_syntax::Ast;
+use std::{
+    collections::HashSet,
+    fs, mem,
+    path::{Path, PathBuf},
+};
 
-use crate::{config::Config, scaffold::modifiers::ModifierDiscoverer};
+use anyhow::{anyhow, bail};
+use bulloak_syntax::{Ast, Position, Span};
+
+use crate::{
+    config::Config, constants::CONTRACT_IDENTIFIER_SEPARATOR,
+    scaffold::modifiers::ModifierDiscoverer, sol,
+};
+
+/// The prefix of the natspec tag of an `@extends` directive.
+const EXTENDS_TAG: &str = "@extends ";
 
 /// Translates the contents of a `.tree` file into a HIR.
 ///
@@ -24,14 +37,142 @@ use crate::{config::Config, scaffold::modifiers::ModifierDiscoverer};
 ///
 /// Returns a `Result` containing the translated `Hir` or a `TranslationError`.
 pub fn translate(text: &str, cfg: &Config) -> anyhow::Result<Hir> {
-    let asts = bulloak_syntax::parse(text)?;
+    let mut stack: Vec<PathBuf> =
+        cfg.tree_path.iter().map(|path| canonical(path)).collect();
+    translate_extending(text, cfg, &mut stack).map(|(hir, _)| hir)
+}
+
+/// Translates the contents of a `.tree` file into a HIR, resolving its
+/// `@extends` directives.
+///
+/// Returns the HIR along with the names of the functions it inherits, which
+/// are left out of it. `stack` holds the trees being translated, to catch
+/// trees that extend themselves.
+fn translate_extending(
+    text: &str,
+    cfg: &Config,
+    stack: &mut Vec<PathBuf>,
+) -> anyhow::Result<(Hir, HashSet<String>)> {
+    let asts = bulloak_syntax::parse_with_aliases(text, &cfg.keyword_aliases)?;
+    let mut hirs: Vec<_> = asts
+        .into_iter()
+        .map(|(line, ast)| {
+            let mut hir = translate_one(&ast, cfg);
+            shift_lines(&mut hir, line - 1);
+            hir
+        })
+        .collect();
+
+    let mut hir = if hirs.len() == 1 {
+        hirs.remove(0)
+    } else {
+        combiner::Combiner::new()
+            .with_sorted_modifiers(cfg.sort_modifiers)
+            .with_duplicate_roots_denied(cfg.deny_duplicate_roots)
+            .combine(text, hirs.into_iter())?
+    };
+
+    if cfg.reverts_last {
+        sort_reverts_last(&mut hir);
+    }
+    if cfg.group_by_keyword {
+        group_by_keyword(&mut hir);
+    }
+    if cfg.emit_setup {
+        add_setup(&mut hir);
+    }
+    check_types(&hir)?;
+    let inherited = resolve_bases(&mut hir, cfg, stack)?;
+
+    Ok((hir, inherited))
+}
+
+/// Checks that the `@enum` and `@struct` directives of the contract in `hir`
+/// declare valid Solidity types.
+fn check_types(hir: &Hir) -> anyhow::Result<()> {
+    let Hir::Root(root) = hir else { return Ok(()) };
+    for child in &root.children {
+        let Hir::ContractDefinition(contract) = child else { continue };
+        if let Some(declaration) = contract.types.iter().find(|declaration| {
+            sol::parse_type_declaration(declaration).is_none()
+        }) {
+            bail!("`{declaration}` is not a valid Solidity enum or struct");
+        }
+    }
+
+    Ok(())
+}
+
+/// Turns the `@extends` directives of the contract in `hir` into base
+/// contracts, and removes the functions the contract inherits from them.
+///
+/// Each base tree is translated with the same configuration, so its
+/// functions are named as they would be in its own Solidity file. Returns
+/// the names of every inherited function.
+fn resolve_bases(
+    hir: &mut Hir,
+    cfg: &Config,
+    stack: &mut Vec<PathBuf>,
+) -> anyhow::Result<HashSet<String>> {
+    let mut inherited = HashSet::new();
+    let Hir::Root(root) = hir else { return Ok(inherited) };
+    for child in &mut root.children {
+        let Hir::ContractDefinition(contract) = child else { continue };
+        let (extends, natspec): (Vec<_>, Vec<_>) =
+            mem::take(&mut contract.natspec)
+                .into_iter()
+                .partition(|tag| tag.starts_with(EXTENDS_TAG));
+        contract.natspec = natspec;
+
+        let dir = cfg.tree_path.as_deref().and_then(Path::parent);
+        for tag in extends {
+            let path = tag[EXTENDS_TAG.len()..].trim().to_owned();
+            let file =
+                dir.map_or_else(|| PathBuf::from(&path), |d| d.join(&path));
+            let key = canonical(&file);
+            if stack.contains(&key) {
+                bail!("`{}` extends itself through `@extends`", file.display());
+            }
 
-    if asts.len() == 1 {
-        return Ok(translate_one(&asts[0], cfg));
+            let text = fs::read_to_string(&file).map_err(|err| {
+                anyhow!(
+                    "failed to read the base tree `{}`: {err}",
+                    file.display()
+                )
+            })?;
+            let base_cfg =
+                Config { tree_path: Some(file.clone()), ..cfg.clone() };
+            stack.push(key);
+            let (base, base_inherited) =
+                translate_extending(&text, &base_cfg, stack)?;
+            stack.pop();
+
+            let Some(base) = base.find_contract() else { continue };
+            inherited.extend(base_inherited);
+            inherited.extend(base.children.iter().filter_map(
+                |child| match child {
+                    Hir::FunctionDefinition(f) => Some(f.identifier.clone()),
+                    _ => None,
+                },
+            ));
+            contract.bases.push(BaseContract {
+                identifier: base.identifier.clone(),
+                path,
+            });
+        }
+
+        contract.children.retain(|child| {
+            !matches!(child, Hir::FunctionDefinition(f) if inherited.contains(&f.identifier))
+        });
     }
 
-    let hirs = asts.into_iter().map(|ast| translate_one(&ast, cfg));
-    Ok(combiner::Combiner::new().combine(text, hirs)?)
+    Ok(inherited)
+}
+
+/// Returns the canonical form of `path`, or `path` itself if it doesn't
+/// exist.
+fn canonical(path: &Path) -> PathBuf {
+    fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
 }
 
 /// Generates the HIR for a single AST.
@@ -50,3 +191,181 @@ pub fn translate_one(ast: &Ast, cfg: &Config) -> Hir {
     let modifiers = discoverer.discover(ast);
     translator::Translator::new().translate(ast, modifiers, cfg)
 }
+
+/// Splits a root name like `token/Foo_Test::bar` into the directory of its
+/// contract, `token`, and the rest of it, `Foo_Test::bar`.
+///
+/// Returns no directory if the contract name has no `/`.
+#[must_use]
+pub fn split_root_path(name: &str) -> (Option<&str>, &str) {
+    let contract = name
+        .split_once(CONTRACT_IDENTIFIER_SEPARATOR)
+        .map_or(name, |(contract, _)| contract);
+    match contract.rfind('/') {
+        Some(idx) => (Some(&name[..idx]), &name[idx + 1..]),
+        None => (None, name),
+    }
+}
+
+/// Returns the path of the Solidity file of a root name like
+/// `token/Foo_Test::bar`, `token/Foo_Test.t.sol`, relative to the output
+/// directory.
+///
+/// Returns `None` if the contract name has no `/`.
+#[must_use]
+pub fn root_file(name: &str) -> Option<PathBuf> {
+    let (Some(dir), rest) = split_root_path(name) else {
+        return None;
+    };
+    let contract = rest
+        .split_once(CONTRACT_IDENTIFIER_SEPARATOR)
+        .map_or(rest, |(contract, _)| contract);
+
+    Some(Path::new(dir).join(contract).with_extension("t.sol"))
+}
+
+/// Expands a condition title with a value set, like `given status in
+/// {Active, Paused}`, into one title per value, like `given status is
+/// Active` and `given status is Paused`.
+///
+/// Returns `None` if the title has no value set.
+#[must_use]
+pub fn expand_value_set(title: &str) -> Option<Vec<String>> {
+    value_set(title)
+        .map(|values| values.into_iter().map(|(_, title)| title).collect())
+}
+
+/// Returns the values of the value set in a condition title, like `Active`
+/// and `Paused` in `given status in {Active, Paused}`, along with the title
+/// each one expands into. See [`expand_value_set`].
+pub(crate) fn value_set(title: &str) -> Option<Vec<(&str, String)>> {
+    let (subject, rest) = title.split_once(" in {")?;
+    let (values, suffix) = rest.split_once('}')?;
+    let values: Vec<_> = values
+        .split(',')
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(|value| (value, format!("{subject} is {value}{suffix}")))
+        .collect();
+
+    (!values.is_empty()).then_some(values)
+}
+
+/// Moves the revert tests of the contract in `hir` after every other test.
+///
+/// The sort is stable, so tests keep their relative order otherwise.
+/// Modifiers never move, since they aren't revert tests.
+fn sort_reverts_last(hir: &mut Hir) {
+    let Hir::Root(root) = hir else { return };
+    for child in &mut root.children {
+        if let Hir::ContractDefinition(contract) = child {
+            contract.children.sort_by_key(|child| {
+                matches!(child, Hir::FunctionDefinition(f) if f.is_revert_test())
+            });
+        }
+    }
+}
+
+/// Moves the tests of the contract in `hir` into a section for the tests
+/// under `given` conditions followed by one for the tests under `when`
+/// conditions.
+///
+/// Each section opens with a banner comment, and is omitted if it would be
+/// empty. The modifiers and the tests of actions at the root stay above the
+/// sections, and the tests keep their relative order within each section.
+fn group_by_keyword(hir: &mut Hir) {
+    let Hir::Root(root) = hir else { return };
+    for child in &mut root.children {
+        let Hir::ContractDefinition(contract) = child else { continue };
+        let keyword = |child: &Hir| match child {
+            Hir::FunctionDefinition(f) if f.is_function() => f.keyword,
+            _ => None,
+        };
+
+        let mut children = vec![];
+        let mut sections = [
+            (ConditionKeyword::Given, vec![]),
+            (ConditionKeyword::When, vec![]),
+        ];
+        for child in mem::take(&mut contract.children) {
+            match sections.iter_mut().find(|(k, _)| Some(*k) == keyword(&child))
+            {
+                Some((_, section)) => section.push(child),
+                None => children.push(child),
+            }
+        }
+        for (keyword, section) in sections {
+            if section.is_empty() {
+                continue;
+            }
+            children.push(Hir::Comment(Comment {
+                lexeme: keyword.banner().to_owned(),
+                kind: CommentKind::Banner,
+            }));
+            children.extend(section);
+        }
+
+        contract.children = children;
+    }
+}
+
+/// Adds a `setUp` function at the start of the contract in `hir`.
+///
+/// If the contract has invariant tests, the function registers a placeholder
+/// target contract for the fuzzer to call. The function points to the first
+/// line of the tree, since it doesn't come from any branch.
+fn add_setup(hir: &mut Hir) {
+    let Hir::Root(root) = hir else { return };
+    let Some(Hir::ContractDefinition(contract)) = root
+        .children
+        .iter_mut()
+        .find(|child| matches!(child, Hir::ContractDefinition(_)))
+    else {
+        return;
+    };
+
+    let has_invariants = contract.children.iter().any(
+        |child| matches!(child, Hir::FunctionDefinition(f) if f.is_invariant()),
+    );
+    let mut children = vec![];
+    if has_invariants {
+        children.push(Hir::Statement(Statement {
+            ty: StatementType::TargetContract,
+        }));
+    }
+
+    let position = Position::new(0, 1, 1);
+    let setup = Hir::FunctionDefinition(FunctionDefinition {
+        identifier: "setUp".to_owned(),
+        ty: FunctionTy::Function,
+        span: Span::new(position, position),
+        modifiers: None,
+        params: vec![],
+        condition_title: None,
+        payable: false,
+        keyword: None,
+        children: Some(children),
+    });
+    contract.children.insert(0, setup);
+}
+
+/// Shifts the lines of the function spans in `hir` by `lines`.
+///
+/// Each tree in a file is parsed on its own, so its spans are relative to the
+/// line the tree starts at. Spans in the HIR are only used to point at lines
+/// of the tree file, which is why offsets are left as is.
+fn shift_lines(hir: &mut Hir, lines: usize) {
+    match hir {
+        Hir::Root(root) => {
+            root.children.iter_mut().for_each(|c| shift_lines(c, lines));
+        }
+        Hir::ContractDefinition(contract) => {
+            contract.children.iter_mut().for_each(|c| shift_lines(c, lines));
+        }
+        Hir::FunctionDefinition(function) => {
+            function.span.start.line += lines;
+            function.span.end.line += lines;
+        }
+        Hir::Comment(_) | Hir::Statement(_) => {}
+    }
+}
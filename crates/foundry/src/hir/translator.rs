@@ -1,13 +1,16 @@
 //! The implementation of a translator between a bulloak tree AST and a
 //! high-level intermediate representation (HIR) -- AST -> HIR.
+use std::collections::HashSet;
+
 use bulloak_syntax::{
     utils::{sanitize, upper_first_letter},
-    Action, Ast, Condition, Description, Visitor,
+    Action, Ast, Condition, Description, Directive, Marker, Span, Visitor,
 };
 use indexmap::IndexMap;
 
 use crate::{
-    config::Config,
+    config::{BodyTemplate, Config},
+    constants::AAA_STEPS,
     hir::{self, Hir},
 };
 
@@ -41,6 +44,7 @@ impl Translator {
 }
 
 /// The internal implementation of the Translator.
+#[allow(clippy::struct_excessive_bools)]
 struct TranslatorI<'a> {
     /// A stack of modifiers that will be applied to the
     /// currently visited function.
@@ -58,15 +62,88 @@ struct TranslatorI<'a> {
     /// to improve performance. Otherwise each title would be converted
     /// to a modifier every time it is used.
     modifiers: &'a IndexMap<String, String>,
+    /// A stack of the markers attached to the conditions that are currently
+    /// being visited.
+    ///
+    /// Markers on a condition apply to every test generated under it, so
+    /// this stack is updated in the same way as `modifier_stack`.
+    marker_stack: Vec<Vec<Marker>>,
+    /// A stack of the titles of the conditions that are currently being
+    /// visited.
+    title_stack: Vec<String>,
+    /// A stack of the values of the value sets that the conditions currently
+    /// being visited were expanded with.
+    ///
+    /// The tests nested under a value set are suffixed with its value, e.g.
+    /// `test_WhenTheCallerIsTheOwner_Active`, so that the tests under each
+    /// value have different names.
+    value_stack: Vec<String>,
+    /// The modifiers whose definition was already emitted.
+    ///
+    /// The modifiers nested under a value set are applied under each value,
+    /// but they must only be defined once.
+    emitted_modifiers: HashSet<String>,
     /// Whether to add `vm.skip(true)` at the beginning of each test.
     with_vm_skip: bool,
+    /// The named addresses to label with `vm.label` in the tests that
+    /// mention them.
+    labels: Vec<String>,
+    /// The templates to expand into the body of the generated tests.
+    body_templates: Vec<BodyTemplate>,
+    /// The statement to emit at the top of every test, if any.
+    default_body_statement: Option<String>,
+    /// The prefix of the tests marked as fuzz tests.
+    fuzz_prefix: String,
+    /// Whether the directories in the root name are left out of the
+    /// contract name.
+    root_as_path: bool,
+    /// Whether to emit an `assertEq` for actions that state the value they
+    /// expect.
+    emit_assertions: bool,
+    /// Whether to emit a `vm.expectRevert` for actions that revert with a
+    /// custom error.
+    emit_expect_revert: bool,
+    /// Whether to emit a call that must succeed for actions that never
+    /// revert.
+    emit_no_revert: bool,
+    /// Whether to label the descriptions of actions that have exactly
+    /// three of them as the Arrange / Act / Assert steps of the test.
+    aaa_from_descriptions: bool,
+    /// Whether to turn descriptions that call a contract or emit an event
+    /// into cheatcode stubs.
+    smart_descriptions: bool,
+    /// Whether to leave out the actions that repeat the title of an earlier
+    /// action of the same test.
+    dedup_actions: bool,
+    /// Whether to open every test with a comment per condition it's under.
+    condition_comments: bool,
 }
 
 impl<'a> TranslatorI<'a> {
     /// Creates a new internal translator.
     fn new(modifiers: &'a IndexMap<String, String>, cfg: &Config) -> Self {
         let with_vm_skip = cfg.emit_vm_skip;
-        Self { modifier_stack: Vec::new(), modifiers, with_vm_skip }
+        Self {
+            modifier_stack: Vec::new(),
+            modifiers,
+            marker_stack: Vec::new(),
+            title_stack: Vec::new(),
+            value_stack: Vec::new(),
+            emitted_modifiers: HashSet::new(),
+            with_vm_skip,
+            labels: cfg.labels.clone(),
+            body_templates: cfg.body_templates.clone(),
+            default_body_statement: cfg.default_body_statement.clone(),
+            fuzz_prefix: cfg.fuzz_prefix.clone(),
+            root_as_path: cfg.root_as_path,
+            emit_assertions: cfg.emit_assertions,
+            emit_expect_revert: cfg.emit_expect_revert,
+            emit_no_revert: cfg.emit_no_revert,
+            aaa_from_descriptions: cfg.aaa_from_descriptions,
+            smart_descriptions: cfg.smart_descriptions,
+            dedup_actions: cfg.dedup_actions,
+            condition_comments: cfg.condition_comments,
+        }
     }
 
     /// Concrete implementation of the translation from AST to HIR.
@@ -80,92 +157,216 @@ impl<'a> TranslatorI<'a> {
         // is a Root HIR node.
         std::mem::take(&mut hirs[0])
     }
-}
 
-impl<'a> Visitor for TranslatorI<'a> {
-    type Error = ();
-    type Output = Vec<Hir>;
+    /// Returns the markers that apply to a test containing `actions`.
+    ///
+    /// These are the markers of every condition in the current path plus the
+    /// markers of the actions themselves.
+    fn active_markers<'b>(&'b self, actions: &[&'b Action]) -> Vec<&'b Marker> {
+        self.marker_stack
+            .iter()
+            .flatten()
+            .chain(actions.iter().flat_map(|action| &action.markers))
+            .collect()
+    }
 
-    fn visit_root(
-        &mut self,
-        root: &bulloak_syntax::Root,
-    ) -> Result<Self::Output, Self::Error> {
-        let mut root_children = Vec::new();
+    /// Returns the named addresses mentioned by a test containing `actions`.
+    ///
+    /// A name is mentioned if it appears as a word, ignoring case, in the title
+    /// of any condition in the current path or of any of the actions.
+    fn mentioned_labels(&self, actions: &[&Action]) -> Vec<&String> {
+        let titles = self
+            .title_stack
+            .iter()
+            .chain(actions.iter().map(|action| &action.title));
+        let words: Vec<&str> = titles
+            .flat_map(|title| {
+                title.split(|c: char| !(c.is_alphanumeric() || c == '_'))
+            })
+            .collect();
 
-        let mut contract_children = Vec::new();
-        for ast in &root.children {
-            match ast {
-                // Root or ActionDescription nodes cannot be children of a root
-                // node. This must be handled in a previous
-                // pass.
-                Ast::Root(_) | Ast::ActionDescription(_) => {
-                    unreachable!()
+        self.labels
+            .iter()
+            .filter(|label| {
+                words.iter().any(|word| word.eq_ignore_ascii_case(label))
+            })
+            .collect()
+    }
+
+    /// Returns the parameters of a fuzz test in the current path.
+    ///
+    /// A parameter is named after the closest word before a placeholder
+    /// value, i.e. a single uppercase letter such as the `X` in `given amount
+    /// X`. Words that can't name a parameter, like the `is` in `given the
+    /// amount is X`, are skipped, and if no word is left the parameter is
+    /// named after the placeholder, e.g. `x`. Parameters are `uint256` unless
+    /// the condition that introduces them has a `[type:<type>]` marker.
+    fn fuzz_params(&self) -> Vec<hir::Parameter> {
+        let mut params: Vec<hir::Parameter> = Vec::new();
+        for (title, markers) in self.title_stack.iter().zip(&self.marker_stack)
+        {
+            let ty = markers
+                .iter()
+                .find(|marker| marker.name == "type")
+                .and_then(|marker| marker.value.as_deref())
+                .unwrap_or("uint256");
+            let words: Vec<&str> = title.split_whitespace().collect();
+            // Skip the keyword, since it never names a parameter.
+            for (idx, word) in words.iter().enumerate().skip(2) {
+                if !is_placeholder(word) {
+                    continue;
                 }
-                // Found a top-level action. This corresponds to a function.
-                Ast::Action(action) => {
-                    let words = action.title.split_whitespace();
-                    let words = words.skip(1); // Removes "it" from the test name.
 
-                    // Map an iterator over the words of an action to the test
-                    // name.
-                    //
-                    // Example: [do, stuff] -> DoStuff
-                    let test_name = words.fold(
-                        String::with_capacity(action.title.len()),
-                        |mut acc, w| {
-                            acc.reserve(w.len() + 1);
-                            acc.push_str(&upper_first_letter(w));
-                            acc
-                        },
-                    );
+                let name = words[1..idx]
+                    .iter()
+                    .rev()
+                    .filter(|word| !is_placeholder(word))
+                    .map(|word| sanitize(word))
+                    .find(|name| is_param_name(name))
+                    .unwrap_or_else(|| word.to_ascii_lowercase());
+                if params.iter().all(|param| param.name != name) {
+                    params.push(hir::Parameter { ty: ty.to_owned(), name });
+                }
+            }
+        }
 
-                    // We need to sanitize here and not in a previous compiler
-                    // phase because we want to emit the action as is in a
-                    // comment.
-                    let test_name = sanitize(&test_name);
-                    let test_name = format!("test_{test_name}");
+        params
+    }
+
+    /// Generates a test function that contains `actions`.
+    ///
+    /// `name` is the name of the test without its prefix, which is
+    /// `test_` by default and the configured fuzz prefix for fuzz tests.
+    ///
+    /// This applies the active markers to the function, which may change the
+    /// function's name and add statements at the end of its body. The
+    /// configured default statement, if any, goes at the top of its body.
+    fn gen_function(
+        &self,
+        name: String,
+        span: Span,
+        modifiers: Option<Vec<String>>,
+        mut children: Vec<Hir>,
+        actions: &[&Action],
+    ) -> Hir {
+        let markers = self.active_markers(actions);
+        let has_marker =
+            |name: &str| markers.iter().any(|marker| marker.name == name);
 
-                    let mut hirs = self.visit_action(action)?;
+        // Invariant tests take no parameters, so they can't be fuzz tests.
+        let is_invariant = has_marker("invariant");
+        let is_fuzz = !is_invariant && has_marker("fuzz");
+        let prefix = if is_invariant {
+            "invariant_"
+        } else if is_fuzz {
+            self.fuzz_prefix.as_str()
+        } else {
+            "test_"
+        };
+        let params = if is_fuzz { self.fuzz_params() } else { vec![] };
+        let mut identifier = format!("{prefix}{name}");
+        let mut statements = vec![];
 
-                    // Include any optional statement for the first function
-                    // node.
-                    if self.with_vm_skip {
-                        hirs.push(Hir::Statement(hir::Statement {
-                            ty: hir::StatementType::VmSkip,
-                        }));
-                    }
+        // Add a `vm.skip(true);` at the start of the function.
+        if self.with_vm_skip {
+            statements.push(hir::StatementType::VmSkip);
+        }
 
-                    let hir =
-                        Hir::FunctionDefinition(hir::FunctionDefinition {
-                            identifier: test_name,
-                            ty: hir::FunctionTy::Function,
-                            span: action.span,
-                            modifiers: None,
-                            children: Some(hirs),
-                        });
-                    contract_children.push(hir);
-                }
-                Ast::Condition(condition) => {
-                    contract_children
-                        .append(&mut self.visit_condition(condition)?);
+        for marker in &markers {
+            match (marker.name.as_str(), &marker.value) {
+                ("warp", Some(timestamp)) => statements
+                    .push(hir::StatementType::VmWarp(timestamp.clone())),
+                ("roll", Some(block)) => {
+                    statements.push(hir::StatementType::VmRoll(block.clone()));
                 }
+                _ => {}
             }
         }
 
-        // Add the contract definition to the hir.
-        root_children.push(Hir::ContractDefinition(hir::ContractDefinition {
-            identifier: root.contract_name.clone(),
-            children: contract_children,
-        }));
+        for label in self.mentioned_labels(actions) {
+            statements.push(hir::StatementType::VmLabel(label.clone()));
+        }
 
-        Ok(vec![Hir::Root(hir::Root { children: root_children })])
+        if has_marker("assembly") {
+            statements.push(hir::StatementType::Assembly);
+        }
+
+        let gas_name = has_marker("gas").then(|| {
+            identifier = format!("{prefix}Gas_{name}");
+            name
+        });
+
+        if let Some(template) =
+            BodyTemplate::find(&self.body_templates, &identifier)
+        {
+            statements
+                .push(hir::StatementType::Raw(template.expand(&identifier)));
+        }
+
+        if let Some(name) = gas_name {
+            statements.push(hir::StatementType::StartSnapshotGas(name));
+            statements.push(hir::StatementType::StopSnapshotGas);
+        }
+
+        children.extend(
+            statements
+                .into_iter()
+                .map(|ty| Hir::Statement(hir::Statement { ty })),
+        );
+        if let Some(statement) = &self.default_body_statement {
+            let ty = hir::StatementType::Raw(statement.clone());
+            children.insert(0, Hir::Statement(hir::Statement { ty }));
+        }
+        if self.condition_comments {
+            let comments = self.title_stack.iter().map(|title| {
+                let (keyword, rest) =
+                    title.split_once(' ').unwrap_or((title.as_str(), ""));
+                Hir::Comment(hir::Comment {
+                    lexeme: format!("{}: {rest}", keyword.to_uppercase()),
+                    kind: hir::CommentKind::Condition,
+                })
+            });
+            children.splice(0..0, comments);
+        }
+
+        Hir::FunctionDefinition(hir::FunctionDefinition {
+            identifier,
+            ty: hir::FunctionTy::Function,
+            span,
+            modifiers,
+            params,
+            condition_title: None,
+            payable: has_marker("payable"),
+            keyword: self
+                .title_stack
+                .last()
+                .and_then(|title| hir::ConditionKeyword::from_title(title)),
+            children: Some(children),
+        })
     }
 
-    fn visit_condition(
+    /// Appends the values of the value sets the current condition is nested
+    /// under to the test `name`, so that the tests under each value are told
+    /// apart.
+    fn with_value_suffix(&self, name: String) -> String {
+        self.value_stack.iter().fold(name, |acc, value| {
+            format!("{acc}_{}", sanitize(&upper_first_letter(value)))
+        })
+    }
+
+    /// Translates `condition` as if its title was `title`.
+    ///
+    /// This lets a condition with a value set be translated once per value,
+    /// where `value` is the one `title` was expanded with.
+    fn translate_condition(
         &mut self,
         condition: &Condition,
-    ) -> Result<Self::Output, Self::Error> {
+        title: &str,
+        value: Option<&str>,
+    ) -> Result<Vec<Hir>, ()> {
         let mut children = Vec::new();
+        self.marker_stack.push(condition.markers.clone());
+        self.title_stack.push(title.to_owned());
 
         let action_count = condition
             .children
@@ -176,7 +377,7 @@ impl<'a> Visitor for TranslatorI<'a> {
         // generate a modifier for it, since it would only be used in
         // the emitted function.
         if condition.children.len() != action_count {
-            if let Some(modifier) = self.modifiers.get(&condition.title) {
+            if let Some(modifier) = self.modifiers.get(title) {
                 self.modifier_stack.push(modifier);
                 // Add a modifier node.
                 let hir = Hir::FunctionDefinition(hir::FunctionDefinition {
@@ -184,18 +385,30 @@ impl<'a> Visitor for TranslatorI<'a> {
                     ty: hir::FunctionTy::Modifier,
                     span: condition.span,
                     modifiers: None,
+                    params: vec![],
+                    condition_title: Some(title.to_owned()),
+                    payable: false,
+                    keyword: None,
                     children: None,
                 });
-                children.push(hir);
+                if self.emitted_modifiers.insert(modifier.clone()) {
+                    children.push(hir);
+                }
             };
         }
 
         // We first visit all actions in order to keep the functions
         // in the same order that they appear in the source .tree text.
         let mut actions = Vec::new();
+        let mut action_nodes = Vec::new();
+        let mut seen = HashSet::new();
         for action in &condition.children {
             if let Ast::Action(action) = action {
+                if self.dedup_actions && !seen.insert(action.title.as_str()) {
+                    continue;
+                }
                 actions.append(&mut self.visit_action(action)?);
+                action_nodes.push(action);
             }
         }
 
@@ -213,7 +426,7 @@ impl<'a> Visitor for TranslatorI<'a> {
                 }
             });
 
-            let mut words = condition.title.split_whitespace();
+            let mut words = title.split_whitespace();
             // It is fine to unwrap because conditions have at least one word in
             // them.
             let keyword = upper_first_letter(words.next().unwrap());
@@ -224,9 +437,7 @@ impl<'a> Visitor for TranslatorI<'a> {
                 //
                 // Example: [when, something, happens] -> WhenSomethingHappens
                 let test_name = words.fold(
-                    String::with_capacity(
-                        condition.title.len() - keyword.len(),
-                    ),
+                    String::with_capacity(title.len() - keyword.len()),
                     |mut acc, w| {
                         acc.reserve(w.len() + 1);
                         acc.push_str(&upper_first_letter(w));
@@ -236,23 +447,23 @@ impl<'a> Visitor for TranslatorI<'a> {
 
                 // The structure for a function name when it is a revert is:
                 //
-                // test_Revert[KEYWORD]_Description
+                // [PREFIX]Revert[KEYWORD]_Description
                 //
-                // where `KEYWORD` is the starting word of the condition.
-                format!("test_Revert{keyword}_{test_name}")
+                // where `PREFIX` is added when generating the function and
+                // `KEYWORD` is the starting word of the condition.
+                format!("Revert{keyword}_{test_name}")
             } else {
                 // Map an iterator over the words of a condition to the test
                 // name.
                 //
                 // Example: [when, something, happens] -> WhenSomethingHappens
-                let test_name = words.fold(keyword, |mut acc, w| {
+                words.fold(keyword, |mut acc, w| {
                     acc.reserve(w.len() + 1);
                     acc.push_str(&upper_first_letter(w));
                     acc
-                });
-
-                format!("test_{test_name}")
+                })
             };
+            let function_name = self.with_value_suffix(function_name);
 
             let modifiers = if self.modifier_stack.is_empty() {
                 None
@@ -262,33 +473,153 @@ impl<'a> Visitor for TranslatorI<'a> {
                 )
             };
 
-            // Add a `vm.skip(true);` at the start of the function.
-            if self.with_vm_skip {
-                actions.push(Hir::Statement(hir::Statement {
-                    ty: hir::StatementType::VmSkip,
-                }));
-            }
-
-            let hir = Hir::FunctionDefinition(hir::FunctionDefinition {
-                identifier: function_name,
-                ty: hir::FunctionTy::Function,
-                span: condition.span,
+            actions.splice(0..0, condition.comment.as_deref().map(note));
+            let hir = self.gen_function(
+                function_name,
+                condition.span,
                 modifiers,
-                children: Some(actions),
-            });
+                actions,
+                &action_nodes,
+            );
             children.push(hir);
         }
 
         // Then we recursively visit all child conditions.
+        self.value_stack.extend(value.map(str::to_owned));
         for condition in &condition.children {
             if let Ast::Condition(condition) = condition {
                 children.append(&mut self.visit_condition(condition)?);
             }
         }
+        value.and_then(|_| self.value_stack.pop());
 
         if condition.children.len() != action_count {
             self.modifier_stack.pop();
         }
+        self.marker_stack.pop();
+        self.title_stack.pop();
+
+        Ok(children)
+    }
+}
+
+impl Visitor for TranslatorI<'_> {
+    type Error = ();
+    type Output = Vec<Hir>;
+
+    fn visit_root(
+        &mut self,
+        root: &bulloak_syntax::Root,
+    ) -> Result<Self::Output, Self::Error> {
+        let mut root_children = Vec::new();
+
+        let mut contract_children = Vec::new();
+        for ast in &root.children {
+            match ast {
+                // Root or ActionDescription nodes cannot be children of a root
+                // node. This must be handled in a previous
+                // pass.
+                Ast::Root(_) | Ast::ActionDescription(_) => {
+                    unreachable!()
+                }
+                // Found a top-level action. This corresponds to a function.
+                Ast::Action(action) => {
+                    let words = action.title.split_whitespace();
+                    let words = words.skip(1); // Removes "it" from the test name.
+
+                    // Map an iterator over the words of an action to the test
+                    // name.
+                    //
+                    // Example: [do, stuff] -> DoStuff
+                    let test_name = words.fold(
+                        String::with_capacity(action.title.len()),
+                        |mut acc, w| {
+                            acc.reserve(w.len() + 1);
+                            acc.push_str(&upper_first_letter(w));
+                            acc
+                        },
+                    );
+
+                    // We need to sanitize here and not in a previous compiler
+                    // phase because we want to emit the action as is in a
+                    // comment.
+                    let test_name = sanitize(&test_name);
+
+                    let hirs = self.visit_action(action)?;
+                    let hir = self.gen_function(
+                        test_name,
+                        action.span,
+                        None,
+                        hirs,
+                        &[action],
+                    );
+                    contract_children.push(hir);
+                }
+                Ast::Condition(condition) => {
+                    contract_children
+                        .append(&mut self.visit_condition(condition)?);
+                }
+            }
+        }
+
+        // The comments of the root become file-level comments.
+        root_children.extend(root.comments.iter().map(|comment| note(comment)));
+
+        // Add the contract definition to the hir.
+        root_children.push(Hir::ContractDefinition(hir::ContractDefinition {
+            identifier: if self.root_as_path {
+                super::split_root_path(&root.contract_name).1.to_owned()
+            } else {
+                root.contract_name.clone()
+            },
+            natspec: root
+                .directives
+                .iter()
+                .filter(|directive| !directive.is_type_declaration())
+                .map(|directive| {
+                    format!("@{} {}", directive.tag, directive.content)
+                        .trim_end()
+                        .to_owned()
+                })
+                .chain(root.markers.iter().filter_map(|marker| {
+                    // `[evm:cancun]` pins the EVM version of the whole suite
+                    // through Foundry's inline config.
+                    let version = marker.value.as_ref()?;
+                    (marker.name == "evm").then(|| {
+                        format!(
+                            r#"forge-config: default.evm_version = "{version}""#
+                        )
+                    })
+                }))
+                .collect(),
+            types: root
+                .directives
+                .iter()
+                .filter_map(Directive::type_declaration)
+                .collect(),
+            bases: vec![],
+            children: contract_children,
+        }));
+
+        Ok(vec![Hir::Root(hir::Root { children: root_children })])
+    }
+
+    fn visit_condition(
+        &mut self,
+        condition: &Condition,
+    ) -> Result<Self::Output, Self::Error> {
+        let Some(values) = hir::value_set(&condition.title) else {
+            return self.translate_condition(condition, &condition.title, None);
+        };
+
+        let mut children = Vec::new();
+        for (value, title) in values {
+            children.append(&mut self.translate_condition(
+                condition,
+                &title,
+                Some(value),
+            )?);
+        }
 
         Ok(children)
     }
@@ -303,11 +634,64 @@ impl<'a> Visitor for TranslatorI<'a> {
                 descriptions.append(&mut self.visit_description(description)?);
             }
         }
+        if self.aaa_from_descriptions && descriptions.len() == AAA_STEPS.len() {
+            for (description, step) in descriptions.iter_mut().zip(AAA_STEPS) {
+                if let Hir::Comment(comment) = description {
+                    let text = comment.lexeme.trim_start();
+                    let indent =
+                        &comment.lexeme[..comment.lexeme.len() - text.len()];
+                    comment.lexeme = format!("{indent}{step}: {text}");
+                }
+            }
+        }
+
+        // Only the markers of the action itself enable tracing, so that
+        // logs don't spread to every action under a condition.
+        let trace = action
+            .markers
+            .iter()
+            .any(|marker| marker.name == "trace")
+            .then(|| {
+                hir::Hir::Statement(hir::Statement {
+                    ty: hir::StatementType::ConsoleLog(action.title.clone()),
+                })
+            });
+
+        let assertion = expected_value(&action.title)
+            .filter(|_| self.emit_assertions)
+            .map(|expected| {
+                hir::Hir::Statement(hir::Statement {
+                    ty: hir::StatementType::AssertEq(expected),
+                })
+            });
+
+        let expect_revert = custom_error(&action.title)
+            .filter(|_| self.emit_expect_revert)
+            .map(|(error, args)| {
+                hir::Hir::Statement(hir::Statement {
+                    ty: hir::StatementType::ExpectRevert(error, args),
+                })
+            });
+
+        let no_revert = (self.emit_no_revert && never_reverts(&action.title))
+            .then_some([
+                hir::StatementType::LowLevelCall,
+                hir::StatementType::AssertSuccess,
+            ])
+            .into_iter()
+            .flatten()
+            .map(|ty| hir::Hir::Statement(hir::Statement { ty }));
 
         Ok(std::iter::once(hir::Hir::Comment(hir::Comment {
             lexeme: action.title.clone(),
+            kind: hir::CommentKind::Action,
         }))
+        .chain(action.comment.as_deref().map(note))
         .chain(descriptions)
+        .chain(trace)
+        .chain(assertion)
+        .chain(expect_revert)
+        .chain(no_revert)
         .collect())
     }
 
@@ -315,12 +699,193 @@ impl<'a> Visitor for TranslatorI<'a> {
         &mut self,
         description: &Description,
     ) -> Result<Self::Output, Self::Error> {
+        if let Some(statements) = description_statements(&description.text)
+            .filter(|_| self.smart_descriptions)
+        {
+            return Ok(statements
+                .into_iter()
+                .map(|ty| hir::Hir::Statement(hir::Statement { ty }))
+                .collect());
+        }
+
         Ok(vec![hir::Hir::Comment(hir::Comment {
             lexeme: description.text.clone(),
+            kind: hir::CommentKind::Description,
         })])
     }
 }
 
+/// Returns the HIR of a comment written in the tree, e.g. `// See #42.`.
+fn note(comment: &str) -> Hir {
+    Hir::Comment(hir::Comment {
+        lexeme: comment.to_owned(),
+        kind: hir::CommentKind::Note,
+    })
+}
+
+/// The words that never name a fuzz parameter: the ones that link a noun to
+/// its value, e.g. the `is` in `given the amount is X`, and the reserved
+/// words of Solidity that read like English.
+const NON_PARAM_WORDS: [&str; 40] = [
+    "a", "after", "an", "and", "are", "at", "be", "by", "case", "default",
+    "delete", "do", "else", "equal", "equals", "for", "from", "has", "have",
+    "if", "in", "is", "new", "of", "or", "return", "returns", "than", "the",
+    "then", "to", "true", "false", "try", "type", "was", "were", "while",
+    "with", "within",
+];
+
+/// Whether `word` is a placeholder value, i.e. a single uppercase letter.
+fn is_placeholder(word: &str) -> bool {
+    word.len() == 1 && word.chars().all(|c| c.is_ascii_uppercase())
+}
+
+/// Whether the sanitized word `name` can name a fuzz parameter.
+fn is_param_name(name: &str) -> bool {
+    name.chars().next().is_some_and(char::is_alphabetic)
+        && !NON_PARAM_WORDS.contains(&name.to_lowercase().as_str())
+}
+
+/// The phrases that introduce the value an action expects, e.g.
+/// `it should equal 42`.
+const EXPECTATIONS: [&str; 4] = ["equal to", "equal", "equals", "return"];
+
+/// Returns the value an action titled `title` expects, if it states one.
+///
+/// The value is the word after one of the [`EXPECTATIONS`], and it's only
+/// returned if it can be used as is in Solidity: a number, e.g. `42` or
+/// `0xff`, or a named constant, e.g. `MAX_UINT`.
+fn expected_value(title: &str) -> Option<String> {
+    let words: Vec<&str> = title.split_whitespace().collect();
+    let lowercase: Vec<String> =
+        words.iter().map(|word| word.to_lowercase()).collect();
+    let value = EXPECTATIONS.iter().find_map(|expectation| {
+        let phrase: Vec<&str> = expectation.split(' ').collect();
+        lowercase
+            .windows(phrase.len())
+            .position(|window| window.iter().eq(phrase.iter()))
+            .and_then(|idx| words.get(idx + phrase.len()))
+    })?;
+
+    let value = value.trim_end_matches(['.', ',', ';']).trim_matches('`');
+    (is_number(value) || is_named_constant(value)).then(|| value.to_owned())
+}
+
+/// The phrase that introduces the custom error an action reverts with, e.g.
+/// `it should revert with Unauthorized(caller)`.
+const REVERT_WITH: &str = "revert with ";
+
+/// Returns the name and the arguments of the custom error an action titled
+/// `title` reverts with, if it names one.
+///
+/// Custom errors are named in `PascalCase` and may be followed by their
+/// arguments in parentheses, e.g. `InsufficientBalance(amount, 0)`.
+fn custom_error(title: &str) -> Option<(String, Vec<String>)> {
+    // Lowercasing only ASCII letters keeps the byte offsets of `title`.
+    let idx = title.to_ascii_lowercase().find(REVERT_WITH)?;
+    let rest = title[idx + REVERT_WITH.len()..].trim_start_matches('`');
+    let name_len = rest
+        .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+        .unwrap_or(rest.len());
+    let (name, rest) = rest.split_at(name_len);
+    if !name.starts_with(|c: char| c.is_ascii_uppercase()) {
+        return None;
+    }
+
+    let args = match rest.strip_prefix('(') {
+        Some(rest) => {
+            let (args, _) = rest.split_once(')')?;
+            args.split(',')
+                .map(str::trim)
+                .filter(|arg| !arg.is_empty())
+                .map(ToOwned::to_owned)
+                .collect()
+        }
+        None => vec![],
+    };
+
+    Some((name.to_owned(), args))
+}
+
+/// The phrases that state that an action doesn't revert, e.g.
+/// `it never reverts`.
+const NO_REVERT: [&str; 3] = ["never revert", "not revert", "n't revert"];
+
+/// Whether an action titled `title` states that it never reverts.
+fn never_reverts(title: &str) -> bool {
+    let title = title.to_lowercase();
+    NO_REVERT.iter().any(|phrase| title.contains(phrase))
+}
+
+/// The verbs that introduce the contract a description calls, e.g.
+/// `calls the oracle`.
+const CALL_VERBS: [&str; 2] = ["call", "calls"];
+/// The verbs that introduce the event a description emits, e.g.
+/// `emits a {Transfer} event`.
+const EMIT_VERBS: [&str; 2] = ["emit", "emits"];
+/// The words skipped between a verb and the name it introduces.
+const ARTICLES: [&str; 3] = ["a", "an", "the"];
+
+/// Returns the cheatcode stubs a description with the text `text` maps to,
+/// if it calls a contract or emits an event.
+///
+/// The name right after the verb, ignoring articles, is used as the target
+/// of the call or as the event, so it must be a valid identifier.
+fn description_statements(text: &str) -> Option<Vec<hir::StatementType>> {
+    let words: Vec<&str> = text.split_whitespace().collect();
+    words.iter().enumerate().find_map(|(idx, word)| {
+        let verb = word.to_lowercase();
+        let is_call = CALL_VERBS.contains(&verb.as_str());
+        if !is_call && !EMIT_VERBS.contains(&verb.as_str()) {
+            return None;
+        }
+
+        let name = words[idx + 1..]
+            .iter()
+            .find(|word| !ARTICLES.contains(&word.to_lowercase().as_str()))?
+            .trim_end_matches(['.', ',', ';'])
+            .trim_matches(['`', '{', '}']);
+        let is_identifier = name
+            .starts_with(|c: char| c.is_alphabetic() || c == '_')
+            && name.chars().all(|c| c.is_alphanumeric() || c == '_');
+        if !is_identifier {
+            return None;
+        }
+
+        Some(if is_call {
+            vec![hir::StatementType::ExpectCall(name.to_owned())]
+        } else {
+            vec![
+                hir::StatementType::ExpectEmit,
+                hir::StatementType::Emit(name.to_owned()),
+            ]
+        })
+    })
+}
+
+/// Whether `word` is a decimal or hexadecimal number literal.
+fn is_number(word: &str) -> bool {
+    let digits = word.strip_prefix("0x").unwrap_or(word);
+    let is_hex = digits.len() != word.len();
+    !digits.is_empty()
+        && digits.chars().all(|c| {
+            c == '_'
+                || if is_hex {
+                    c.is_ascii_hexdigit()
+                } else {
+                    c.is_ascii_digit()
+                }
+        })
+}
+
+/// Whether `word` is named like a constant, i.e. in `SCREAMING_SNAKE_CASE`.
+fn is_named_constant(word: &str) -> bool {
+    word.starts_with(|c: char| c.is_ascii_uppercase() || c == '_')
+        && word.chars().any(|c| c.is_ascii_uppercase())
+        && word
+            .chars()
+            .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_')
+}
+
 #[cfg(test)]
 mod tests {
     use anyhow::Result;
@@ -350,6 +915,9 @@ mod tests {
     fn contract(identifier: String, children: Vec<Hir>) -> Hir {
         Hir::ContractDefinition(hir::ContractDefinition {
             identifier,
+            natspec: vec![],
+            types: vec![],
+            bases: vec![],
             children,
         })
     }
@@ -361,21 +929,52 @@ mod tests {
         modifiers: Option<Vec<String>>,
         children: Option<Vec<Hir>>,
     ) -> Hir {
+        // Test names start with the keyword of their condition, if any.
+        let name = identifier
+            .trim_start_matches("test_")
+            .trim_start_matches("testFuzz_")
+            .trim_start_matches("Revert");
+        let keyword = if name.starts_with("When") {
+            Some(hir::ConditionKeyword::When)
+        } else if name.starts_with("Given") {
+            Some(hir::ConditionKeyword::Given)
+        } else {
+            None
+        };
+
         Hir::FunctionDefinition(hir::FunctionDefinition {
             identifier,
             ty,
             span,
             modifiers,
+            params: vec![],
+            condition_title: None,
+            payable: false,
+            keyword,
             children,
         })
     }
 
+    fn modifier(identifier: &str, title: &str, span: Span) -> Hir {
+        Hir::FunctionDefinition(hir::FunctionDefinition {
+            identifier: identifier.to_owned(),
+            ty: hir::FunctionTy::Modifier,
+            span,
+            modifiers: None,
+            params: vec![],
+            condition_title: Some(title.to_owned()),
+            payable: false,
+            keyword: None,
+            children: None,
+        })
+    }
+
     fn statement(ty: hir::StatementType) -> Hir {
         Hir::Statement(hir::Statement { ty })
     }
 
     fn comment(lexeme: String) -> Hir {
-        Hir::Comment(hir::Comment { lexeme })
+        Hir::Comment(hir::Comment { lexeme, kind: hir::CommentKind::Action })
     }
 
     #[test]
@@ -400,6 +999,524 @@ mod tests {
         );
     }
 
+    #[test]
+    fn assembly_markers() {
+        let file_contents = r"Foo_Test
+└── when something happens [assembly]
+   └── when something else happens
+      └── it should do something";
+        assert_eq!(
+            translate(file_contents).unwrap(),
+            root(vec![contract(
+                "Foo_Test".to_owned(),
+                vec![
+                    modifier(
+                        "whenSomethingHappens",
+                        "when something happens",
+                        Span::new(
+                            Position::new(9, 2, 1),
+                            Position::new(131, 4, 32)
+                        )
+                    ),
+                    function(
+                        "test_WhenSomethingElseHappens".to_owned(),
+                        hir::FunctionTy::Function,
+                        Span::new(
+                            Position::new(56, 3, 4),
+                            Position::new(131, 4, 32)
+                        ),
+                        Some(vec!["whenSomethingHappens".to_owned()]),
+                        Some(vec![
+                            comment("it should do something".to_owned()),
+                            statement(hir::StatementType::VmSkip),
+                            statement(hir::StatementType::Assembly)
+                        ])
+                    ),
+                ]
+            )])
+        );
+    }
+
+    #[test]
+    fn expected_values() {
+        use super::expected_value;
+
+        assert_eq!(
+            expected_value("it should equal MAX_UINT."),
+            Some("MAX_UINT".to_owned())
+        );
+        assert_eq!(
+            expected_value("It should return `0xff`"),
+            Some("0xff".to_owned())
+        );
+        assert_eq!(
+            expected_value("it should be equal to 1_000"),
+            Some("1_000".to_owned())
+        );
+        assert_eq!(expected_value("it should equal the balance"), None);
+        assert_eq!(expected_value("it should equal Max"), None);
+        assert_eq!(expected_value("it should revert"), None);
+    }
+
+    #[test]
+    fn custom_errors() {
+        use super::custom_error;
+
+        assert_eq!(
+            custom_error("it should revert with CustomError(arg)."),
+            Some(("CustomError".to_owned(), vec!["arg".to_owned()]))
+        );
+        assert_eq!(
+            custom_error("It should revert with `Unauthorized`"),
+            Some(("Unauthorized".to_owned(), vec![]))
+        );
+        assert_eq!(
+            custom_error("it should revert with Bad(amount, 0) as expected"),
+            Some(("Bad".to_owned(), vec!["amount".to_owned(), "0".to_owned()]))
+        );
+        assert_eq!(
+            custom_error("İt should revert with Unauthorized"),
+            Some(("Unauthorized".to_owned(), vec![]))
+        );
+        assert_eq!(custom_error("it should revert with an error"), None);
+        assert_eq!(custom_error("it should revert with Bad(amount"), None);
+        assert_eq!(custom_error("it should revert"), None);
+    }
+
+    #[test]
+    fn no_revert_checks() {
+        use super::never_reverts;
+
+        assert!(never_reverts("it never reverts"));
+        assert!(never_reverts("it should not revert"));
+        assert!(never_reverts("it doesn't revert"));
+        assert!(!never_reverts("it should revert"));
+
+        let ast = parse_one("Foo_Test\n├── it never reverts\n└── it reverts")
+            .unwrap();
+        let mut discoverer = modifiers::ModifierDiscoverer::new();
+        let modifiers = discoverer.discover(&ast);
+        let cfg = Config { emit_no_revert: true, ..Config::default() };
+        let hir =
+            hir::translator::Translator::new().translate(&ast, modifiers, &cfg);
+
+        let Some(contract) = hir.find_contract() else { panic!() };
+        let children = |index: usize| {
+            let Hir::FunctionDefinition(function) = &contract.children[index]
+            else {
+                panic!()
+            };
+            function.children.clone().unwrap()
+        };
+        assert_eq!(
+            children(0),
+            vec![
+                comment("it never reverts".to_owned()),
+                statement(hir::StatementType::LowLevelCall),
+                statement(hir::StatementType::AssertSuccess),
+            ]
+        );
+        assert_eq!(children(1), vec![comment("it reverts".to_owned())]);
+    }
+
+    #[test]
+    fn assertions() {
+        let ast = parse_one("Foo_Test\n└── it should equal MAX_UINT").unwrap();
+        let mut discoverer = modifiers::ModifierDiscoverer::new();
+        let modifiers = discoverer.discover(&ast);
+        let cfg = Config { emit_assertions: true, ..Config::default() };
+        let hir =
+            hir::translator::Translator::new().translate(&ast, modifiers, &cfg);
+
+        assert!(hir.contains_text("MAX_UINT"));
+        let Some(contract) = hir.find_contract() else { panic!() };
+        let Hir::FunctionDefinition(function) = &contract.children[0] else {
+            panic!()
+        };
+        assert_eq!(
+            function.children.as_ref().unwrap()[1],
+            statement(hir::StatementType::AssertEq("MAX_UINT".to_owned()))
+        );
+    }
+
+    #[test]
+    fn default_body_statement() {
+        let ast = parse_one("Foo_Test\n└── it should work").unwrap();
+        let mut discoverer = modifiers::ModifierDiscoverer::new();
+        let modifiers = discoverer.discover(&ast);
+        let cfg = Config {
+            emit_vm_skip: true,
+            default_body_statement: Some("_test();".to_owned()),
+            ..Config::default()
+        };
+        let hir =
+            hir::translator::Translator::new().translate(&ast, modifiers, &cfg);
+
+        let Some(contract) = hir.find_contract() else { panic!() };
+        let Hir::FunctionDefinition(function) = &contract.children[0] else {
+            panic!()
+        };
+        assert_eq!(
+            function.children,
+            Some(vec![
+                statement(hir::StatementType::Raw("_test();".to_owned())),
+                comment("it should work".to_owned()),
+                statement(hir::StatementType::VmSkip),
+            ])
+        );
+    }
+
+    #[test]
+    fn aaa_from_descriptions() {
+        let tree = r"Foo_Test
+├── it should deposit
+│   ├── Mint some tokens.
+│   ├── Call deposit.
+│   └── Check the balance.
+└── it should withdraw
+    └── Call withdraw.";
+        let ast = parse_one(tree).unwrap();
+        let mut discoverer = modifiers::ModifierDiscoverer::new();
+        let modifiers = discoverer.discover(&ast);
+        let cfg = Config { aaa_from_descriptions: true, ..Config::default() };
+        let hir =
+            hir::translator::Translator::new().translate(&ast, modifiers, &cfg);
+
+        let Some(contract) = hir.find_contract() else { panic!() };
+        let lexemes = |index: usize| {
+            let Hir::FunctionDefinition(function) = &contract.children[index]
+            else {
+                panic!()
+            };
+            function
+                .children
+                .iter()
+                .flatten()
+                .filter_map(|child| match child {
+                    Hir::Comment(comment) => Some(comment.lexeme.clone()),
+                    _ => None,
+                })
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            lexemes(0),
+            vec![
+                "it should deposit",
+                "    Arrange: Mint some tokens.",
+                "    Act: Call deposit.",
+                "    Assert: Check the balance.",
+            ]
+        );
+        assert_eq!(
+            lexemes(1),
+            vec!["it should withdraw", "    Call withdraw."]
+        );
+    }
+
+    #[test]
+    fn dedup_actions() {
+        let tree = r"Foo_Test
+└── when something happens
+    ├── it should work
+    ├── it should emit
+    └── it should work";
+        let ast = parse_one(tree).unwrap();
+        let mut discoverer = modifiers::ModifierDiscoverer::new();
+        let modifiers = discoverer.discover(&ast);
+        let cfg = Config { dedup_actions: true, ..Config::default() };
+        let hir =
+            hir::translator::Translator::new().translate(&ast, modifiers, &cfg);
+
+        let Some(contract) = hir.find_contract() else { panic!() };
+        let Hir::FunctionDefinition(function) = &contract.children[0] else {
+            panic!()
+        };
+        let lexemes: Vec<_> = function
+            .children
+            .iter()
+            .flatten()
+            .filter_map(|child| match child {
+                Hir::Comment(comment) => Some(comment.lexeme.as_str()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(lexemes, vec!["it should work", "it should emit"]);
+    }
+
+    #[test]
+    fn condition_comments() {
+        let tree = r"Foo_Test
+├── given the vault is paused
+│   └── when the caller is the owner
+│       └── it should unpause
+└── it should work";
+        let ast = parse_one(tree).unwrap();
+        let mut discoverer = modifiers::ModifierDiscoverer::new();
+        let modifiers = discoverer.discover(&ast);
+        let cfg = Config { condition_comments: true, ..Config::default() };
+        let hir =
+            hir::translator::Translator::new().translate(&ast, modifiers, &cfg);
+
+        let Some(contract) = hir.find_contract() else { panic!() };
+        let lexemes = |index: usize| {
+            let Hir::FunctionDefinition(function) = &contract.children[index]
+            else {
+                panic!()
+            };
+            function
+                .children
+                .iter()
+                .flatten()
+                .filter_map(|child| match child {
+                    Hir::Comment(comment) => Some(comment.lexeme.clone()),
+                    _ => None,
+                })
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            lexemes(1),
+            vec![
+                "GIVEN: the vault is paused",
+                "WHEN: the caller is the owner",
+                "it should unpause",
+            ]
+        );
+        assert_eq!(lexemes(2), vec!["it should work"]);
+    }
+
+    #[test]
+    fn value_sets() {
+        let file_contents = r"Foo_Test
+└── given the status in {Active, Paused}
+   └── when something happens
+      └── it should work";
+        let hir = translate(file_contents).unwrap();
+
+        let Some(contract) = hir.find_contract() else { panic!() };
+        let identifiers: Vec<_> = contract
+            .children
+            .iter()
+            .filter_map(|child| match child {
+                Hir::FunctionDefinition(function) => {
+                    Some(function.identifier.as_str())
+                }
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            identifiers,
+            vec![
+                "givenTheStatusIsActive",
+                "test_WhenSomethingHappens_Active",
+                "givenTheStatusIsPaused",
+                "test_WhenSomethingHappens_Paused",
+            ]
+        );
+        assert_eq!(
+            hir::expand_value_set("given x in {A, B} now"),
+            Some(vec![
+                "given x is A now".to_owned(),
+                "given x is B now".to_owned()
+            ])
+        );
+        assert_eq!(hir::expand_value_set("given x in a set"), None);
+    }
+
+    #[test]
+    fn warp_and_roll_markers() {
+        let file_contents = r"Foo_Test
+└── when time passes [warp:1000]
+   └── it should work [roll:START_BLOCK]";
+        assert_eq!(
+            translate(file_contents).unwrap(),
+            root(vec![contract(
+                "Foo_Test".to_owned(),
+                vec![function(
+                    "test_WhenTimePasses".to_owned(),
+                    hir::FunctionTy::Function,
+                    Span::new(Position::new(9, 2, 1), Position::new(93, 3, 40)),
+                    None,
+                    Some(vec![
+                        comment("it should work".to_owned()),
+                        statement(hir::StatementType::VmSkip),
+                        statement(hir::StatementType::VmWarp(
+                            "1000".to_owned()
+                        )),
+                        statement(hir::StatementType::VmRoll(
+                            "START_BLOCK".to_owned()
+                        )),
+                    ])
+                )]
+            )])
+        );
+    }
+
+    #[test]
+    fn gas_markers() {
+        let file_contents = "Foo_Test\n└── it should be cheap [gas]";
+        assert_eq!(
+            translate(file_contents).unwrap(),
+            root(vec![contract(
+                "Foo_Test".to_owned(),
+                vec![function(
+                    "test_Gas_ShouldBeCheap".to_owned(),
+                    hir::FunctionTy::Function,
+                    Span::new(Position::new(9, 2, 1), Position::new(42, 2, 28)),
+                    None,
+                    Some(vec![
+                        comment("it should be cheap".to_owned()),
+                        statement(hir::StatementType::VmSkip),
+                        statement(hir::StatementType::StartSnapshotGas(
+                            "ShouldBeCheap".to_owned()
+                        )),
+                        statement(hir::StatementType::StopSnapshotGas),
+                    ])
+                )]
+            )])
+        );
+    }
+
+    #[test]
+    fn fuzz_markers() {
+        let file_contents = r"Foo_Test
+└── when something happens [fuzz]
+   ├── it should revert
+   └── when something else happens
+      └── it should revert";
+        assert_eq!(
+            translate(file_contents).unwrap(),
+            root(vec![contract(
+                "Foo_Test".to_owned(),
+                vec![
+                    modifier(
+                        "whenSomethingHappens",
+                        "when something happens",
+                        Span::new(
+                            Position::new(9, 2, 1),
+                            Position::new(151, 5, 26)
+                        )
+                    ),
+                    function(
+                        "testFuzz_RevertWhen_SomethingHappens".to_owned(),
+                        hir::FunctionTy::Function,
+                        Span::new(
+                            Position::new(9, 2, 1),
+                            Position::new(151, 5, 26)
+                        ),
+                        Some(vec!["whenSomethingHappens".to_owned()]),
+                        Some(vec![
+                            comment("it should revert".to_owned()),
+                            statement(hir::StatementType::VmSkip),
+                        ])
+                    ),
+                    function(
+                        "testFuzz_RevertWhen_SomethingElseHappens".to_owned(),
+                        hir::FunctionTy::Function,
+                        Span::new(
+                            Position::new(82, 4, 4),
+                            Position::new(151, 5, 26)
+                        ),
+                        Some(vec!["whenSomethingHappens".to_owned()]),
+                        Some(vec![
+                            comment("it should revert".to_owned()),
+                            statement(hir::StatementType::VmSkip),
+                        ])
+                    ),
+                ]
+            )])
+        );
+    }
+
+    #[test]
+    fn fuzz_params() {
+        let file_contents = r"Foo_Test
+└── given amount X [fuzz]
+   └── when fee F exceeds amount X
+      └── it should revert";
+        let hir = translate(file_contents).unwrap();
+        let contract = hir.find_contract().unwrap();
+        let params: Vec<_> = contract
+            .children
+            .iter()
+            .filter_map(|child| match child {
+                Hir::FunctionDefinition(function) => {
+                    Some(function.params.clone())
+                }
+                _ => None,
+            })
+            .collect();
+
+        let param = |ty: &str, name: &str| hir::Parameter {
+            ty: ty.to_owned(),
+            name: name.to_owned(),
+        };
+        assert_eq!(
+            params,
+            vec![
+                vec![],
+                vec![param("uint256", "amount"), param("uint256", "fee")]
+            ]
+        );
+    }
+
+    #[test]
+    fn fuzz_params_skip_linking_words() {
+        let params = |file_contents: &str| -> Vec<String> {
+            let hir = translate(file_contents).unwrap();
+            let contract = hir.find_contract().unwrap();
+            contract
+                .children
+                .iter()
+                .filter_map(|child| match child {
+                    Hir::FunctionDefinition(function)
+                        if function.is_function() =>
+                    {
+                        Some(function.params.iter().map(ToString::to_string))
+                    }
+                    _ => None,
+                })
+                .flatten()
+                .collect()
+        };
+
+        let file_contents = r"Foo_Test
+└── given the amount is X [fuzz]
+   └── when the fee is F
+      └── it should revert";
+        assert_eq!(
+            params(file_contents),
+            vec!["uint256 amount", "uint256 fee"]
+        );
+
+        let file_contents = r"Foo_Test
+└── when the X is Y [fuzz]
+   └── it should revert";
+        assert_eq!(params(file_contents), vec!["uint256 x", "uint256 y"]);
+    }
+
+    #[test]
+    fn typed_fuzz_params() {
+        let file_contents = r"Foo_Test
+└── given payload P [type:bytes] [fuzz]
+   └── when fee F exceeds the limit
+      └── it should revert";
+        let hir = translate(file_contents).unwrap();
+        let contract = hir.find_contract().unwrap();
+        let params: Vec<String> = contract
+            .children
+            .iter()
+            .filter_map(|child| match child {
+                Hir::FunctionDefinition(function) if function.is_function() => {
+                    Some(function.params.iter().map(ToString::to_string))
+                }
+                _ => None,
+            })
+            .flatten()
+            .collect();
+
+        assert_eq!(params, vec!["bytes memory payload", "uint256 fee"]);
+    }
+
     #[test]
     fn two_children() {
         let file_contents = r"FooBarTheBest_Test
@@ -462,15 +1579,13 @@ Foo_Test
             root(vec![contract(
                 "Foo_Test".to_owned(),
                 vec![
-                    function(
-                        "whenStuffCalled".to_owned(),
-                        hir::FunctionTy::Modifier,
+                    modifier(
+                        "whenStuffCalled",
+                        "when stuff called",
                         Span::new(
                             Position::new(10, 3, 1),
                             Position::new(235, 9, 32)
-                        ),
-                        None,
-                        None
+                        )
                     ),
                     function(
                         "test_WhenStuffCalled".to_owned(),
@@ -12,6 +12,7 @@ pub mod check;
 pub mod config;
 pub mod constants;
 pub mod hir;
+pub mod reverse;
 pub mod scaffold;
 pub mod sol;
 
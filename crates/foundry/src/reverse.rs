@@ -0,0 +1,310 @@
+//! Reconstructs a tree from an existing Solidity test file.
+//!
+//! This is the inverse of scaffolding: the names of the tests and of the
+//! modifiers applied to them are un-mangled back into conditions, and the
+//! action comments in their bodies become actions. The result is a
+//! best-effort `.tree` that scaffolds back into the same functions for the
+//! common cases.
+
+use anyhow::{anyhow, bail};
+use bulloak_syntax::utils::{sanitize, to_pascal_case, upper_first_letter};
+use solang_parser::pt::{
+    CodeLocation, ContractPart, FunctionAttribute, FunctionDefinition,
+    FunctionTy,
+};
+
+use crate::{config::Config, sol::find_contract};
+
+/// The action of a test whose body has no action comments.
+const DEFAULT_ACTION: &str = "It should pass.";
+/// The action of a revert test whose body has no action comments.
+const REVERT_ACTION: &str = "It should revert.";
+
+/// A node of the reconstructed tree.
+#[derive(Debug, Default)]
+struct Node {
+    /// The text of the node, without the `├──` connector.
+    title: String,
+    /// The children of the node, in the order they were found.
+    children: Vec<Node>,
+    /// Whether this node is a condition that can have conditions nested in
+    /// it.
+    is_condition: bool,
+}
+
+impl Node {
+    fn condition(title: String) -> Self {
+        Self { title, children: vec![], is_condition: true }
+    }
+
+    fn action(title: String) -> Self {
+        Self { title, children: vec![], is_condition: false }
+    }
+
+    /// Returns the condition child whose title scaffolds into the same name
+    /// as `title`, adding it if there is none.
+    fn child_condition(&mut self, title: &str) -> &mut Node {
+        let name = to_pascal_case(&sanitize(title));
+        let idx = self.children.iter().position(|child| {
+            child.is_condition
+                && to_pascal_case(&sanitize(strip_markers(&child.title)))
+                    == name
+        });
+        let idx = idx.unwrap_or_else(|| {
+            self.children.push(Node::condition(title.to_owned()));
+            self.children.len() - 1
+        });
+        &mut self.children[idx]
+    }
+}
+
+/// Reconstructs the tree of the test contract in the Solidity code `src`.
+///
+/// Only functions named like tests, e.g. `test_WhenTheCallerIsTheOwner` or
+/// `testFuzz_RevertWhen_TheAmountIsZero`, are taken into account. Modifiers
+/// named like conditions, e.g. `whenTheCallerIsTheOwner`, become the
+/// conditions the test is nested in.
+///
+/// # Errors
+///
+/// Returns an error if `src` can't be parsed or has no contract.
+pub fn reconstruct(src: &str, cfg: &Config) -> anyhow::Result<String> {
+    let (pt, _) = solang_parser::parse(src, 0)
+        .map_err(|_| anyhow!("failed to parse the Solidity code"))?;
+    let Some(contract) = find_contract(&pt) else {
+        bail!("no contract found in the Solidity code");
+    };
+    let Some(name) = &contract.name else {
+        bail!("the contract has no name");
+    };
+
+    let mut root = Node::condition(name.name.clone());
+    for part in &contract.parts {
+        if let ContractPart::FunctionDefinition(f) = part {
+            add_test(&mut root, f, src, cfg);
+        }
+    }
+
+    let mut tree = root.title.clone();
+    tree.push('\n');
+    render(&root.children, "", &mut tree);
+    Ok(tree)
+}
+
+/// Adds the test `f` to the tree under `root`, if `f` is a test.
+fn add_test(root: &mut Node, f: &FunctionDefinition, src: &str, cfg: &Config) {
+    if !matches!(f.ty, FunctionTy::Function) {
+        return;
+    }
+    let Some(name) = f.name.as_ref().map(|name| name.name.as_str()) else {
+        return;
+    };
+    let (name, is_fuzz) =
+        if let Some(name) = name.strip_prefix(&cfg.fuzz_prefix) {
+            (name, true)
+        } else if let Some(name) = name.strip_prefix("test_") {
+            (name, false)
+        } else {
+            return;
+        };
+
+    let (condition, is_revert) = match name.strip_prefix("Revert") {
+        Some(rest) if condition_keyword(rest).is_some() => {
+            (Some(to_words(&rest.replacen('_', "", 1))), true)
+        }
+        _ if condition_keyword(name).is_some() => (Some(to_words(name)), false),
+        _ => (None, false),
+    };
+
+    let modifiers = modifiers(f);
+    let mut node = root;
+    for modifier in &modifiers {
+        node = node.child_condition(&upper_first_letter(&to_words(modifier)));
+    }
+    if let Some(condition) = condition {
+        // A test with actions and nested conditions is applied the modifier
+        // of its own condition.
+        let condition = upper_first_letter(&condition);
+        let is_innermost_modifier = !modifiers.is_empty()
+            && to_pascal_case(&sanitize(strip_markers(&node.title)))
+                == to_pascal_case(&sanitize(&condition));
+        if !is_innermost_modifier {
+            let title =
+                if is_fuzz { format!("{condition} [fuzz]") } else { condition };
+            node = node.child_condition(&title);
+        }
+    }
+
+    let mut actions = body_actions(f, src);
+    if actions.is_empty() {
+        let title = match name {
+            _ if is_revert => REVERT_ACTION.to_owned(),
+            _ if condition_keyword(name).is_some() => DEFAULT_ACTION.to_owned(),
+            _ => format!("It {}.", to_words(name)),
+        };
+        actions.push(Node::action(title));
+    }
+    node.children.extend(actions);
+}
+
+/// Returns the keyword a mangled condition, e.g. `WhenTheAmountIsZero`,
+/// starts with.
+fn condition_keyword(name: &str) -> Option<&'static str> {
+    ["When", "Given"].into_iter().find(|keyword| {
+        name.strip_prefix(keyword)
+            .and_then(|rest| rest.chars().next())
+            .is_some_and(|c| c.is_uppercase() || c == '_')
+    })
+}
+
+/// Returns the names of the modifiers applied to `f` that are named like
+/// conditions, e.g. `whenTheCallerIsTheOwner`, in application order.
+fn modifiers(f: &FunctionDefinition) -> Vec<String> {
+    f.attributes
+        .iter()
+        .filter_map(|attribute| match attribute {
+            FunctionAttribute::BaseOrModifier(_, base) => {
+                base.name.identifiers.last().map(|id| id.name.clone())
+            }
+            _ => None,
+        })
+        .filter(|name| condition_keyword(&upper_first_letter(name)).is_some())
+        .collect()
+}
+
+/// Returns the actions in the comments of the body of `f`, along with their
+/// descriptions.
+///
+/// Action comments look like `// It should revert.`, and the indented
+/// comments that follow them are their descriptions.
+fn body_actions(f: &FunctionDefinition, src: &str) -> Vec<Node> {
+    let Some(body) = &f.body else {
+        return vec![];
+    };
+    let loc = body.loc();
+    let mut actions: Vec<Node> = vec![];
+    for line in src[loc.start()..loc.end()].lines() {
+        let Some(comment) = line.trim().strip_prefix("//") else {
+            continue;
+        };
+        let text = comment.trim();
+        if text.is_empty() {
+            continue;
+        }
+
+        let is_action = {
+            let mut words = text.split_whitespace();
+            words.next().is_some_and(|w| w.eq_ignore_ascii_case("it"))
+                && words.next().is_some()
+        };
+        if is_action {
+            actions.push(Node::action(text.to_owned()));
+        } else if comment.starts_with("  ") {
+            if let Some(action) = actions.last_mut() {
+                action.children.push(Node::action(text.to_owned()));
+            }
+        }
+    }
+
+    actions
+}
+
+/// Splits a mangled identifier, e.g. `TheCallerIsTheOwner`, into lowercase
+/// words, e.g. `the caller is the owner`.
+///
+/// Acronyms like `ID` keep their case.
+fn to_words(identifier: &str) -> String {
+    let chars: Vec<char> = identifier.chars().collect();
+    let mut words: Vec<String> = vec![];
+    let mut word = String::new();
+    for (i, &c) in chars.iter().enumerate() {
+        if c == '_' {
+            words.push(std::mem::take(&mut word));
+            continue;
+        }
+
+        let prev = i.checked_sub(1).map(|i| chars[i]);
+        let next = chars.get(i + 1);
+        let starts_word = (c.is_ascii_digit()
+            && prev.is_some_and(char::is_alphabetic)
+            && !word.starts_with(|c: char| c.is_ascii_digit()))
+            || c.is_uppercase()
+                && prev.is_some_and(|p| {
+                    p.is_lowercase()
+                        || p.is_ascii_digit()
+                        || (p.is_uppercase()
+                            && next.is_some_and(|n| n.is_lowercase()))
+                });
+        if starts_word {
+            words.push(std::mem::take(&mut word));
+        }
+        word.push(c);
+    }
+    words.push(word);
+
+    words
+        .into_iter()
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            let is_acronym =
+                word.len() > 1 && word.chars().all(|c| !c.is_lowercase());
+            if is_acronym {
+                word
+            } else {
+                word.to_lowercase()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Strips the markers at the end of a title, e.g. `[fuzz]`.
+fn strip_markers(title: &str) -> &str {
+    let mut title = title.trim_end();
+    while title.ends_with(']') {
+        match title.rfind('[') {
+            Some(idx) => title = title[..idx].trim_end(),
+            None => break,
+        }
+    }
+    title
+}
+
+/// Renders `nodes` as the children of a tree node into `tree`.
+fn render(nodes: &[Node], indent: &str, tree: &mut String) {
+    for (i, node) in nodes.iter().enumerate() {
+        let is_last = i == nodes.len() - 1;
+        let (connector, padding) = if is_last {
+            ("└── ", "    ")
+        } else {
+            ("├── ", "│   ")
+        };
+        tree.push_str(indent);
+        tree.push_str(connector);
+        tree.push_str(&node.title);
+        tree.push('\n');
+        render(&node.children, &format!("{indent}{padding}"), tree);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::to_words;
+
+    #[test]
+    fn splits_mangled_identifiers_into_words() {
+        assert_eq!(
+            to_words("WhenTheCallerIsTheOwner"),
+            "when the caller is the owner"
+        );
+        assert_eq!(to_words("whenTheIDIsZero"), "when the ID is zero");
+        assert_eq!(
+            to_words("GivenTheCallerIs0x1337"),
+            "given the caller is 0x1337"
+        );
+        assert_eq!(
+            to_words("WhenThereIsReentrancy1"),
+            "when there is reentrancy 1"
+        );
+    }
+}
@@ -1,24 +1,50 @@
 //! Defines a Solidity code emitter from a HIR.
 
-use std::result;
+use std::{fmt::Write, path::PathBuf, result};
 
 use bulloak_syntax::utils::sanitize;
 
 use crate::{
     config::Config,
-    constants::INTERNAL_DEFAULT_INDENTATION,
+    constants::{AAA_STEPS, FORGEFMT_DISABLE, GENERATED_MARKER},
     hir::{self, visitor::Visitor, Hir},
+    sol,
 };
 
 /// Solidity code emitter.
 ///
 /// This struct holds the state of the emitter. It is not
 /// tied to a specific HIR.
+#[allow(clippy::struct_excessive_bools)]
 pub struct Emitter {
     /// The indentation level of the emitted code.
     indent: usize,
     /// The Solidity version to be used in the pragma directive.
     solidity_version: String,
+    /// Whether to emit action descriptions as `TODO:` comments.
+    descriptions_as_todos: bool,
+    /// The paths of the files to import after the pragma directive.
+    imports: Vec<String>,
+    /// Whether to omit the pragma directive.
+    skip_pragma: bool,
+    /// The features to enable with `pragma experimental`.
+    experimental_pragmas: Vec<String>,
+    /// The solhint rules to disable after the license line.
+    solhint_disable: Vec<String>,
+    /// The hash of the tree to store after the license line, if any.
+    tree_hash: Option<String>,
+    /// The path of the tree file to link every function to, if any.
+    spec_path: Option<PathBuf>,
+    /// Whether to emit condition titles as comments above modifiers.
+    modifier_comments: bool,
+    /// Whether to emit the comments of tests above their signature.
+    comments_above: bool,
+    /// Whether to mark every generated function with a comment.
+    mark_generated: bool,
+    /// Whether to add an Arrange / Act / Assert skeleton to every test.
+    aaa: bool,
+    /// Whether to keep forge-fmt from reflowing generated comments.
+    forgefmt_disable: bool,
 }
 
 impl Emitter {
@@ -26,8 +52,20 @@ impl Emitter {
     #[must_use]
     pub fn new(cfg: &Config) -> Self {
         Self {
-            indent: INTERNAL_DEFAULT_INDENTATION,
-            solidity_version: cfg.solidity_version.clone(),
+            indent: cfg.indent,
+            solidity_version: cfg.pragma_version(),
+            descriptions_as_todos: cfg.descriptions_as_todos,
+            imports: cfg.imports.clone(),
+            skip_pragma: cfg.skip_pragma,
+            experimental_pragmas: cfg.experimental_pragmas.clone(),
+            solhint_disable: cfg.solhint_disable.clone(),
+            tree_hash: cfg.tree_hash.clone(),
+            spec_path: cfg.spec_path.clone(),
+            modifier_comments: cfg.modifier_comments,
+            comments_above: cfg.comments_above,
+            mark_generated: cfg.mark_generated,
+            aaa: cfg.aaa,
+            forgefmt_disable: cfg.forgefmt_disable,
         }
     }
 
@@ -86,6 +124,7 @@ impl EmitterI {
     ///
     /// This includes:
     /// - The Solidity version pragma.
+    /// - The contract's natspec tags.
     /// - The contract's name.
     fn emit_contract_header(
         &self,
@@ -95,8 +134,19 @@ impl EmitterI {
 
         // It's fine to unwrap here because we check that the filename always
         // has an extension.
+        for tag in &contract.natspec {
+            emitted.push_str("/// ");
+            emitted.push_str(tag);
+            emitted.push('\n');
+        }
         let contract_name = sanitize(&contract.identifier);
-        emitted.push_str(format!("contract {contract_name} {{\n").as_str());
+        emitted.push_str("contract ");
+        emitted.push_str(&contract_name);
+        for (idx, base) in contract.bases.iter().enumerate() {
+            emitted.push_str(if idx == 0 { " is " } else { ", " });
+            emitted.push_str(&base.identifier);
+        }
+        emitted.push_str(" {\n");
 
         emitted
     }
@@ -132,23 +182,40 @@ impl EmitterI {
         let fn_indentation = self.emitter.indent();
         let fn_body_indentation = fn_indentation.repeat(2);
 
+        let params = function
+            .params
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(", ");
         let has_modifiers = function.modifiers.is_some();
         if has_modifiers {
             emitted.push_str(
                 format!(
-                    "{}function {}()\n",
+                    "{}function {}({params})\n",
                     fn_indentation, function.identifier
                 )
                 .as_str(),
             );
             emitted
                 .push_str(format!("{fn_body_indentation}external\n").as_str());
+            if function.payable {
+                emitted.push_str(
+                    format!("{fn_body_indentation}payable\n").as_str(),
+                );
+            }
         } else {
             emitted.push_str(
-                format!("{}function {}()", fn_indentation, function.identifier)
-                    .as_str(),
+                format!(
+                    "{}function {}({params})",
+                    fn_indentation, function.identifier
+                )
+                .as_str(),
             );
             emitted.push_str(" external");
+            if function.payable {
+                emitted.push_str(" payable");
+            }
         }
 
         // Emit the modifiers that should be applied to this function.
@@ -189,16 +256,62 @@ impl Visitor for EmitterI {
     ) -> result::Result<Self::RootOutput, Self::Error> {
         let mut emitted = String::new();
         emitted.push_str("// SPDX-License-Identifier: UNLICENSED\n");
-        emitted.push_str(&format!(
-            "pragma solidity {};\n\n",
-            self.emitter.solidity_version
-        ));
+        if !self.emitter.solhint_disable.is_empty() {
+            emitted.push_str("// ");
+            emitted.push_str(&sol::solhint_directive(
+                &self.emitter.solhint_disable,
+            ));
+            emitted.push('\n');
+        }
+        if let Some(hash) = &self.emitter.tree_hash {
+            emitted.push_str("// ");
+            emitted.push_str(&sol::tree_hash_directive(hash));
+            emitted.push('\n');
+        }
+        if !self.emitter.skip_pragma {
+            let _ = writeln!(
+                emitted,
+                "pragma solidity {};",
+                self.emitter.solidity_version
+            );
+            for feature in &self.emitter.experimental_pragmas {
+                emitted.push_str("pragma experimental ");
+                emitted.push_str(feature);
+                emitted.push_str(";\n");
+            }
+            emitted.push('\n');
+        }
+        let comments: Vec<_> = root
+            .children
+            .iter()
+            .filter_map(|child| match child {
+                Hir::Comment(comment) => Some(comment.lexeme.as_str()),
+                _ => None,
+            })
+            .collect();
+        if !comments.is_empty() {
+            for comment in comments {
+                emitted.push_str("// ");
+                emitted.push_str(comment);
+                emitted.push('\n');
+            }
+            emitted.push('\n');
+        }
+        if !self.emitter.imports.is_empty() {
+            for path in &self.emitter.imports {
+                emitted.push_str("import \"");
+                emitted.push_str(path);
+                emitted.push_str("\";\n");
+            }
+            emitted.push('\n');
+        }
 
         for hir in &root.children {
             let result = match hir {
                 Hir::ContractDefinition(contract) => {
                     self.visit_contract(contract)?
                 }
+                Hir::Comment(_) => continue,
                 _ => unreachable!(),
             };
 
@@ -216,10 +329,20 @@ impl Visitor for EmitterI {
 
         let contract_header = self.emit_contract_header(contract);
         emitted.push_str(&contract_header);
+        for declaration in &contract.types {
+            emitted.push_str(&self.emitter.indent());
+            emitted.push_str(declaration);
+            emitted.push_str("\n\n");
+        }
 
         for hir in &contract.children {
             if let Hir::FunctionDefinition(function) = hir {
                 emitted.push_str(&self.visit_function(function)?);
+            } else if let Hir::Comment(comment) = hir {
+                emitted.push_str(&self.emitter.indent());
+                emitted.push_str("// ");
+                emitted.push_str(&comment.lexeme);
+                emitted.push('\n');
             }
         }
 
@@ -237,20 +360,74 @@ impl Visitor for EmitterI {
     ) -> result::Result<Self::FunctionDefinitionOutput, Self::Error> {
         let mut emitted = String::new();
 
+        if self.emitter.mark_generated {
+            emitted.push_str(&self.emitter.indent());
+            emitted.push_str("// ");
+            emitted.push_str(GENERATED_MARKER);
+            emitted.push('\n');
+        }
+
+        if let (true, Some(title)) =
+            (self.emitter.modifier_comments, &function.condition_title)
+        {
+            emitted.push_str(&self.emitter.indent());
+            emitted.push_str("// ");
+            emitted.push_str(title);
+            emitted.push('\n');
+        }
+
+        if let Some(spec_path) = &self.emitter.spec_path {
+            emitted.push_str(&self.emitter.indent());
+            emitted.push_str("/// ");
+            emitted.push_str(&function.spec_ref(spec_path));
+            emitted.push('\n');
+        }
+
         if matches!(function.ty, hir::FunctionTy::Modifier) {
             emitted.push_str(&self.emit_modifier(&function.identifier));
         } else {
+            let comments_above = self.emitter.comments_above;
+            if let (true, Some(children)) = (comments_above, &function.children)
+            {
+                for child in children {
+                    if let Hir::Comment(comment) = child {
+                        let text =
+                            comment.text(self.emitter.descriptions_as_todos);
+                        emitted.push_str(&self.emitter.indent());
+                        emitted.push_str("// ");
+                        emitted.push_str(&text);
+                        emitted.push('\n');
+                    }
+                }
+            }
+
             let fn_header = self.emit_fn_header(function);
             emitted.push_str(&fn_header);
 
             if let Some(ref children) = function.children {
                 for child in children {
                     if let Hir::Comment(comment) = child {
-                        emitted.push_str(&self.visit_comment(comment)?);
+                        if !comments_above {
+                            emitted.push_str(&self.visit_comment(comment)?);
+                        }
                     } else if let Hir::Statement(statement) = child {
                         emitted.push_str(&self.visit_statement(statement)?);
                     }
                 }
+
+                let has_comments = children
+                    .iter()
+                    .any(|child| matches!(child, Hir::Comment(_)));
+                if self.emitter.aaa && has_comments {
+                    let indentation = self.emitter.indent().repeat(2);
+                    emitted.push('\n');
+                    for step in AAA_STEPS {
+                        emitted.push_str(&indentation);
+                        emitted.push_str("// ");
+                        emitted.push_str(step);
+                        emitted.push('\n');
+                    }
+                }
             }
 
             let indentation = self.emitter.indent();
@@ -266,8 +443,13 @@ impl Visitor for EmitterI {
     ) -> result::Result<Self::CommentOutput, Self::Error> {
         let mut emitted = String::new();
         let indentation = self.emitter.indent().repeat(2);
-        emitted
-            .push_str(format!("{indentation}// {}\n", comment.lexeme).as_str());
+        let text = comment.text(self.emitter.descriptions_as_todos);
+        if self.emitter.forgefmt_disable {
+            emitted.push_str(
+                format!("{indentation}// {FORGEFMT_DISABLE}\n").as_str(),
+            );
+        }
+        emitted.push_str(format!("{indentation}// {text}\n").as_str());
 
         Ok(emitted)
     }
@@ -280,12 +462,92 @@ impl Visitor for EmitterI {
         let indentation = self.emitter.indent().repeat(2);
 
         // Match any supported statement to its string representation
-        match statement.ty {
+        match &statement.ty {
             hir::StatementType::VmSkip => {
                 emitted.push_str(
                     format!("{indentation}vm.skip(true);\n").as_str(),
                 );
             }
+            hir::StatementType::VmLabel(name) => {
+                emitted.push_str(
+                    format!("{indentation}vm.label({name}, \"{name}\");\n")
+                        .as_str(),
+                );
+            }
+            hir::StatementType::VmWarp(timestamp) => {
+                emitted.push_str(
+                    format!("{indentation}vm.warp({timestamp});\n").as_str(),
+                );
+            }
+            hir::StatementType::VmRoll(block) => {
+                emitted.push_str(
+                    format!("{indentation}vm.roll({block});\n").as_str(),
+                );
+            }
+            hir::StatementType::Assembly => {
+                emitted
+                    .push_str(format!("{indentation}assembly {{}}\n").as_str());
+            }
+            hir::StatementType::StartSnapshotGas(name) => {
+                emitted.push_str(
+                    format!("{indentation}vm.startSnapshotGas(\"{name}\");\n")
+                        .as_str(),
+                );
+            }
+            hir::StatementType::StopSnapshotGas => {
+                emitted.push_str(
+                    format!("{indentation}vm.stopSnapshotGas();\n").as_str(),
+                );
+            }
+            hir::StatementType::Raw(text) => {
+                emitted.push_str(format!("{indentation}{text}\n").as_str());
+            }
+            hir::StatementType::TargetContract => {
+                emitted.push_str(
+                    format!("{indentation}targetContract(address(0));\n")
+                        .as_str(),
+                );
+            }
+            hir::StatementType::ExpectRevert(error, args) => {
+                let statement = sol::expect_revert(error, args);
+                emitted
+                    .push_str(format!("{indentation}{statement}\n").as_str());
+            }
+            hir::StatementType::LowLevelCall => {
+                let statement = sol::LOW_LEVEL_CALL;
+                emitted
+                    .push_str(format!("{indentation}{statement}\n").as_str());
+            }
+            hir::StatementType::AssertSuccess => {
+                let statement = sol::ASSERT_SUCCESS;
+                emitted
+                    .push_str(format!("{indentation}{statement}\n").as_str());
+            }
+            hir::StatementType::AssertEq(expected) => {
+                let statement = sol::assert_eq(expected);
+                emitted
+                    .push_str(format!("{indentation}{statement}\n").as_str());
+            }
+            hir::StatementType::ConsoleLog(title) => {
+                let statement = sol::console_log(title);
+                emitted
+                    .push_str(format!("{indentation}{statement}\n").as_str());
+            }
+            hir::StatementType::ExpectCall(target) => {
+                let statement = sol::expect_call(target);
+                emitted
+                    .push_str(format!("{indentation}{statement}\n").as_str());
+            }
+            hir::StatementType::ExpectEmit => {
+                let statement = sol::EXPECT_EMIT;
+                emitted
+                    .push_str(format!("{indentation}{statement}\n").as_str());
+            }
+            hir::StatementType::Emit(event) => {
+                let statement = sol::emit_event(event);
+                emitted
+                    .push_str(format!("{indentation}{statement}\n").as_str());
+            }
         }
 
         Ok(emitted)
@@ -461,6 +723,28 @@ contract FileTest {
         Ok(())
     }
 
+    #[test]
+    fn configurable_indentation() -> anyhow::Result<()> {
+        let file_contents = "FileTest\n└── when something bad happens\n   └── it should not revert";
+        let cfg = Config { indent: 4, ..Config::default() };
+        let hir = translate(file_contents, &cfg)?;
+        let emitted = emitter::Emitter::new(&cfg).emit(&hir);
+
+        assert_eq!(
+            emitted,
+            r"// SPDX-License-Identifier: UNLICENSED
+pragma solidity 0.8.0;
+
+contract FileTest {
+    function test_WhenSomethingBadHappens() external {
+        // it should not revert
+    }
+}"
+        );
+
+        Ok(())
+    }
+
     #[test]
     fn with_vm_skip() -> anyhow::Result<()> {
         let file_contents = "FileTest\n└── when something bad happens\n   └── it should not revert";
@@ -485,6 +769,177 @@ contract FileTest {
         Ok(())
     }
 
+    #[test]
+    fn with_forgefmt_disable() -> anyhow::Result<()> {
+        let file_contents = "FileTest\n└── when something bad happens\n   └── it should not revert";
+        let cfg = Config { forgefmt_disable: true, ..Config::default() };
+        let hir = translate(file_contents, &cfg)?;
+        let emitted = emitter::Emitter::new(&cfg).emit(&hir);
+
+        assert_eq!(
+            emitted,
+            r"// SPDX-License-Identifier: UNLICENSED
+pragma solidity 0.8.0;
+
+contract FileTest {
+  function test_WhenSomethingBadHappens() external {
+    // forgefmt: disable-next-line
+    // it should not revert
+  }
+}"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn with_aaa_skeleton() -> anyhow::Result<()> {
+        let file_contents = "FileTest\n└── when something bad happens\n   └── it should not revert";
+        let cfg = Config { aaa: true, ..Config::default() };
+        let hir = translate(file_contents, &cfg)?;
+        let emitted = emitter::Emitter::new(&cfg).emit(&hir);
+
+        assert_eq!(
+            emitted,
+            r"// SPDX-License-Identifier: UNLICENSED
+pragma solidity 0.8.0;
+
+contract FileTest {
+  function test_WhenSomethingBadHappens() external {
+    // it should not revert
+
+    // Arrange
+    // Act
+    // Assert
+  }
+}"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn with_experimental_pragmas() -> anyhow::Result<()> {
+        let file_contents =
+            "FileTest\n└── when something bad happens\n   └── it should revert";
+        let cfg = Config {
+            experimental_pragmas: vec!["SMTChecker".to_owned()],
+            ..Config::default()
+        };
+        let hir = translate(file_contents, &cfg)?;
+        let emitted = emitter::Emitter::new(&cfg).emit(&hir);
+
+        assert!(emitted.starts_with(
+            r"// SPDX-License-Identifier: UNLICENSED
+pragma solidity 0.8.0;
+pragma experimental SMTChecker;
+
+contract FileTest {"
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn with_mark_generated() -> anyhow::Result<()> {
+        let file_contents = String::from(
+            r"FileTest
+└── when something bad happens
+    └── when stuff happens
+        └── it should revert",
+        );
+        let cfg = Config { mark_generated: true, ..Config::default() };
+        let hir = translate(&file_contents, &cfg)?;
+        let emitted = emitter::Emitter::new(&cfg).emit(&hir);
+
+        assert_eq!(
+            emitted,
+            r"// SPDX-License-Identifier: UNLICENSED
+pragma solidity 0.8.0;
+
+contract FileTest {
+  // @bulloak:generated
+  modifier whenSomethingBadHappens() {
+    _;
+  }
+
+  // @bulloak:generated
+  function test_RevertWhen_StuffHappens()
+    external
+    whenSomethingBadHappens
+  {
+    // it should revert
+  }
+}"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn with_comments_above() -> anyhow::Result<()> {
+        let file_contents =
+            "FileTest\n└── when something bad happens\n   └── it should revert";
+        let cfg = Config { comments_above: true, ..Config::default() };
+        let hir = translate(file_contents, &cfg)?;
+        let emitted = emitter::Emitter::new(&cfg).emit(&hir);
+
+        assert!(emitted.ends_with(
+            r"contract FileTest {
+  // it should revert
+  function test_RevertWhen_SomethingBadHappens() external {
+  }
+}"
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn with_tree_hash() -> anyhow::Result<()> {
+        let file_contents =
+            "FileTest\n└── when something bad happens\n   └── it should revert";
+        let cfg = Config {
+            tree_hash: Some("0123456789abcdef".to_owned()),
+            ..Config::default()
+        };
+        let hir = translate(file_contents, &cfg)?;
+        let emitted = emitter::Emitter::new(&cfg).emit(&hir);
+
+        assert!(emitted.starts_with(
+            r"// SPDX-License-Identifier: UNLICENSED
+// @bulloak:tree-hash 0123456789abcdef
+pragma solidity 0.8.0;"
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn with_solhint_disable() -> anyhow::Result<()> {
+        let file_contents =
+            "FileTest\n└── when something bad happens\n   └── it should revert";
+        let cfg = Config {
+            solhint_disable: vec![
+                "no-empty-blocks".to_owned(),
+                "func-name-mixedcase".to_owned(),
+            ],
+            ..Config::default()
+        };
+        let hir = translate(file_contents, &cfg)?;
+        let emitted = emitter::Emitter::new(&cfg).emit(&hir);
+
+        assert!(emitted.starts_with(
+            r"// SPDX-License-Identifier: UNLICENSED
+// solhint-disable no-empty-blocks, func-name-mixedcase
+pragma solidity 0.8.0;
+
+contract FileTest {"
+        ));
+
+        Ok(())
+    }
+
     #[test]
     #[should_panic]
     fn with_vm_skip_top_level_statement() {
@@ -711,6 +1166,39 @@ contract DescriptionsTest {
         Ok(())
     }
 
+    #[test]
+    fn tree_comments() -> anyhow::Result<()> {
+        let file_contents = String::from(
+            r"// The vault suite.
+CommentsTest
+├── when the amount is zero // See issue #42.
+│   └── it should revert // With `ZeroAmount`.
+└── it should deposit",
+        );
+
+        assert_eq!(
+            &scaffold(&file_contents)?,
+            r"// SPDX-License-Identifier: UNLICENSED
+pragma solidity 0.8.0;
+
+// The vault suite.
+
+contract CommentsTest {
+  function test_RevertWhen_TheAmountIsZero() external {
+    // See issue #42.
+    // it should revert
+    // With `ZeroAmount`.
+  }
+
+  function test_ShouldDeposit() external {
+    // it should deposit
+  }
+}"
+        );
+
+        Ok(())
+    }
+
     #[test]
     fn deep_tree() -> anyhow::Result<()> {
         let file_contents = String::from(
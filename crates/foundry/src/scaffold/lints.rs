@@ -0,0 +1,546 @@
+//! Defines lints that warn about trees that generate questionable Solidity.
+//!
+//! Unlike errors, lints never prevent a tree from being scaffolded. Most of
+//! them are opt-in through the configuration.
+
+use std::{collections::HashSet, fmt, path::Path};
+
+use bulloak_syntax::{utils::sanitize, Ast, Diagnostic, Position, Span};
+use owo_colors::OwoColorize;
+use thiserror::Error;
+
+use crate::{
+    check::utils::file_stem,
+    config::Config,
+    hir::{self, Hir},
+};
+
+/// A warning about a tree.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Lint {
+    /// The kind of lint.
+    pub kind: LintKind,
+    /// The span of the tree element that triggered this lint.
+    pub span: Span,
+}
+
+impl Lint {
+    /// Create a new lint.
+    #[must_use]
+    pub const fn new(kind: LintKind, span: Span) -> Self {
+        Self { kind, span }
+    }
+}
+
+/// The type of a lint.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[non_exhaustive]
+pub enum LintKind {
+    /// A generated function name is longer than the configured limit.
+    ///
+    /// (function name, limit)
+    #[error(
+        "function name \"{0}\" is {} characters long, exceeding the limit of {1}",
+        .0.len()
+    )]
+    NameTooLong(String, usize),
+    /// Two roots of a tree file have the same identifier, so their branches
+    /// are merged into the same set of tests.
+    ///
+    /// (root identifier)
+    #[error("the root \"{0}\" appears more than once")]
+    DuplicateRoot(String),
+    /// An action starts with a spelling of the `it` keyword other than `it`
+    /// or `It`.
+    ///
+    /// (keyword)
+    #[error("action starts with \"{0}\" instead of \"it\"")]
+    ItKeyword(String),
+    /// A condition or action title has more words than the configured
+    /// limit.
+    ///
+    /// (title, limit)
+    #[error(
+        "title \"{0}\" has {} words, exceeding the limit of {1}",
+        .0.split_whitespace().count()
+    )]
+    TitleTooLong(String, usize),
+    /// A generated contract has no functions, e.g. because every test of
+    /// the tree is inherited from a base tree.
+    ///
+    /// (contract name)
+    #[error("contract \"{0}\" has no functions")]
+    EmptyContract(String),
+    /// A generated contract isn't named like the Solidity file it's written
+    /// to.
+    ///
+    /// (contract name, file stem)
+    #[error("contract \"{0}\" doesn't match the name of its file \"{1}\"")]
+    FileNameMismatch(String, String),
+    /// An action repeats the title of an earlier action of the same test.
+    ///
+    /// (action title)
+    #[error("action \"{0}\" appears more than once in the same test")]
+    DuplicateAction(String),
+}
+
+impl LintKind {
+    /// Optionally returns a help text to be used when displaying the lint.
+    #[must_use]
+    pub const fn help(&self) -> Option<&'static str> {
+        match self {
+            LintKind::NameTooLong(_, _) => {
+                Some("consider shortening the title of the branch")
+            }
+            LintKind::ItKeyword(_) => {
+                Some("start the action with `it` or `It`")
+            }
+            LintKind::TitleTooLong(_, _) => {
+                Some("consider moving some details to an action description")
+            }
+            LintKind::DuplicateRoot(_) => Some(
+                "rename one of the roots, or merge their branches if this is intentional",
+            ),
+            LintKind::EmptyContract(_) => {
+                Some("consider adding branches to the tree")
+            }
+            LintKind::FileNameMismatch(_, _) => {
+                Some("consider renaming the root or the tree file")
+            }
+            LintKind::DuplicateAction(_) => {
+                Some("remove the repeated action, or pass `--dedup-actions`")
+            }
+        }
+    }
+}
+
+impl LintKind {
+    /// Returns the stable code of this kind of lint.
+    #[must_use]
+    pub const fn code(&self) -> &'static str {
+        match self {
+            LintKind::NameTooLong(_, _) => "B401",
+            LintKind::DuplicateRoot(_) => "B402",
+            LintKind::ItKeyword(_) => "B403",
+            LintKind::TitleTooLong(_, _) => "B404",
+            LintKind::EmptyContract(_) => "B405",
+            LintKind::FileNameMismatch(_, _) => "B406",
+            LintKind::DuplicateAction(_) => "B407",
+        }
+    }
+}
+
+impl From<&Lint> for Diagnostic {
+    fn from(lint: &Lint) -> Self {
+        Diagnostic::warning(lint.kind.code(), lint.kind.to_string(), lint.span)
+    }
+}
+
+impl fmt::Display for Lint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}: {}", "warn".yellow(), self.kind)?;
+        if let Some(help_text) = self.kind.help() {
+            writeln!(f, "     {} help: {}", "=".blue(), help_text)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Runs the lints enabled in `cfg` over the contents of a `.tree` file.
+///
+/// Duplicate roots and empty contracts are always reported. Lints are sorted by
+/// the position of the element that triggered them.
+///
+/// # Errors
+///
+/// Returns an error if the tree fails to parse.
+pub fn lint(text: &str, cfg: &Config) -> anyhow::Result<Vec<Lint>> {
+    let mut lints = vec![];
+    let asts = bulloak_syntax::parse_with_aliases(text, &cfg.keyword_aliases)?;
+    check_duplicate_roots(&asts, &mut lints);
+    if cfg.lint_it_keyword {
+        for (line, ast) in &asts {
+            check_it_keyword(ast, line - 1, &mut lints);
+        }
+    }
+    if cfg.lint_duplicate_actions {
+        for (line, ast) in &asts {
+            check_duplicate_actions(ast, line - 1, &mut lints);
+        }
+    }
+    if let Some(max) = cfg.max_title_words {
+        for (line, ast) in &asts {
+            check_title_words(ast, max, line - 1, &mut lints);
+        }
+    }
+
+    let hir = hir::translate(text, cfg)?;
+    if let Some(contract) = hir.find_contract() {
+        let line = asts.first().map_or(1, |(line, _)| *line);
+        check_empty_contract(contract, line, &mut lints);
+        // With `root_as_path`, files are named after their contract.
+        if let (true, false, Some(tree)) =
+            (cfg.check_file_name, cfg.root_as_path, &cfg.tree_path)
+        {
+            check_file_name(contract, tree, line, &mut lints);
+        }
+        let functions =
+            contract.children.iter().filter_map(|child| match child {
+                Hir::FunctionDefinition(function)
+                    if !(cfg.skip_modifiers && function.is_modifier()) =>
+                {
+                    Some(function)
+                }
+                _ => None,
+            });
+
+        for function in functions {
+            if let Some(max) = cfg.max_name_length {
+                check_name_length(function, max, &mut lints);
+            }
+        }
+    }
+
+    lints.sort_by_key(|lint| lint.span);
+    Ok(lints)
+}
+
+/// Checks that no two roots of a tree file have the same identifier.
+///
+/// The lint points to the line of every repeated root.
+fn check_duplicate_roots(asts: &[(usize, Ast)], lints: &mut Vec<Lint>) {
+    let mut roots = HashSet::new();
+    for (line, ast) in asts {
+        let Ast::Root(root) = ast else { continue };
+        if !roots.insert(&root.contract_name) {
+            let position = Position::new(0, *line, 1);
+            let kind = LintKind::DuplicateRoot(root.contract_name.clone());
+            lints.push(Lint::new(kind, Span::new(position, position)));
+        }
+    }
+}
+
+/// Checks that every action in `ast` starts with `it` or `It`.
+///
+/// The parser accepts the keyword in any case, e.g. `IT`, so this only
+/// catches the spellings that break the convention. `offset` is the number
+/// of lines above the tree in its file.
+fn check_it_keyword(ast: &Ast, offset: usize, lints: &mut Vec<Lint>) {
+    match ast {
+        Ast::Root(root) => root
+            .children
+            .iter()
+            .for_each(|child| check_it_keyword(child, offset, lints)),
+        Ast::Condition(condition) => condition
+            .children
+            .iter()
+            .for_each(|child| check_it_keyword(child, offset, lints)),
+        Ast::Action(action) => {
+            let keyword =
+                action.title.split_whitespace().next().unwrap_or_default();
+            if !matches!(keyword, "it" | "It") {
+                let mut span = action.span;
+                span.start.line += offset;
+                span.end.line += offset;
+                let kind = LintKind::ItKeyword(keyword.to_owned());
+                lints.push(Lint::new(kind, span));
+            }
+        }
+        Ast::ActionDescription(_) => {}
+    }
+}
+
+/// Checks that no two actions of the same test in `ast` have the same
+/// title.
+///
+/// The actions directly under a condition share a test, while the actions
+/// directly under a root get one test each. `offset` is the number of lines
+/// above the tree in its file.
+fn check_duplicate_actions(ast: &Ast, offset: usize, lints: &mut Vec<Lint>) {
+    let children = match ast {
+        Ast::Root(root) => &root.children,
+        Ast::Condition(condition) => &condition.children,
+        Ast::Action(_) | Ast::ActionDescription(_) => return,
+    };
+
+    let mut titles = HashSet::new();
+    for child in children {
+        match child {
+            Ast::Action(action)
+                if matches!(ast, Ast::Condition(_))
+                    && !titles.insert(&action.title) =>
+            {
+                let mut span = action.span;
+                span.start.line += offset;
+                span.end.line += offset;
+                let kind = LintKind::DuplicateAction(action.title.clone());
+                lints.push(Lint::new(kind, span));
+            }
+            _ => check_duplicate_actions(child, offset, lints),
+        }
+    }
+}
+
+/// Checks that no condition or action title in `ast` has more than `max`
+/// words.
+///
+/// `offset` is the number of lines above the tree in its file.
+fn check_title_words(
+    ast: &Ast,
+    max: usize,
+    offset: usize,
+    lints: &mut Vec<Lint>,
+) {
+    let (title, span, children) = match ast {
+        Ast::Root(root) => ("", root.span, root.children.as_slice()),
+        Ast::Condition(condition) => (
+            condition.title.as_str(),
+            condition.span,
+            condition.children.as_slice(),
+        ),
+        Ast::Action(action) => (action.title.as_str(), action.span, &[][..]),
+        Ast::ActionDescription(_) => return,
+    };
+
+    if title.split_whitespace().count() > max {
+        let mut span = span;
+        span.start.line += offset;
+        span.end.line += offset;
+        let kind = LintKind::TitleTooLong(title.to_owned(), max);
+        lints.push(Lint::new(kind, span));
+    }
+    for child in children {
+        check_title_words(child, max, offset, lints);
+    }
+}
+
+/// Checks that a generated contract has at least one function.
+///
+/// The lint points to `line`, the line of the first root of the tree.
+fn check_empty_contract(
+    contract: &hir::ContractDefinition,
+    line: usize,
+    lints: &mut Vec<Lint>,
+) {
+    let has_functions = contract
+        .children
+        .iter()
+        .any(|child| matches!(child, Hir::FunctionDefinition(_)));
+    if !has_functions {
+        let position = Position::new(0, line, 1);
+        let kind = LintKind::EmptyContract(contract.identifier.clone());
+        lints.push(Lint::new(kind, Span::new(position, position)));
+    }
+}
+
+/// Checks that a generated contract is named like the Solidity file of
+/// `tree`, which shares its name.
+///
+/// The lint points to `line`, the line of the first root of the tree.
+fn check_file_name(
+    contract: &hir::ContractDefinition,
+    tree: &Path,
+    line: usize,
+    lints: &mut Vec<Lint>,
+) {
+    let contract_name = sanitize(&contract.identifier);
+    let Some(stem) = file_stem(tree) else { return };
+    if stem != contract_name {
+        let position = Position::new(0, line, 1);
+        let kind = LintKind::FileNameMismatch(contract_name, stem.to_owned());
+        lints.push(Lint::new(kind, Span::new(position, position)));
+    }
+}
+
+/// Checks that the name of a generated function doesn't exceed `max`
+/// characters.
+fn check_name_length(
+    function: &hir::FunctionDefinition,
+    max: usize,
+    lints: &mut Vec<Lint>,
+) {
+    if function.identifier.len() > max {
+        let kind = LintKind::NameTooLong(function.identifier.clone(), max);
+        lints.push(Lint::new(kind, function.span));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bulloak_syntax::{Diagnostic, Severity};
+    use pretty_assertions::assert_eq;
+
+    use super::{lint, LintKind};
+    use crate::config::Config;
+
+    #[test]
+    fn lints_nothing_by_default() {
+        let tree =
+            "Foo\n└── When something very long happens\n    └── It works.";
+        assert!(lint(tree, &Config::default()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn lints_long_function_names() {
+        let tree = r"Foo
+├── When short
+│   └── It works.
+└── When something very long happens
+    └── It works.";
+        let cfg = Config { max_name_length: Some(20), ..Config::default() };
+        let lints = lint(tree, &cfg).unwrap();
+
+        let kinds: Vec<_> = lints.into_iter().map(|lint| lint.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![LintKind::NameTooLong(
+                "test_WhenSomethingVeryLongHappens".to_owned(),
+                20
+            )]
+        );
+    }
+
+    #[test]
+    fn lints_long_modifier_names() {
+        let tree = r"Foo
+└── When something very long happens
+    ├── When short
+    │   └── It works.
+    └── When shorter
+        └── It works.";
+        let cfg = Config { max_name_length: Some(20), ..Config::default() };
+        let lints = lint(tree, &cfg).unwrap();
+
+        assert_eq!(lints.len(), 1);
+        assert_eq!(
+            lints[0].kind,
+            LintKind::NameTooLong(
+                "whenSomethingVeryLongHappens".to_owned(),
+                20
+            )
+        );
+        assert_eq!(lints[0].span.start.line, 2);
+    }
+
+    #[test]
+    fn lints_duplicate_roots() {
+        let tree = r"Foo::bar
+└── When something happens
+    └── It works.
+
+Foo::baz
+└── When something happens
+    └── It works.
+
+Foo::bar
+└── When something else happens
+    └── It works.";
+        let lints = lint(tree, &Config::default()).unwrap();
+
+        assert_eq!(lints.len(), 1);
+        assert_eq!(
+            lints[0].kind,
+            LintKind::DuplicateRoot("Foo::bar".to_owned())
+        );
+        assert_eq!(lints[0].span.start.line, 9);
+    }
+
+    #[test]
+    fn lints_actions_without_the_it_keyword() {
+        let tree = r"Foo
+├── It works.
+└── When something happens
+    ├── it works.
+    └── IT works.";
+        assert!(lint(tree, &Config::default()).unwrap().is_empty());
+
+        let cfg = Config { lint_it_keyword: true, ..Config::default() };
+        let lints = lint(tree, &cfg).unwrap();
+
+        assert_eq!(lints.len(), 1);
+        assert_eq!(lints[0].kind, LintKind::ItKeyword("IT".to_owned()));
+        assert_eq!(lints[0].span.start.line, 5);
+    }
+
+    #[test]
+    fn lints_duplicate_actions() {
+        let tree = r"Foo
+├── it works
+└── when something happens
+    ├── it works
+    ├── it reverts
+    └── it works";
+        assert!(lint(tree, &Config::default()).unwrap().is_empty());
+
+        let cfg = Config { lint_duplicate_actions: true, ..Config::default() };
+        let lints = lint(tree, &cfg).unwrap();
+
+        assert_eq!(lints.len(), 1);
+        assert_eq!(
+            lints[0].kind,
+            LintKind::DuplicateAction("it works".to_owned())
+        );
+        assert_eq!(lints[0].span.start.line, 6);
+    }
+
+    #[test]
+    fn lints_titles_with_too_many_words() {
+        let tree = r"Foo
+├── When short
+│   └── It works.
+└── When something very long happens
+    └── It works as expected.";
+        let cfg = Config { max_title_words: Some(3), ..Config::default() };
+        let lints = lint(tree, &cfg).unwrap();
+
+        assert_eq!(lints.len(), 2);
+        assert_eq!(
+            lints[0].kind,
+            LintKind::TitleTooLong(
+                "When something very long happens".to_owned(),
+                3
+            )
+        );
+        assert_eq!(lints[0].span.start.line, 4);
+        assert_eq!(lints[1].span.start.line, 5);
+        assert_eq!(
+            lints[0].kind.to_string(),
+            "title \"When something very long happens\" has 5 words, \
+             exceeding the limit of 3"
+        );
+    }
+
+    #[test]
+    fn lints_contracts_named_unlike_their_file() {
+        let tree = "Foo\n└── It works.";
+        let cfg = Config {
+            check_file_name: true,
+            tree_path: Some("test/Bar.tree".into()),
+            ..Config::default()
+        };
+        let lints = lint(tree, &cfg).unwrap();
+
+        assert_eq!(
+            lints[0].kind,
+            LintKind::FileNameMismatch("Foo".to_owned(), "Bar".to_owned())
+        );
+        assert_eq!(lints[0].kind.code(), "B406");
+
+        let cfg = Config { tree_path: Some("test/Foo.tree".into()), ..cfg };
+        assert!(lint(tree, &cfg).unwrap().is_empty());
+    }
+
+    #[test]
+    fn converts_lints_to_diagnostics() {
+        let tree =
+            "Foo\n└── When something very long happens\n    └── It works.";
+        let cfg = Config { max_name_length: Some(20), ..Config::default() };
+        let lints = lint(tree, &cfg).unwrap();
+
+        let diagnostic = Diagnostic::from(&lints[0]);
+        assert_eq!(diagnostic.severity, Severity::Warning);
+        assert_eq!(diagnostic.code, "B401");
+        assert_eq!(diagnostic.span, lints[0].span);
+    }
+}
@@ -2,24 +2,152 @@
 //!
 //! This command scaffolds a Solidity file from a spec `.tree` file.
 
+use std::{collections::HashMap, ops::Range};
+
+use anyhow::anyhow;
+use bulloak_syntax::Span;
 use forge_fmt::fmt;
+use solang_parser::pt::{CodeLocation, ContractPart};
 
-use crate::{config::Config, hir::translate, sol};
+use crate::{
+    config::Config,
+    hir::{translate, Hir},
+    sol,
+};
 
 pub mod emitter;
+pub mod lints;
 pub mod modifiers;
 
+/// A map from byte ranges of the generated Solidity code to the spans of the
+/// tree they come from.
+pub type SourceMap = Vec<(Range<usize>, Span)>;
+
 /// Generates Solidity code from a `.tree` file.
 ///
 /// This function takes the content of a `.tree` file and a configuration,
 /// translates it to an intermediate representation, then to Solidity, and
 /// finally formats the resulting Solidity code.
+///
+/// # Errors
+///
+/// Returns an error if the tree fails to parse or to translate, or if the
+/// resulting code can't be formatted, e.g. because a statement passed through
+/// the configuration isn't valid Solidity.
 pub fn scaffold(text: &str, cfg: &Config) -> anyhow::Result<String> {
     let hir = translate(text, cfg)?;
-    let pt = sol::Translator::new(cfg).translate(&hir);
-    let source = sol::Formatter::new().emit(pt);
-    let formatted =
-        fmt(&source).expect("should format the emitted solidity code");
+    emit(&hir, cfg)
+}
+
+/// Generates Solidity code from a `.tree` file, along with a map from the
+/// generated code back to the tree.
+///
+/// Each entry of the map pairs the byte range of a generated function in the
+/// returned code, from its `function` or `modifier` keyword to the end of its
+/// body, with the span of the branch it comes from. Functions that don't come
+/// from any branch, such as `receive`, are left out.
+///
+/// # Errors
+///
+/// Returns an error if the tree fails to parse or to translate, or if the
+/// resulting code can't be formatted or parsed back.
+pub fn scaffold_with_sourcemap(
+    text: &str,
+    cfg: &Config,
+) -> anyhow::Result<(String, SourceMap)> {
+    let hir = translate(text, cfg)?;
+    let formatted = emit(&hir, cfg)?;
+
+    let spans: HashMap<&str, Span> = hir
+        .find_contract()
+        .map(|contract| {
+            contract
+                .children
+                .iter()
+                .filter_map(|child| match child {
+                    Hir::FunctionDefinition(f) => {
+                        Some((f.identifier.as_str(), f.span))
+                    }
+                    _ => None,
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let parsed = forge_fmt::parse(&formatted)
+        .map_err(|_| anyhow!("failed to parse the emitted Solidity code"))?;
+    let mut sourcemap = vec![];
+    if let Some(contract) = sol::find_contract(&parsed.pt) {
+        for part in &contract.parts {
+            let ContractPart::FunctionDefinition(f) = part else { continue };
+            let Some(span) =
+                f.name.as_ref().and_then(|name| spans.get(name.name.as_str()))
+            else {
+                continue;
+            };
+            let end =
+                f.body.as_ref().map_or(f.loc.end(), |body| body.loc().end());
+            sourcemap.push((f.loc.start()..end, *span));
+        }
+    }
+
+    Ok((formatted, sourcemap))
+}
+
+/// Emits the formatted Solidity code of a translated tree.
+fn emit(hir: &Hir, cfg: &Config) -> anyhow::Result<String> {
+    let sentinel = sol::sentinel(hir);
+    let pt = sol::Translator::new(cfg).with_sentinel(&sentinel).translate(hir);
+    let source = sol::Formatter::new().with_sentinel(&sentinel).emit(pt);
+    let formatted = fmt(&source).map_err(|err| {
+        anyhow!("failed to format the emitted Solidity code: {err}")
+    })?;
+    Ok(sol::restore_directives(&formatted, &sentinel))
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::{scaffold, scaffold_with_sourcemap};
+    use crate::config::Config;
+
+    #[test]
+    fn maps_functions_back_to_the_tree() {
+        let tree = r"Foo
+├── When something happens
+│   └── It works.
+└── It should never revert.";
+        let cfg = Config::default();
+        let (source, sourcemap) = scaffold_with_sourcemap(tree, &cfg).unwrap();
+        assert_eq!(source, scaffold(tree, &cfg).unwrap());
+
+        let mapped: Vec<_> = sourcemap
+            .iter()
+            .map(|(range, span)| {
+                let code = &source[range.clone()];
+                (code.lines().next().unwrap(), span.start.line)
+            })
+            .collect();
+        assert_eq!(
+            mapped,
+            vec![
+                ("function test_WhenSomethingHappens() external {", 2),
+                ("function test_ShouldNeverRevert() external {", 4),
+            ]
+        );
+        assert!(sourcemap
+            .iter()
+            .all(|(range, _)| source[range.clone()].ends_with('}')));
+    }
 
-    Ok(formatted)
+    #[test]
+    fn fails_to_scaffold_invalid_statements() {
+        let tree = "Foo\n└── It works.";
+        let cfg = Config {
+            default_body_statement: Some("foo(".to_owned()),
+            ..Config::default()
+        };
+        assert!(scaffold(tree, &cfg).is_err());
+    }
 }
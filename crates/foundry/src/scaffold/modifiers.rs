@@ -9,6 +9,8 @@ use bulloak_syntax::{
 };
 use indexmap::IndexMap;
 
+use crate::hir::expand_value_set;
+
 /// AST visitor that discovers modifiers.
 ///
 /// Modifiers are discovered by visiting the AST and collecting all condition
@@ -19,6 +21,9 @@ use indexmap::IndexMap;
 /// for the later phases of the compiler. `IndexMap` was chosen since preserving
 /// the order of insertion to match the order of the modifiers in the source
 /// tree is helpful and the performance trade-off is negligible.
+///
+/// Conditions with a value set, e.g. `given status in {Active, Paused}`,
+/// produce a modifier per value.
 #[derive(Clone, Default)]
 pub struct ModifierDiscoverer {
     modifiers: IndexMap<String, String>,
@@ -69,10 +74,12 @@ impl Visitor for ModifierDiscoverer {
         &mut self,
         condition: &Condition,
     ) -> anyhow::Result<Self::Output, Self::Error> {
-        self.modifiers.insert(
-            condition.title.clone(),
-            lower_first_letter(&to_pascal_case(&condition.title)),
-        );
+        let titles = expand_value_set(&condition.title)
+            .unwrap_or_else(|| vec![condition.title.clone()]);
+        for title in titles {
+            let modifier = lower_first_letter(&to_pascal_case(&title));
+            self.modifiers.insert(title, modifier);
+        }
 
         for condition in &condition.children {
             if let Ast::Condition(condition) = condition {
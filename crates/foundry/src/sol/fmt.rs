@@ -1,14 +1,16 @@
+use std::sync::LazyLock;
+
 use bulloak_syntax::utils::sanitize;
-use once_cell::sync::Lazy;
 use regex::Regex;
 use solang_parser::pt::{
     Base, ContractDefinition, ContractPart, ErrorDefinition, ErrorParameter,
     EventDefinition, EventParameter, Expression, FunctionAttribute,
     FunctionDefinition, Parameter, SourceUnit, SourceUnitPart, Statement,
-    StructDefinition, TypeDefinition, VariableAttribute,
+    StructDefinition, TypeDefinition, VariableAttribute, VariableDefinition,
 };
 
-use super::visitor::Visitor;
+use super::{disguised_name, visitor::Visitor, SENTINEL};
+use crate::constants::FORGEFMT_DISABLE;
 
 trait Identified {
     fn name(&self) -> String;
@@ -20,11 +22,34 @@ impl Identified for Base {
     }
 }
 
-pub(crate) struct Formatter;
+/// Matches the disguised variables of the default sentinel, which is the one
+/// almost every tree uses.
+static RE_BULLOAK_COMMENT: LazyLock<Regex> =
+    LazyLock::new(|| disguised_variables_regex(SENTINEL));
+
+pub(crate) struct Formatter {
+    /// The prefix of the disguised variables to turn back into comments.
+    sentinel: String,
+    /// Matches the disguised variables of `sentinel`.
+    re_comment: Regex,
+}
 
 impl Formatter {
     pub(crate) fn new() -> Self {
-        Formatter {}
+        Formatter {
+            sentinel: SENTINEL.to_owned(),
+            re_comment: RE_BULLOAK_COMMENT.clone(),
+        }
+    }
+
+    /// Sets the prefix of the disguised variables, which must match the one
+    /// the translator used.
+    pub(crate) fn with_sentinel(mut self, sentinel: &str) -> Self {
+        if sentinel != self.sentinel {
+            sentinel.clone_into(&mut self.sentinel);
+            self.re_comment = disguised_variables_regex(sentinel);
+        }
+        self
     }
 
     pub(crate) fn emit(&mut self, mut pt: SourceUnit) -> String {
@@ -32,7 +57,7 @@ impl Formatter {
             .visit_source_unit(&mut pt)
             .expect("should emit the solidity source");
 
-        cleanup_comments(&source)
+        cleanup_comments(&source, &self.re_comment, &self.sentinel)
     }
 }
 
@@ -44,12 +69,34 @@ impl Visitor for Formatter {
         &mut self,
         source_unit: &mut SourceUnit,
     ) -> Result<Self::Output, Self::Error> {
-        let mut parts = vec![];
+        let mut source = String::from("// SPDX-License-Identifier: UNLICENSED");
+        let mut prev_is_natspec = false;
+        let mut prev_is_comment = false;
+        let mut is_header = true;
         for p in &mut source_unit.0 {
-            parts.push(self.visit_source_unit_part(p)?);
+            // natspec tags must be separated from the previous part by an
+            // empty line, or forge-fmt attaches them to that part instead of
+            // to the contract.
+            let is_natspec = is_natspec(p, &self.sentinel);
+            if is_natspec && !prev_is_natspec {
+                source.push('\n');
+            }
+            prev_is_natspec = is_natspec;
+
+            // Comments below the header, i.e. the comments of the root of
+            // the tree, are set apart from the parts around them.
+            let is_comment = is_comment(p, &self.sentinel);
+            if !is_header && is_comment != prev_is_comment && !is_natspec {
+                source.push('\n');
+            }
+            prev_is_comment = is_comment;
+            is_header &= is_comment;
+
+            source.push('\n');
+            source.push_str(&self.visit_source_unit_part(p)?);
         }
 
-        Ok(parts.join("\n"))
+        Ok(source)
     }
 
     fn visit_source_unit_part(
@@ -57,12 +104,6 @@ impl Visitor for Formatter {
         part: &mut SourceUnitPart,
     ) -> Result<Self::Output, Self::Error> {
         match part {
-            SourceUnitPart::PragmaDirective(_, _, _) => {
-                let header = "// SPDX-License-Identifier: UNLICENSED\n";
-                let header = format!("{header}{part}");
-
-                Ok(header)
-            }
             SourceUnitPart::ContractDefinition(inner) => {
                 self.visit_contract(inner)
             }
@@ -100,12 +141,23 @@ impl Visitor for Formatter {
             result.push(' ');
         }
 
-        let mut parts = vec![];
-        for p in &mut contract.parts {
-            parts.push(self.visit_contract_part(p)?);
+        // Break the line so that a leading comment isn't attached to `{`.
+        result.push_str("{\n");
+        let mut prev_is_doc = false;
+        for (idx, p) in contract.parts.iter_mut().enumerate() {
+            // Keep natspec tags and comments right above the part they
+            // document.
+            if idx > 0 {
+                result.push_str(if prev_is_doc { "\n" } else { "\n\n" });
+            }
+            prev_is_doc = matches!(
+                p,
+                ContractPart::VariableDefinition(definition)
+                    if is_natspec_definition(definition, &self.sentinel)
+                        || is_comment_definition(definition, &self.sentinel)
+            );
+            result.push_str(&self.visit_contract_part(p)?);
         }
-        result.push('{');
-        result.push_str(&parts.join("\n\n"));
         result.push('}');
 
         Ok(result)
@@ -308,23 +360,103 @@ impl Visitor for Formatter {
     }
 }
 
+/// Whether `part` is a disguised natspec tag.
+fn is_natspec(part: &SourceUnitPart, sentinel: &str) -> bool {
+    matches!(
+        part,
+        SourceUnitPart::VariableDefinition(definition)
+            if is_natspec_definition(definition, sentinel)
+    )
+}
+
+/// Whether `part` is a disguised comment.
+fn is_comment(part: &SourceUnitPart, sentinel: &str) -> bool {
+    matches!(
+        part,
+        SourceUnitPart::VariableDefinition(definition)
+            if is_comment_definition(definition, sentinel)
+    )
+}
+
+/// Whether `definition` is a disguised natspec tag.
+fn is_natspec_definition(
+    definition: &VariableDefinition,
+    sentinel: &str,
+) -> bool {
+    definition
+        .name
+        .as_ref()
+        .is_some_and(|name| name.name == disguised_name(sentinel, "natspec"))
+}
+
+/// Whether `definition` is a disguised comment.
+fn is_comment_definition(
+    definition: &VariableDefinition,
+    sentinel: &str,
+) -> bool {
+    definition
+        .name
+        .as_ref()
+        .is_some_and(|name| name.name == disguised_name(sentinel, "comment"))
+}
+
 /// Converts special `__bulloak_comment__` variables to regular solidity
 /// comments.
 ///
 /// Specifically, it looks for patterns matching `string __bulloak_comment__ =
-/// "<comment>";` and converts them into `// <comment>` format.
-fn cleanup_comments(source: &str) -> String {
-    static RE_BULLOAK_COMMENT: Lazy<Regex> = Lazy::new(|| {
-        Regex::new(r#"string __bulloak_comment__ = "(.*)";"#).unwrap()
-    });
-    RE_BULLOAK_COMMENT.replace_all(source, "// $1").to_string()
+/// "<comment>";` and converts them into `// <comment>` format. Similarly,
+/// `string __bulloak_raw__ = "<statement>";` is replaced by `<statement>` and
+/// `string __bulloak_natspec__ = "<tag>";` by `/// <tag>`, and
+/// `string __bulloak_verbatim__ = "<comment>";` by `// <comment>` preceded by
+/// a `// __bulloak_verbatim__` line, see [`restore_directives`].
+///
+/// `sentinel` is the prefix of the variables, `__bulloak` by default, and `re`
+/// the regex returned by [`disguised_variables_regex`] for it.
+fn cleanup_comments(source: &str, re: &Regex, sentinel: &str) -> String {
+    re.replace_all(source, |caps: &regex::Captures<'_>| {
+        let indentation = &caps[1];
+        match &caps[2] {
+            "comment" => format!("{indentation}// {}", &caps[3]),
+            "verbatim" => format!(
+                "{indentation}// {}\n{indentation}// {}",
+                disguised_name(sentinel, "verbatim"),
+                &caps[3]
+            ),
+            "natspec" => format!("{indentation}/// {}", &caps[3]),
+            _ => format!("{indentation}{}", &caps[3]),
+        }
+    })
+    .to_string()
+}
+
+/// Returns the regex that matches the disguised variables prefixed with
+/// `sentinel`, see [`cleanup_comments`].
+fn disguised_variables_regex(sentinel: &str) -> Regex {
+    let pattern = format!(
+        r#"([ \t]*)string {}_(comment|verbatim|raw|natspec)__ = "(.*)";"#,
+        regex::escape(sentinel)
+    );
+    Regex::new(&pattern).expect("should be a valid regex")
+}
+
+/// Turns the `// __bulloak_verbatim__` lines of formatted `source` into
+/// `// forgefmt: disable-next-line` directives.
+///
+/// This happens after formatting, since forge-fmt would otherwise leave the
+/// comment below each directive as is, including its indentation.
+pub(crate) fn restore_directives(source: &str, sentinel: &str) -> String {
+    let marker = format!("// {}", disguised_name(sentinel, "verbatim"));
+    source.replace(&marker, &format!("// {FORGEFMT_DISABLE}"))
 }
 
 #[cfg(test)]
 mod tests {
     use pretty_assertions::assert_eq;
 
-    use crate::sol::fmt::cleanup_comments;
+    use crate::sol::{
+        fmt::{cleanup_comments, RE_BULLOAK_COMMENT},
+        SENTINEL,
+    };
 
     #[test]
     fn cleanups_comments() {
@@ -371,6 +503,9 @@ function test_WhenFirstArgIsZero() external whenFirstArgIsSmallerThanSecondArg {
 function test_WhenFirstArgIsBiggerThanSecondArg() external {
 // It should match the result of `keccak256(abi.encodePacked(b,a))`.
 }}"#;
-        assert_eq!(expected, cleanup_comments(&source));
+        assert_eq!(
+            expected,
+            cleanup_comments(source, &RE_BULLOAK_COMMENT, SENTINEL)
+        );
     }
 }
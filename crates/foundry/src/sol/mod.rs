@@ -1,19 +1,211 @@
 //! This module implements functionality related to operating on a parse tree
 //! (PT) from `solang_parser`.
 
+use sha2::{Digest, Sha256};
 use solang_parser::pt::{
     ContractDefinition, ContractPart, FunctionDefinition, FunctionTy,
-    Identifier, SourceUnit, SourceUnitPart,
+    Identifier, Loc, SourceUnit, SourceUnitPart,
 };
 
-use crate::hir::hir;
+use crate::{
+    constants::TREE_HASH_MARKER,
+    hir::{hir, Hir},
+};
 pub(crate) mod fmt;
 pub(crate) mod translator;
 mod visitor;
 
-pub(crate) use fmt::Formatter;
+pub(crate) use fmt::{restore_directives, Formatter};
 pub(crate) use translator::Translator;
 
+/// The default prefix of the variables that disguise comments, natspec tags
+/// and raw statements in the PT, e.g. `__bulloak_comment__`.
+pub(crate) const SENTINEL: &str = "__bulloak";
+
+/// Returns a prefix for the disguised variables that doesn't appear in any
+/// text of `hir`.
+///
+/// The formatter turns disguised variables back into comments with a search
+/// and replace, so a tree that contains the name of a disguised variable
+/// would otherwise be emitted incorrectly. In that case, a number is
+/// appended to the default prefix, e.g. `__bulloak1`.
+pub(crate) fn sentinel(hir: &Hir) -> String {
+    let mut sentinel = SENTINEL.to_owned();
+    let mut suffix = 0;
+    while hir.contains_text(&sentinel) {
+        suffix += 1;
+        sentinel = format!("{SENTINEL}{suffix}");
+    }
+
+    sentinel
+}
+
+/// Returns the name of the disguised variable of `kind`, e.g. `comment`.
+pub(crate) fn disguised_name(sentinel: &str, kind: &str) -> String {
+    format!("{sentinel}_{kind}__")
+}
+
+/// Returns the `console.log` statement that traces an action titled `title`.
+///
+/// Quotes and backslashes in the title are escaped so that the string literal
+/// stays valid.
+pub(crate) fn console_log(title: &str) -> String {
+    let text = title.replace('\\', "\\\\").replace('"', "\\\"");
+    format!(r#"console.log("{text}");"#)
+}
+
+/// Returns the `assertEq` statement that checks a result against the value
+/// an action expects.
+///
+/// `actual` is a placeholder for the user to replace with the result.
+pub(crate) fn assert_eq(expected: &str) -> String {
+    format!("assertEq(actual, {expected});")
+}
+
+/// Returns the `vm.expectRevert` statement that expects a revert with the
+/// custom error `error`, called with `args`.
+pub(crate) fn expect_revert(error: &str, args: &[String]) -> String {
+    if args.is_empty() {
+        return format!("vm.expectRevert({error}.selector);");
+    }
+
+    format!(
+        "vm.expectRevert(abi.encodeWithSelector({error}.selector, {}));",
+        args.join(", ")
+    )
+}
+
+/// Returns the `vm.expectCall` statement that expects a call to `target`.
+///
+/// `data` is a placeholder for the user to replace with the calldata.
+pub(crate) fn expect_call(target: &str) -> String {
+    format!("vm.expectCall(address({target}), data);")
+}
+
+/// The statement that expects the next event to be emitted.
+pub(crate) const EXPECT_EMIT: &str = "vm.expectEmit();";
+
+/// Returns the statement that emits the event a test expects.
+pub(crate) fn emit_event(event: &str) -> String {
+    format!("emit {event}();")
+}
+
+/// The low-level call of a test whose action never reverts.
+///
+/// `target` and `data` are placeholders for the user to replace with the
+/// contract under test and the calldata.
+pub(crate) const LOW_LEVEL_CALL: &str =
+    "(bool success,) = address(target).call(data);";
+/// The check that the call in [`LOW_LEVEL_CALL`] didn't revert.
+pub(crate) const ASSERT_SUCCESS: &str =
+    r#"assertTrue(success, "should not revert");"#;
+
+/// Returns the body of the comment that disables the solhint `rules`.
+pub(crate) fn solhint_directive(rules: &[String]) -> String {
+    format!("solhint-disable {}", rules.join(", "))
+}
+
+/// Returns the hex-encoded SHA-256 hash of `text`.
+///
+/// This is the hash stored in the `@bulloak:tree-hash` comment of the files
+/// generated with `--tree-hash` or `--detect-manual-edits`.
+#[must_use]
+pub fn hash(text: &str) -> String {
+    format!("{:x}", Sha256::digest(text))
+}
+
+/// Returns the body of the comment that stores the tree `hash`.
+pub(crate) fn tree_hash_directive(hash: &str) -> String {
+    format!("{TREE_HASH_MARKER} {hash}")
+}
+
+/// Returns the hashes stored in the `@bulloak:tree-hash` comment of the
+/// Solidity code `src`, if any.
+///
+/// The first one is the hash of the tree, and the second one, if present, the
+/// hash of the code itself as written by `bulloak`. See [`stamp`].
+fn stored_hashes(src: &str) -> Option<(&str, Option<&str>)> {
+    src.lines().find_map(|line| {
+        let comment = line.trim().strip_prefix("//")?.trim_start();
+        let mut hashes =
+            comment.strip_prefix(TREE_HASH_MARKER)?.split_whitespace();
+        Some((hashes.next().unwrap_or_default(), hashes.next()))
+    })
+}
+
+/// Returns the tree hash stored in the Solidity code `src`, if any.
+pub(crate) fn stored_tree_hash(src: &str) -> Option<&str> {
+    stored_hashes(src).map(|(tree, _)| tree)
+}
+
+/// Appends the hash of the Solidity code `src` to its `@bulloak:tree-hash`
+/// comment, so that [`is_manually_edited`] can tell whether it changed.
+///
+/// Code without a tree hash is returned as is.
+#[must_use]
+pub fn stamp(src: &str) -> String {
+    let src = src.trim_end();
+    let Some((tree, None)) = stored_hashes(src) else {
+        return format!("{src}\n");
+    };
+
+    let directive = tree_hash_directive(tree);
+    let stamped = format!("{directive} {}", hash(src));
+    format!("{}\n", src.replacen(&directive, &stamped, 1))
+}
+
+/// Whether the Solidity code `src` changed since [`stamp`] was called on it.
+///
+/// Code without a stamp is considered to be edited, since we can't tell
+/// whether it was generated by `bulloak`.
+#[must_use]
+pub fn is_manually_edited(src: &str) -> bool {
+    let Some((tree, Some(stored))) = stored_hashes(src) else {
+        return true;
+    };
+
+    let directive = tree_hash_directive(tree);
+    let stamped = format!("{directive} {stored}");
+    let original = src.trim_end().replacen(&stamped, &directive, 1);
+    hash(&original) != stored
+}
+
+/// Checks that `statement` is valid Solidity in the body of a function, e.g.
+/// `_test();`.
+///
+/// Statements passed through the configuration are emitted as is, so they
+/// are checked before scaffolding rather than failing to format later.
+///
+/// # Errors
+///
+/// Returns a description of the problem if `statement` doesn't parse.
+pub fn validate_statement(statement: &str) -> Result<(), String> {
+    let src =
+        format!("contract C {{ function f() external {{ {statement}\n}} }}");
+    match solang_parser::parse(&src, 0) {
+        Ok(_) => Ok(()),
+        Err(_) => Err(format!("invalid Solidity statement: {statement}")),
+    }
+}
+
+/// Checks that `path` can be emitted in an import directive, e.g.
+/// `import { Test } from "<path>";`.
+///
+/// # Errors
+///
+/// Returns a description of the problem if `path` contains quotes or line
+/// breaks.
+pub fn validate_import(path: &str) -> Result<(), String> {
+    if path.contains(['"', '\n', '\r']) {
+        return Err(format!(
+            "invalid import path: {path:?}, paths can't contain quotes or \
+             line breaks"
+        ));
+    }
+
+    Ok(())
+}
+
 /// Searches for and returns the first `ContractDefinition` found in a given
 /// `SourceUnit`.
 #[must_use]
@@ -24,6 +216,37 @@ pub fn find_contract(pt: &SourceUnit) -> Option<Box<ContractDefinition>> {
     })
 }
 
+/// Parses the Solidity declaration of an enum or a struct, e.g.
+/// `enum Status { Active, Paused }`, into a contract part.
+///
+/// Returns `None` if `declaration` isn't a single valid enum or struct.
+pub(crate) fn parse_type_declaration(
+    declaration: &str,
+) -> Option<ContractPart> {
+    let source = format!("contract C {{ {declaration} }}");
+    let (pt, _) = solang_parser::parse(&source, 0).ok()?;
+    let contract = find_contract(&pt)?;
+    match contract.parts.as_slice() {
+        [part @ (ContractPart::EnumDefinition(_)
+        | ContractPart::StructDefinition(_))] => Some(part.clone()),
+        _ => None,
+    }
+}
+
+/// Searches for the first `pragma solidity` directive in a given
+/// `SourceUnit` and returns its location and version, e.g. `^0.8.0`.
+#[must_use]
+pub fn find_pragma(pt: &SourceUnit) -> Option<(Loc, String)> {
+    pt.0.iter().find_map(|part| match part {
+        SourceUnitPart::PragmaDirective(loc, Some(name), Some(version))
+            if name.name == "solidity" =>
+        {
+            Some((*loc, version.string.clone()))
+        }
+        _ => None,
+    })
+}
+
 /// Given a HIR function, `find_matching_fn` performs a search over the sol
 /// contract parts trying to find a sol function with a matching name and type.
 pub(crate) fn find_matching_fn<'a>(
@@ -72,8 +295,12 @@ mod tests {
     use solang_parser::pt;
 
     use crate::{
-        hir,
-        sol::{find_matching_fn, fn_types_match, fns_match},
+        config::Config,
+        hir::{self, translate},
+        sol::{
+            find_matching_fn, fn_types_match, fns_match, is_manually_edited,
+            sentinel, stamp, stored_tree_hash,
+        },
     };
 
     #[test]
@@ -94,6 +321,10 @@ mod tests {
             ty,
             span: Default::default(),
             modifiers: Default::default(),
+            params: vec![],
+            condition_title: None,
+            payable: false,
+            keyword: None,
             children: Default::default(),
         }
     }
@@ -179,4 +410,28 @@ mod tests {
         let actual = find_matching_fn(&contract, &needle_hir);
         assert_eq!(None, actual);
     }
+    #[test]
+    fn picks_a_sentinel_missing_from_the_tree() {
+        let cfg = Config::default();
+        let hir = translate("Foo\n└── it should work.", &cfg).unwrap();
+        assert_eq!(sentinel(&hir), "__bulloak");
+
+        let tree = "Foo\n└── it should emit __bulloak_comment__.";
+        let hir = translate(tree, &cfg).unwrap();
+        assert_eq!(sentinel(&hir), "__bulloak1");
+    }
+
+    #[test]
+    fn detects_manual_edits_of_stamped_code() {
+        let src = "// @bulloak:tree-hash abc\ncontract Foo {}\n";
+        assert!(is_manually_edited(src));
+
+        let stamped = stamp(src);
+        assert_eq!(stored_tree_hash(&stamped), Some("abc"));
+        assert!(!is_manually_edited(&stamped));
+        assert!(is_manually_edited(&stamped.replace("Foo", "Bar")));
+
+        // Code without a tree hash can't be stamped.
+        assert_eq!(stamp("contract Foo {}"), "contract Foo {}\n");
+    }
 }
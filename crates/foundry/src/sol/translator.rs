@@ -15,18 +15,26 @@
 //! interface for initiating the translation process. Internally, a
 //! `TranslatorI` struct implements the detailed translation logic.
 
-use std::cell::Cell;
+use std::{cell::Cell, path::PathBuf};
 
 use bulloak_syntax::utils::sanitize;
 use solang_parser::pt::{
     Base, ContractDefinition, ContractPart, ContractTy, Expression,
     FunctionAttribute, FunctionDefinition, FunctionTy, Identifier,
-    IdentifierPath, Import, ImportPath, Loc, SourceUnit, SourceUnitPart,
-    Statement, StringLiteral, Type, VariableDeclaration, Visibility,
+    IdentifierPath, Import, ImportPath, Loc, Mutability, Parameter,
+    ParameterList, SourceUnit, SourceUnitPart, Statement, StorageLocation,
+    StringLiteral, Type, VariableDeclaration, VariableDefinition, Visibility,
+    YulBlock,
 };
 
+use super::{
+    assert_eq, console_log, disguised_name, emit_event, expect_call,
+    expect_revert, parse_type_declaration, solhint_directive,
+    tree_hash_directive, ASSERT_SUCCESS, EXPECT_EMIT, LOW_LEVEL_CALL, SENTINEL,
+};
 use crate::{
-    config::Config,
+    config::{Config, TestBase},
+    constants::{AAA_STEPS, GENERATED_MARKER},
     hir::{self, visitor::Visitor, Hir},
 };
 
@@ -36,13 +44,47 @@ use crate::{
 /// It visits a HIR in depth-first order an generates a PT
 /// as a result.
 #[derive(Default)]
+#[allow(clippy::struct_excessive_bools)]
 pub(crate) struct Translator {
     /// The Solidity version to be used in the pragma directive.
     sol_version: String,
     /// A flag indicating if there is a forge-std dependency.
     with_forge_std: bool,
+    /// Whether forge-std's `console` is imported along with `Test`.
+    with_console: bool,
+    /// The contract to inherit from when there is a forge-std dependency.
+    test_base: TestBase,
     /// Whether to emit modifiers.
     skip_modifiers: bool,
+    /// Whether to emit action descriptions as `TODO:` comments.
+    descriptions_as_todos: bool,
+    /// The paths of the files to import after the pragma directive.
+    imports: Vec<String>,
+    /// Whether to omit the pragma directive.
+    skip_pragma: bool,
+    /// The features to enable with `pragma experimental`.
+    experimental_pragmas: Vec<String>,
+    /// The solhint rules to disable after the license line.
+    solhint_disable: Vec<String>,
+    /// The hash of the tree to store after the license line, if any.
+    tree_hash: Option<String>,
+    /// The path of the tree file to link every function to, if any.
+    spec_path: Option<PathBuf>,
+    /// Whether to emit condition titles as comments above modifiers.
+    modifier_comments: bool,
+    /// Whether to emit the comments of tests above their signature.
+    comments_above: bool,
+    /// Whether to mark every generated function with a comment.
+    mark_generated: bool,
+    /// Whether to emit a `receive` function when a test is payable.
+    emit_receive: bool,
+    /// Whether to add an Arrange / Act / Assert skeleton to every test.
+    aaa: bool,
+    /// Whether to keep forge-fmt from reflowing generated comments.
+    forgefmt_disable: bool,
+    /// The prefix of the variables that disguise comments and raw
+    /// statements.
+    sentinel: String,
 }
 
 impl Translator {
@@ -52,17 +94,45 @@ impl Translator {
         let with_forge_std = [cfg.emit_vm_skip].into_iter().any(|f| f);
 
         Self {
-            sol_version: cfg.solidity_version.clone(),
+            sol_version: cfg.pragma_version(),
             with_forge_std,
+            with_console: false,
+            test_base: cfg.test_base.clone(),
             skip_modifiers: cfg.skip_modifiers,
+            descriptions_as_todos: cfg.descriptions_as_todos,
+            imports: cfg.imports.clone(),
+            skip_pragma: cfg.skip_pragma,
+            experimental_pragmas: cfg.experimental_pragmas.clone(),
+            solhint_disable: cfg.solhint_disable.clone(),
+            tree_hash: cfg.tree_hash.clone(),
+            spec_path: cfg.spec_path.clone(),
+            modifier_comments: cfg.modifier_comments,
+            comments_above: cfg.comments_above,
+            mark_generated: cfg.mark_generated,
+            emit_receive: cfg.emit_receive,
+            aaa: cfg.aaa,
+            forgefmt_disable: cfg.forgefmt_disable,
+            sentinel: SENTINEL.to_owned(),
         }
     }
 
+    /// Sets the prefix of the variables that disguise comments and raw
+    /// statements.
+    ///
+    /// See [`super::sentinel`].
+    #[must_use]
+    pub(crate) fn with_sentinel(mut self, sentinel: &str) -> Self {
+        sentinel.clone_into(&mut self.sentinel);
+        self
+    }
+
     /// Translate a HIR to a PT.
     ///
     /// This function is the entry point of the translator.
     #[must_use]
-    pub(crate) fn translate(self, hir: &Hir) -> SourceUnit {
+    pub(crate) fn translate(mut self, hir: &Hir) -> SourceUnit {
+        self.with_forge_std |= hir.uses_cheatcodes();
+        self.with_console = hir.uses_console();
         TranslatorI::new(self).translate(hir)
     }
 }
@@ -122,6 +192,242 @@ impl TranslatorI {
         Identifier { loc: function_name_loc, name: identifier.to_owned() }
     }
 
+    /// Generates the parameter list of a function, where every parameter is
+    /// declared as `<type> <name>`, or as `<type> memory <name>` if its type
+    /// is a reference type.
+    fn translate_params(&self, fn_params: &[hir::Parameter]) -> ParameterList {
+        self.bump("("); // `(` after the function identifier.
+        let mut params = Vec::with_capacity(fn_params.len());
+        for (idx, param) in fn_params.iter().enumerate() {
+            if idx > 0 {
+                self.bump(", ");
+            }
+            let param_start = self.offset.get();
+            let ty_loc = self.bump(&param.ty);
+            let ty = if param.ty == "uint256" {
+                Expression::Type(ty_loc, Type::Uint(256))
+            } else {
+                Expression::Variable(Identifier {
+                    loc: ty_loc,
+                    name: param.ty.clone(),
+                })
+            };
+            let storage = param.location().map(|location| {
+                self.bump(" ");
+                StorageLocation::Memory(self.bump(location))
+            });
+            self.bump(" ");
+            let name = Identifier {
+                loc: self.bump(&param.name),
+                name: param.name.clone(),
+            };
+            let loc = Loc::File(0, param_start, self.offset.get());
+            let param = Parameter {
+                loc,
+                annotation: None,
+                ty,
+                storage,
+                name: Some(name),
+            };
+            params.push((loc, Some(param)));
+        }
+        self.bump(") "); // `) ` before the function attributes.
+
+        params
+    }
+
+    /// Disguises `text` as a `string <name> = "<text>";` statement so
+    /// that it survives formatting untouched, where the variable name is
+    /// derived from `kind`, e.g. `__bulloak_comment__`.
+    ///
+    /// The formatter later replaces these definitions with their contents.
+    fn translate_disguised(&self, kind: &str, text: &str) -> Statement {
+        let variable_name = &disguised_name(&self.translator.sentinel, kind);
+        let definition_start = self.offset.get();
+        let declaration_start = definition_start;
+        let ty = Expression::Type(self.bump("string"), Type::String);
+        self.bump(" "); // ` ` after type.
+        let variable_loc = self.bump(variable_name);
+        let declaration_loc =
+            Loc::File(0, declaration_start, self.offset.get());
+        let name =
+            Identifier { loc: variable_loc, name: variable_name.to_owned() };
+        let variable = VariableDeclaration {
+            loc: declaration_loc,
+            ty,
+            storage: None,
+            name: Some(name),
+        };
+        self.bump(" = ");
+        let text_loc = self.bump(&format!(r#""{text}""#));
+        let string_literal =
+            Some(Expression::StringLiteral(vec![StringLiteral {
+                loc: text_loc,
+                unicode: false,
+                string: text.to_owned(),
+            }]));
+        self.bump(";"); // `;` after string literal.
+
+        Statement::VariableDefinition(
+            Loc::File(0, definition_start, self.offset.get()),
+            variable,
+            string_literal,
+        )
+    }
+
+    /// Generates the comments that go right after the license line: the
+    /// solhint directive, if any rules should be disabled, and the tree hash,
+    /// if it should be stored.
+    fn translate_header_comments(&self) -> Vec<SourceUnitPart> {
+        let solhint = (!self.translator.solhint_disable.is_empty())
+            .then(|| solhint_directive(&self.translator.solhint_disable));
+        let hash =
+            self.translator.tree_hash.as_deref().map(tree_hash_directive);
+        solhint
+            .into_iter()
+            .chain(hash)
+            .map(|directive| {
+                SourceUnitPart::VariableDefinition(Box::new(
+                    self.translate_part_disguised("comment", &directive),
+                ))
+            })
+            .collect()
+    }
+
+    /// Disguises a natspec `tag` as a variable definition, see
+    /// `translate_disguised`.
+    fn translate_natspec(&self, tag: &str) -> VariableDefinition {
+        self.translate_part_disguised("natspec", tag)
+    }
+
+    /// Disguises `text` as a state variable, so that it can be emitted
+    /// outside of a function body.
+    fn translate_part_disguised(
+        &self,
+        kind: &str,
+        text: &str,
+    ) -> VariableDefinition {
+        let Statement::VariableDefinition(loc, declaration, initializer) =
+            self.translate_disguised(kind, text)
+        else {
+            unreachable!()
+        };
+
+        VariableDefinition {
+            loc,
+            ty: declaration.ty,
+            attrs: vec![],
+            name: declaration.name,
+            initializer,
+        }
+    }
+
+    /// Translates a `vm` cheatcode call with a single argument that is
+    /// emitted verbatim, e.g. `vm.warp(1000);`.
+    fn translate_vm_call_verbatim(&self, method: &str, arg: &str) -> Statement {
+        self.translate_vm_call(method, |t| {
+            let loc = t.bump(arg);
+            vec![Expression::Variable(Identifier { loc, name: arg.to_owned() })]
+        })
+    }
+
+    /// Bumps `self.offset` given a `vm` cheatcode call and returns the
+    /// appropriate `Statement::Expression`.
+    ///
+    /// `args` is called right after bumping the opening parenthesis, so that
+    /// it can bump the offset for the arguments of the call.
+    fn translate_vm_call(
+        &self,
+        method: &str,
+        args: impl FnOnce(&Self) -> Vec<Expression>,
+    ) -> Statement {
+        let start_offset = self.offset.get();
+        let loc_vm = self.bump("vm");
+        self.bump(".");
+        let loc_method = self.bump(method);
+        self.bump("(");
+        let args = args(self);
+        let args_end = self.offset.get();
+        self.bump(");");
+
+        let vm_interface = Expression::MemberAccess(
+            Loc::File(0, start_offset, loc_method.end()),
+            Box::new(Expression::Variable(Identifier {
+                loc: loc_vm,
+                name: "vm".to_owned(),
+            })),
+            Identifier { loc: loc_method, name: method.to_owned() },
+        );
+        let vm_call = Expression::FunctionCall(
+            Loc::File(0, loc_method.start(), args_end),
+            Box::new(vm_interface),
+            args,
+        );
+
+        Statement::Expression(
+            Loc::File(0, start_offset, self.offset.get()),
+            vm_call,
+        )
+    }
+
+    /// Bumps `self.offset` given a pragma directive, e.g. `pragma solidity
+    /// 0.8.0;`, and returns the appropriate PT node.
+    fn translate_pragma(&self, name: &str, value: &str) -> SourceUnitPart {
+        let pragma_start = self.offset.get();
+        self.bump("pragma");
+        self.bump(" ");
+        let pragma_ty =
+            Some(Identifier { loc: self.bump(name), name: name.to_owned() });
+        self.bump(" ");
+        let pragma_identifier = Some(StringLiteral {
+            loc: self.bump(value),
+            unicode: false,
+            string: value.to_owned(),
+        });
+        let pragma = SourceUnitPart::PragmaDirective(
+            Loc::File(0, pragma_start, self.offset.get()),
+            pragma_ty,
+            pragma_identifier,
+        );
+        self.bump(";\n");
+
+        pragma
+    }
+
+    /// Bumps `self.offset` given an import directive of the `names` exported
+    /// by `path`, e.g. `import { Test, console } from "forge-std/Test.sol";`,
+    /// and returns the appropriate PT node.
+    fn translate_import(&self, names: &[&str], path: &str) -> SourceUnitPart {
+        let loc_import_start = self.offset.get();
+        self.bump("import { ");
+        let mut identifiers = vec![];
+        for (idx, name) in names.iter().enumerate() {
+            if idx > 0 {
+                self.bump(", ");
+            }
+            let loc = self.bump(name);
+            identifiers
+                .push((Identifier { loc, name: (*name).to_owned() }, None));
+        }
+        self.bump(" } from \"");
+        let loc_path = self.bump(path);
+
+        // The import directive `Rename` corresponds to `import {x} from
+        // y.sol`.
+        let import = SourceUnitPart::ImportDirective(Import::Rename(
+            ImportPath::Filename(StringLiteral {
+                loc: loc_path,
+                unicode: false,
+                string: path.to_owned(),
+            }),
+            identifiers,
+            Loc::File(0, loc_import_start, loc_path.end()),
+        ));
+        self.bump("\";\n");
+
+        import
+    }
+
     /// Bumps `self.offset` given a modifier and returns the appropriate
     /// `FunctionAttribute::BaseOrModifier`.
     fn translate_modifier(&self, modifier: &str) -> FunctionAttribute {
@@ -174,6 +480,12 @@ impl TranslatorI {
                     Visibility::External(Some(self.bump("external"))),
                 )];
                 self.bump(" ");
+                if function.payable {
+                    attrs.push(FunctionAttribute::Mutability(
+                        Mutability::Payable(self.bump("payable")),
+                    ));
+                    self.bump(" ");
+                }
                 if let Some(ref modifiers) = function.modifiers {
                     attrs.extend(
                         modifiers.iter().map(|m| self.translate_modifier(m)),
@@ -185,6 +497,43 @@ impl TranslatorI {
         }
     }
 
+    /// Generates an empty `receive() external payable {}` function, so that
+    /// the test contract can be sent ETH.
+    fn gen_receive(&self) -> ContractPart {
+        let start_offset = self.offset.get();
+        let ty_loc = self.bump("receive");
+        self.bump("() ");
+        let visibility = Visibility::External(Some(self.bump("external")));
+        self.bump(" ");
+        let mutability = Mutability::Payable(self.bump("payable"));
+        self.bump(" ");
+        let attributes = vec![
+            FunctionAttribute::Visibility(visibility),
+            FunctionAttribute::Mutability(mutability),
+        ];
+
+        let body_start = self.offset.get();
+        self.bump("{}");
+        let func_def = FunctionDefinition {
+            loc: Loc::File(0, start_offset, body_start - 1),
+            ty: FunctionTy::Receive,
+            name: None,
+            name_loc: ty_loc,
+            params: vec![],
+            attributes,
+            return_not_returns: None,
+            returns: vec![],
+            body: Some(Statement::Block {
+                loc: Loc::File(0, body_start, self.offset.get()),
+                unchecked: false,
+                statements: vec![],
+            }),
+        };
+        self.bump("\n\n");
+
+        ContractPart::FunctionDefinition(Box::new(func_def))
+    }
+
     /// Generates the statements of a modifier function. In the context of this
     /// translation, a modifier's body is represented by a special variable
     /// definition. This function creates and returns a vector of statements
@@ -257,7 +606,19 @@ impl TranslatorI {
                 stmts.push(self.visit_statement(statement)?);
             }
             if let Hir::Comment(comment) = child {
-                stmts.push(self.visit_comment(comment)?);
+                if !self.translator.comments_above {
+                    stmts.push(self.visit_comment(comment)?);
+                }
+            }
+        }
+
+        let has_comments =
+            children.iter().any(|child| matches!(child, Hir::Comment(_)));
+        if self.translator.aaa && has_comments {
+            // An empty raw statement becomes a blank line.
+            stmts.push(self.translate_disguised("raw", ""));
+            for step in AAA_STEPS {
+                stmts.push(self.translate_disguised("comment", step));
             }
         }
 
@@ -270,6 +631,28 @@ impl TranslatorI {
         Ok(stmts)
     }
 
+    /// Generates the comments of `function` as contract parts, so that they
+    /// end up above its signature instead of inside its body.
+    fn gen_comments_above(
+        &self,
+        function: &hir::FunctionDefinition,
+    ) -> Vec<ContractPart> {
+        let children = function.children.as_deref().unwrap_or_default();
+        children
+            .iter()
+            .filter_map(|child| match child {
+                Hir::Comment(comment) => {
+                    let text =
+                        comment.text(self.translator.descriptions_as_todos);
+                    Some(ContractPart::VariableDefinition(Box::new(
+                        self.translate_part_disguised("comment", &text),
+                    )))
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
     /// Generates the body of a function or modifier as a sequence of
     /// statements. This function differentiates between function and
     /// modifier types and generates the respective bodies accordingly.
@@ -352,53 +735,71 @@ impl Visitor for TranslatorI {
         &mut self,
         root: &hir::Root,
     ) -> Result<Self::RootOutput, Self::Error> {
-        let mut source_unit = Vec::with_capacity(2);
+        let mut source_unit = self.translate_header_comments();
 
-        let pragma_start = self.offset.get();
-        self.bump("pragma");
-        self.bump(" ");
-        let pragma_ty = Some(Identifier {
-            loc: self.bump("solidity"),
-            name: "solidity".to_owned(),
-        });
-        self.bump(" ");
-        let pragma_identifier = Some(StringLiteral {
-            loc: self.bump(&self.translator.sol_version),
-            unicode: false,
-            string: self.translator.sol_version.clone(),
-        });
-        source_unit.push(SourceUnitPart::PragmaDirective(
-            Loc::File(0, pragma_start, self.offset.get()),
-            pragma_ty,
-            pragma_identifier,
-        ));
-        self.bump(";\n");
+        // Add the pragma directives, unless the user opted out of them.
+        if !self.translator.skip_pragma {
+            let version = self.translator.sol_version.clone();
+            source_unit.push(self.translate_pragma("solidity", &version));
+            for feature in &self.translator.experimental_pragmas {
+                source_unit
+                    .push(self.translate_pragma("experimental", feature));
+            }
+        }
+
+        // Add the comments of the root of the tree.
+        for child in &root.children {
+            if let Hir::Comment(comment) = child {
+                source_unit.push(SourceUnitPart::VariableDefinition(Box::new(
+                    self.translate_part_disguised("comment", &comment.lexeme),
+                )));
+                self.bump("\n");
+            }
+        }
 
-        // Add the forge-std's Test import, if needed.
+        // Add the import of forge-std's `Test`, or of the configured test
+        // base, if needed.
         if self.translator.with_forge_std {
-            // Getting the relevant offsets for `import {Test} from
-            // "forge-std/Test.sol"`.
+            let base = self.translator.test_base.clone();
+            let with_console = self.translator.with_console;
+            let mut names = vec![base.name.as_str()];
+            // Only forge-std's `Test.sol` exports `console` too.
+            if with_console && base.is_forge_std() {
+                names.push("console");
+            }
+            source_unit.push(self.translate_import(&names, &base.path));
+            if with_console && !base.is_forge_std() {
+                source_unit.push(
+                    self.translate_import(
+                        &["console"],
+                        "forge-std/console.sol",
+                    ),
+                );
+            }
+        }
+
+        // Import the contracts generated from the base trees.
+        for base in root.children.iter().flat_map(|child| match child {
+            Hir::ContractDefinition(contract) => contract.bases.as_slice(),
+            _ => &[],
+        }) {
+            source_unit.push(
+                self.translate_import(&[&base.identifier], &base.import_path()),
+            );
+        }
+
+        // Add the user-provided imports, e.g. `import "test/Constants.sol"`.
+        for path in &self.translator.imports {
             let loc_import_start = self.offset.get();
-            self.bump("import { ");
-            let loc_identifier = self.bump("Test");
-            self.bump(" } from \"");
-            let loc_path = self.bump("forge-std/Test.sol");
-
-            // The import directive `Rename` corresponds to `import {x} from
-            // y.sol`.
-            source_unit.push(SourceUnitPart::ImportDirective(Import::Rename(
+            self.bump("import \"");
+            let loc_path = self.bump(path);
+
+            source_unit.push(SourceUnitPart::ImportDirective(Import::Plain(
                 ImportPath::Filename(StringLiteral {
                     loc: loc_path,
                     unicode: false,
-                    string: "forge-std/Test.sol".to_string(),
+                    string: path.clone(),
                 }),
-                vec![(
-                    Identifier {
-                        loc: loc_identifier,
-                        name: "Test".to_string(),
-                    },
-                    None,
-                )],
                 Loc::File(0, loc_import_start, loc_path.end()),
             )));
 
@@ -407,6 +808,14 @@ impl Visitor for TranslatorI {
 
         for child in &root.children {
             if let Hir::ContractDefinition(contract) = child {
+                // Disguise the natspec tags in the same way as comments, see
+                // `visit_comment`.
+                for tag in &contract.natspec {
+                    source_unit.push(SourceUnitPart::VariableDefinition(
+                        Box::new(self.translate_natspec(tag)),
+                    ));
+                    self.bump("\n");
+                }
                 source_unit.push(self.visit_contract(contract)?);
             }
         }
@@ -448,39 +857,84 @@ impl Visitor for TranslatorI {
             name: contract.identifier.clone(),
         });
 
-        let mut contract_base = vec![];
-
-        // If there is an import, inherit the base contract as well.
+        // If there is an import, inherit the base contract as well. `Test`
+        // goes first, since the base trees may inherit from it too.
+        let mut base_names: Vec<&str> = vec![];
         if self.translator.with_forge_std {
-            let base_start = self.offset.get();
-            self.bump(" is ");
-            let base_loc = self.bump("Test");
+            base_names.push(&self.translator.test_base.name);
+        }
+        base_names.extend(contract.bases.iter().map(|b| b.identifier.as_str()));
+
+        let mut contract_base = vec![];
+        for (idx, name) in base_names.into_iter().enumerate() {
+            self.bump(if idx == 0 { " is " } else { ", " });
+            let base_loc = self.bump(name);
             let base_identifier_path = IdentifierPath {
                 loc: base_loc,
                 identifiers: vec![Identifier {
                     loc: base_loc,
-                    name: "Test".to_string(),
+                    name: name.to_owned(),
                 }],
             };
 
-            contract_base = vec![Base {
-                loc: Loc::File(0, base_start, base_loc.end()),
+            contract_base.push(Base {
+                loc: base_loc,
                 name: base_identifier_path,
                 args: None,
-            }];
+            });
         }
         self.bump(" {"); // `{` after contract identifier and base.
 
         let mut parts = Vec::with_capacity(contract.children.len());
+        for declaration in &contract.types {
+            self.bump(declaration);
+            parts.extend(parse_type_declaration(declaration));
+        }
         for child in &contract.children {
             if let Hir::FunctionDefinition(function) = child {
                 if function.is_modifier() && self.translator.skip_modifiers {
                     continue;
                 }
+                if self.translator.mark_generated {
+                    parts.push(ContractPart::VariableDefinition(Box::new(
+                        self.translate_part_disguised(
+                            "comment",
+                            GENERATED_MARKER,
+                        ),
+                    )));
+                }
+                if let (true, Some(title)) = (
+                    self.translator.modifier_comments,
+                    &function.condition_title,
+                ) {
+                    parts.push(ContractPart::VariableDefinition(Box::new(
+                        self.translate_part_disguised("comment", title),
+                    )));
+                }
+                if let Some(spec_path) = &self.translator.spec_path {
+                    let tag = function.spec_ref(spec_path);
+                    parts.push(ContractPart::VariableDefinition(Box::new(
+                        self.translate_natspec(&tag),
+                    )));
+                }
+                if self.translator.comments_above {
+                    parts.extend(self.gen_comments_above(function));
+                }
                 parts.push(self.visit_function(function)?);
+            } else if let Hir::Comment(comment) = child {
+                parts.push(ContractPart::VariableDefinition(Box::new(
+                    self.translate_part_disguised("comment", &comment.lexeme),
+                )));
             }
         }
 
+        let has_payable_tests = contract.children.iter().any(
+            |child| matches!(child, Hir::FunctionDefinition(f) if f.payable),
+        );
+        if self.translator.emit_receive && has_payable_tests {
+            parts.push(self.gen_receive());
+        }
+
         let contract_def = ContractDefinition {
             loc: Loc::File(0, contract_start, self.offset.get()),
             name: contract_name,
@@ -534,7 +988,7 @@ impl Visitor for TranslatorI {
             self.translate_function_id(&function.identifier);
         let function_id_loc = function_identifier.loc;
         let function_name = Some(function_identifier);
-        self.bump("() "); // `(<identifier>) `.
+        let params = self.translate_params(&function.params);
         let attributes = self.gen_function_attr(function);
 
         let body_start = self.offset.get();
@@ -546,7 +1000,7 @@ impl Visitor for TranslatorI {
             ty: function_ty,
             name: function_name,
             name_loc: function_id_loc,
-            params: vec![],
+            params,
             attributes,
             return_not_returns: None,
             returns: vec![],
@@ -596,37 +1050,17 @@ impl Visitor for TranslatorI {
         //
         // The idea is to remove the extra parts with a search and replace when
         // emitting the parse tree and leave the comment's lexeme as is.
-        let definition_start = self.offset.get();
-        let declaration_start = definition_start;
-        let ty = Expression::Type(self.bump("string"), Type::String);
-        self.bump(" "); // ` ` after type.
-        let variable_name = "__bulloak_comment__";
-        let variable_loc = self.bump(variable_name);
-        let declaration_loc =
-            Loc::File(0, declaration_start, self.offset.get());
-        let name =
-            Identifier { loc: variable_loc, name: variable_name.to_owned() };
-        let variable = VariableDeclaration {
-            loc: declaration_loc,
-            ty,
-            storage: None,
-            name: Some(name),
+        //
+        // Comments to keep verbatim get their own kind, so that the
+        // `forgefmt` directive is added after formatting. Otherwise,
+        // forge-fmt would leave the comment unindented.
+        let text = comment.text(self.translator.descriptions_as_todos);
+        let kind = if self.translator.forgefmt_disable {
+            "verbatim"
+        } else {
+            "comment"
         };
-        self.bump(" = ");
-        let comment_loc = self.bump(&format!(r#""{}""#, &comment.lexeme));
-        let string_literal =
-            Some(Expression::StringLiteral(vec![StringLiteral {
-                loc: comment_loc,
-                unicode: false,
-                string: comment.lexeme.clone(),
-            }]));
-        self.bump(";"); // `;` after string literal.
-        let definition = Statement::VariableDefinition(
-            Loc::File(0, definition_start, self.offset.get()),
-            variable,
-            string_literal,
-        );
-
+        let definition = self.translate_disguised(kind, &text);
         Ok(definition)
     }
 
@@ -637,41 +1071,88 @@ impl Visitor for TranslatorI {
     ) -> Result<Self::StatementOutput, Self::Error> {
         let start_offset = self.offset.get();
 
-        match statement.ty {
-            hir::StatementType::VmSkip => {
-                let loc_vm = self.bump("vm");
-                self.bump(".");
-                let loc_skip = self.bump("skip");
-                self.bump("(");
-                let loc_arg = self.bump("true");
-                self.bump(");");
-
-                let vm_interface = Expression::MemberAccess(
-                    Loc::File(0, start_offset, loc_skip.end()),
-                    Box::new(Expression::Variable(
-                        solang_parser::pt::Identifier {
-                            loc: loc_vm,
-                            name: "vm".to_owned(),
-                        },
-                    )),
-                    solang_parser::pt::Identifier {
-                        loc: loc_skip,
-                        name: "skip".to_owned(),
-                    },
-                );
+        match &statement.ty {
+            hir::StatementType::VmSkip => Ok(self
+                .translate_vm_call("skip", |t| {
+                    vec![Expression::BoolLiteral(t.bump("true"), true)]
+                })),
+            hir::StatementType::Assembly => {
+                self.bump("assembly ");
+                let block_loc = self.bump("{}");
 
-                let vm_skip_arg = vec![Expression::BoolLiteral(loc_arg, true)];
-
-                let vm_skip_call = Expression::FunctionCall(
-                    Loc::File(0, loc_skip.start(), loc_arg.end()),
-                    Box::new(vm_interface),
-                    vm_skip_arg,
-                );
-
-                Ok(Statement::Expression(
-                    Loc::File(0, start_offset, self.offset.get()),
-                    vm_skip_call,
-                ))
+                Ok(Statement::Assembly {
+                    loc: Loc::File(0, start_offset, self.offset.get()),
+                    dialect: None,
+                    flags: None,
+                    block: YulBlock { loc: block_loc, statements: vec![] },
+                })
+            }
+            hir::StatementType::VmLabel(name) => {
+                Ok(self.translate_vm_call("label", |t| {
+                    let address_loc = t.bump(name);
+                    t.bump(", ");
+                    let label_loc = t.bump(&format!(r#""{name}""#));
+                    vec![
+                        Expression::Variable(Identifier {
+                            loc: address_loc,
+                            name: name.clone(),
+                        }),
+                        Expression::StringLiteral(vec![StringLiteral {
+                            loc: label_loc,
+                            unicode: false,
+                            string: name.clone(),
+                        }]),
+                    ]
+                }))
+            }
+            hir::StatementType::VmWarp(timestamp) => {
+                Ok(self.translate_vm_call_verbatim("warp", timestamp))
+            }
+            hir::StatementType::VmRoll(block) => {
+                Ok(self.translate_vm_call_verbatim("roll", block))
+            }
+            hir::StatementType::StartSnapshotGas(name) => Ok(self
+                .translate_vm_call("startSnapshotGas", |t| {
+                    let loc = t.bump(&format!(r#""{name}""#));
+                    vec![Expression::StringLiteral(vec![StringLiteral {
+                        loc,
+                        unicode: false,
+                        string: name.clone(),
+                    }])]
+                })),
+            hir::StatementType::StopSnapshotGas => {
+                Ok(self.translate_vm_call("stopSnapshotGas", |_| vec![]))
+            }
+            hir::StatementType::Raw(text) => {
+                Ok(self.translate_disguised("raw", text))
+            }
+            hir::StatementType::TargetContract => {
+                Ok(self
+                    .translate_disguised("raw", "targetContract(address(0));"))
+            }
+            hir::StatementType::ConsoleLog(title) => {
+                Ok(self.translate_disguised("raw", &console_log(title)))
+            }
+            hir::StatementType::AssertEq(expected) => {
+                Ok(self.translate_disguised("raw", &assert_eq(expected)))
+            }
+            hir::StatementType::ExpectRevert(error, args) => {
+                Ok(self.translate_disguised("raw", &expect_revert(error, args)))
+            }
+            hir::StatementType::LowLevelCall => {
+                Ok(self.translate_disguised("raw", LOW_LEVEL_CALL))
+            }
+            hir::StatementType::AssertSuccess => {
+                Ok(self.translate_disguised("raw", ASSERT_SUCCESS))
+            }
+            hir::StatementType::ExpectCall(target) => {
+                Ok(self.translate_disguised("raw", &expect_call(target)))
+            }
+            hir::StatementType::ExpectEmit => {
+                Ok(self.translate_disguised("raw", EXPECT_EMIT))
+            }
+            hir::StatementType::Emit(event) => {
+                Ok(self.translate_disguised("raw", &emit_event(event)))
             }
         }
     }
@@ -1,6 +1,8 @@
 //! The AST for a bulloak tree file.
 
-use crate::span::Span;
+use unicode_xid::UnicodeXID;
+
+use crate::{semantics, span::Span};
 
 /// An Abstract Syntax Tree (AST) that describes the semantic
 /// structure of a bulloak tree.
@@ -39,6 +41,67 @@ impl Ast {
     pub fn is_action(&self) -> bool {
         matches!(self, Self::Action(_))
     }
+
+    /// Runs the semantic checks of [`crate::parse_one`] over this AST.
+    ///
+    /// This is useful for ASTs that are built programmatically rather than
+    /// parsed. Since there is no source text to point into, the errors only
+    /// carry the spans of the offending nodes.
+    ///
+    /// # Errors
+    ///
+    /// Returns every semantic error found in the AST.
+    pub fn validate(&self) -> Result<(), semantics::Errors> {
+        semantics::SemanticAnalyzer::new("").analyze(self)
+    }
+
+    /// Returns the identifiers referenced in the action titles of this AST,
+    /// e.g. `Transfer` in `it should emit {Transfer}`.
+    ///
+    /// Identifiers are returned in the order they first appear in, without
+    /// duplicates. Braces that don't hold a single identifier, like the
+    /// value sets of conditions, are ignored.
+    #[must_use]
+    pub fn referenced_identifiers(&self) -> Vec<String> {
+        let mut identifiers = vec![];
+        self.collect_identifiers(&mut identifiers);
+        identifiers
+    }
+
+    /// Appends the identifiers referenced in the action titles of this AST
+    /// to `identifiers`, see [`Ast::referenced_identifiers`].
+    fn collect_identifiers(&self, identifiers: &mut Vec<String>) {
+        let children = match self {
+            Self::Root(root) => &root.children,
+            Self::Condition(condition) => &condition.children,
+            Self::Action(action) => {
+                for identifier in braced_identifiers(&action.title) {
+                    if !identifiers.iter().any(|i| i == identifier) {
+                        identifiers.push(identifier.to_owned());
+                    }
+                }
+                return;
+            }
+            Self::ActionDescription(_) => return,
+        };
+
+        for child in children {
+            child.collect_identifiers(identifiers);
+        }
+    }
+}
+
+/// Returns the identifiers between braces in `text`, e.g. `Transfer` in
+/// `it should emit {Transfer}`.
+fn braced_identifiers(text: &str) -> impl Iterator<Item = &str> {
+    text.split('{').skip(1).filter_map(|part| {
+        let (identifier, _) = part.split_once('}')?;
+        let mut chars = identifier.chars();
+        let is_identifier =
+            chars.next().is_some_and(|c| c.is_xid_start() || c == '_')
+                && chars.all(|c| c.is_xid_continue() || c == '$');
+        is_identifier.then_some(identifier)
+    })
 }
 
 /// The root node of the AST.
@@ -46,6 +109,12 @@ impl Ast {
 pub struct Root {
     /// The name that is used for the emitted contract.
     pub contract_name: String,
+    /// The header directives above the root, e.g. `@title My Test Suite`.
+    pub directives: Vec<Directive>,
+    /// The markers attached to the root, e.g. `[evm:cancun]`.
+    pub markers: Vec<Marker>,
+    /// The comments above the root or trailing it, without the `//`.
+    pub comments: Vec<String>,
     /// The span that encompasses this node. It includes
     /// all of its children.
     pub span: Span,
@@ -60,6 +129,12 @@ pub struct Condition {
     ///
     /// For example: "when stuff happens".
     pub title: String,
+    /// The status symbol that prefixes this condition's title, e.g. `✅`.
+    pub status: Option<String>,
+    /// The markers attached to this condition, e.g. `[assembly]`.
+    pub markers: Vec<Marker>,
+    /// The comment trailing this condition's title, without the `//`.
+    pub comment: Option<String>,
     /// The span that encompasses this node. It includes
     /// all of its children.
     pub span: Span,
@@ -74,6 +149,12 @@ pub struct Action {
     ///
     /// For example: "It should revert."
     pub title: String,
+    /// The status symbol that prefixes this action's title, e.g. `✅`.
+    pub status: Option<String>,
+    /// The markers attached to this action, e.g. `[assembly]`.
+    pub markers: Vec<Marker>,
+    /// The comment trailing this action's title, without the `//`.
+    pub comment: Option<String>,
     /// The span that encompasses this node.
     pub span: Span,
     /// The children AST nodes of this node.
@@ -93,3 +174,138 @@ pub struct Description {
     /// The span that encompasses this node.
     pub span: Span,
 }
+
+/// A header directive of the AST.
+///
+/// Directives have the form `@tag content` and sit right above the root of a
+/// tree. They become contract-level natspec tags.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Directive {
+    /// The tag of this directive.
+    ///
+    /// For example: "custom:security-contact" in
+    /// `@custom:security-contact security@example.com`.
+    pub tag: String,
+    /// The content of this directive.
+    ///
+    /// For example: "My Test Suite" in `@title My Test Suite`.
+    pub content: String,
+    /// The span of this directive.
+    pub span: Span,
+}
+
+impl Directive {
+    /// Creates a directive from its lexeme, e.g. `@title My Test Suite`.
+    ///
+    /// Returns `None` if the lexeme doesn't start with `@` followed by a tag.
+    #[must_use]
+    pub fn from_lexeme(lexeme: &str, span: Span) -> Option<Self> {
+        let directive = lexeme.strip_prefix('@')?;
+        let (tag, content) = directive
+            .split_once(char::is_whitespace)
+            .unwrap_or((directive, ""));
+        if tag.is_empty() {
+            return None;
+        }
+
+        Some(Self {
+            tag: tag.to_owned(),
+            content: content.trim().to_owned(),
+            span,
+        })
+    }
+
+    /// Whether this directive declares a type to emit into the contract,
+    /// i.e. whether its tag is `enum` or `struct`.
+    #[must_use]
+    pub fn is_type_declaration(&self) -> bool {
+        TYPE_TAGS.contains(&self.tag.as_str())
+    }
+
+    /// Returns the Solidity declaration of the type this directive declares,
+    /// e.g. `enum Status { Active, Paused }` for
+    /// `@enum Status { Active, Paused }`.
+    ///
+    /// Returns `None` if this directive doesn't declare a type, or if its
+    /// content isn't an identifier followed by a body between braces.
+    #[must_use]
+    pub fn type_declaration(&self) -> Option<String> {
+        if !self.is_type_declaration() {
+            return None;
+        }
+        let (name, body) = self.content.split_once('{')?;
+        let body = body.strip_suffix('}')?;
+        let mut chars = name.trim_end().chars();
+        let is_identifier =
+            chars.next().is_some_and(|c| c.is_xid_start() || c == '_')
+                && chars.all(|c| c.is_xid_continue() || c == '$');
+        let is_balanced = !body.contains(['{', '}']);
+        (is_identifier && is_balanced)
+            .then(|| format!("{} {}", self.tag, self.content))
+    }
+}
+
+/// The tags of the directives that declare a type, e.g.
+/// `@enum Status { Active, Paused }`.
+const TYPE_TAGS: [&str; 2] = ["enum", "struct"];
+
+/// A marker attached to the title of a condition or an action.
+///
+/// Markers have the form `[name]` or `[name:value]` and tweak the code
+/// generated for the node they are attached to. They are not part of the
+/// title of the node.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Marker {
+    /// The name of this marker.
+    ///
+    /// For example: "evm" in `[evm:cancun]`.
+    pub name: String,
+    /// The optional value of this marker.
+    ///
+    /// For example: "cancun" in `[evm:cancun]`.
+    pub value: Option<String>,
+    /// The span of this marker.
+    pub span: Span,
+}
+
+impl Marker {
+    /// Creates a marker from its lexeme, e.g. `[evm:cancun]`.
+    ///
+    /// Returns `None` if the lexeme is not enclosed in brackets.
+    #[must_use]
+    pub fn from_lexeme(lexeme: &str, span: Span) -> Option<Self> {
+        let inner = lexeme.strip_prefix('[')?.strip_suffix(']')?;
+        let (name, value) = match inner.split_once(':') {
+            Some((name, value)) => (name, Some(value.to_owned())),
+            None => (inner, None),
+        };
+
+        Some(Self { name: name.to_owned(), value, span })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use crate::parse_one;
+
+    #[test]
+    fn referenced_identifiers() {
+        let ast = parse_one(
+            r"Foo
+├── when the amount is in {ZERO, ONE}
+│   └── it should revert with {InvalidAmount}
+└── when the amount is valid
+    ├── it should emit {Transfer} and {Approval}
+    ├── it should emit {Transfer} again
+    └── it should not { break }",
+        )
+        .unwrap();
+
+        assert_eq!(
+            ast.referenced_identifiers(),
+            vec!["InvalidAmount", "Transfer", "Approval"]
+        );
+    }
+}
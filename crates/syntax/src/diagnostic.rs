@@ -0,0 +1,152 @@
+//! Defines a unified representation of the problems `bulloak` reports.
+//!
+//! Every error, lint and violation converts into a [`Diagnostic`], so that
+//! tooling can render them consistently. Each kind of problem has a stable
+//! code, e.g. `B101`:
+//!
+//! - `B0xx`: tokenizer errors.
+//! - `B1xx`: parser errors.
+//! - `B2xx`: semantic errors.
+//! - `B3xx`: errors combining the roots of a tree.
+//! - `B4xx`: lints.
+//! - `B5xx`: `bulloak check` violations.
+
+use std::fmt;
+
+use crate::{error::FrontendError, parser, semantics, span::Span, tokenizer};
+
+/// How serious a diagnostic is.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Severity {
+    /// The problem prevents `bulloak` from doing its job.
+    Error,
+    /// The problem is worth a look, but doesn't prevent `bulloak` from
+    /// doing its job.
+    Warning,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Severity::Error => write!(f, "error"),
+            Severity::Warning => write!(f, "warning"),
+        }
+    }
+}
+
+/// A problem found in a tree or in a Solidity file.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Diagnostic {
+    /// How serious this problem is.
+    pub severity: Severity,
+    /// The span of the construct that triggered this problem.
+    ///
+    /// Problems that refer to a whole file have a default span.
+    pub span: Span,
+    /// A human-readable description of this problem.
+    pub message: String,
+    /// The stable code of this kind of problem, e.g. `B101`.
+    pub code: &'static str,
+}
+
+impl Diagnostic {
+    /// Creates a new error diagnostic.
+    #[must_use]
+    pub fn error(code: &'static str, message: String, span: Span) -> Self {
+        Self { severity: Severity::Error, span, message, code }
+    }
+
+    /// Creates a new warning diagnostic.
+    #[must_use]
+    pub fn warning(code: &'static str, message: String, span: Span) -> Self {
+        Self { severity: Severity::Warning, span, message, code }
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}[{}]: {} (line {}, column {})",
+            self.severity,
+            self.code,
+            self.message,
+            self.span.start.line,
+            self.span.start.column
+        )
+    }
+}
+
+impl From<&tokenizer::Error> for Diagnostic {
+    fn from(error: &tokenizer::Error) -> Self {
+        let kind = error.kind();
+        Diagnostic::error(kind.code(), kind.to_string(), *error.span())
+    }
+}
+
+impl From<&parser::Error> for Diagnostic {
+    fn from(error: &parser::Error) -> Self {
+        let kind = error.kind();
+        Diagnostic::error(kind.code(), kind.to_string(), *error.span())
+    }
+}
+
+impl From<&semantics::Error> for Diagnostic {
+    fn from(error: &semantics::Error) -> Self {
+        let kind = error.kind();
+        Diagnostic::error(kind.code(), kind.to_string(), *error.span())
+    }
+}
+
+/// Converts an error returned by one of the parsing functions into
+/// diagnostics.
+///
+/// Returns no diagnostics if `error` doesn't come from `bulloak-syntax`.
+#[must_use]
+pub fn diagnostics(error: &anyhow::Error) -> Vec<Diagnostic> {
+    if let Some(error) = error.downcast_ref::<tokenizer::Error>() {
+        vec![error.into()]
+    } else if let Some(error) = error.downcast_ref::<parser::Error>() {
+        vec![error.into()]
+    } else if let Some(errors) = error.downcast_ref::<semantics::Errors>() {
+        errors.0.iter().map(Into::into).collect()
+    } else if let Some(error) = error.downcast_ref::<semantics::Error>() {
+        vec![error.into()]
+    } else {
+        vec![]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::{diagnostics, Diagnostic, Severity};
+    use crate::{parse, Position, Span};
+
+    #[test]
+    fn converts_parsing_errors() {
+        let error = parse("Foo\n└── when something").unwrap_err();
+        let diagnostics = diagnostics(&error);
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].severity, Severity::Error);
+        assert_eq!(diagnostics[0].code, "B202");
+        assert_eq!(
+            diagnostics[0].message,
+            "found a condition with no children"
+        );
+    }
+
+    #[test]
+    fn displays_diagnostics() {
+        let span = Span::new(Position::new(4, 2, 1), Position::new(8, 2, 5));
+        let diagnostic =
+            Diagnostic::warning("B401", "some lint".to_owned(), span);
+
+        assert_eq!(
+            diagnostic.to_string(),
+            "warning[B401]: some lint (line 2, column 1)"
+        );
+    }
+}
@@ -4,6 +4,7 @@
 //! in string form to an AST. It also includes a semantic analyzer.
 
 mod ast;
+pub mod diagnostic;
 mod error;
 pub mod parser;
 pub mod semantics;
@@ -14,10 +15,11 @@ pub mod tokenizer;
 pub mod utils;
 mod visitor;
 
-pub use ast::{Action, Ast, Condition, Description, Root};
+pub use ast::{Action, Ast, Condition, Description, Directive, Marker, Root};
+pub use diagnostic::{Diagnostic, Severity};
 pub use error::FrontendError;
 pub use span::{Position, Span};
-pub use tokenizer::{Token, TokenKind};
+pub use tokenizer::{KeywordAliases, Token, TokenKind};
 pub use visitor::Visitor;
 
 /// Parses a string containing trees into ASTs.
@@ -25,12 +27,72 @@ pub fn parse(text: &str) -> anyhow::Result<Vec<ast::Ast>> {
     splitter::split_trees(text).map(parse_one).collect()
 }
 
+/// Parses a string containing trees into ASTs, pairing each AST with the line
+/// its tree starts at.
+///
+/// Note that the spans in each AST are still relative to the start of its
+/// tree.
+///
+/// # Errors
+///
+/// Returns an error if any of the trees fails to parse.
+pub fn parse_with_lines(text: &str) -> anyhow::Result<Vec<(usize, ast::Ast)>> {
+    parse_with_aliases(text, &KeywordAliases::new())
+}
+
+/// Like [`parse_with_lines`], but recognizes the words in `aliases` as the
+/// keywords they map to, e.g. `if` as `when`.
+///
+/// # Errors
+///
+/// Returns an error if any of the trees fails to parse.
+pub fn parse_with_aliases(
+    text: &str,
+    aliases: &KeywordAliases,
+) -> anyhow::Result<Vec<(usize, ast::Ast)>> {
+    splitter::split_trees_with_lines(text)
+        .map(|(line, tree)| Ok((line, parse_tree(tree, aliases)?.1)))
+        .collect()
+}
+
 /// Parses a string containing a single tree into an AST.
 pub fn parse_one(text: &str) -> anyhow::Result<ast::Ast> {
-    let tokens = tokenizer::Tokenizer::new().tokenize(text)?;
+    parse_with_tokens(text).map(|(_, ast)| ast)
+}
+
+/// Parses a string containing a single tree into an AST, returning the tokens
+/// it was parsed from as well.
+///
+/// This is useful for tooling that needs both, e.g. to provide semantic
+/// highlighting, since it avoids tokenizing the tree twice.
+///
+/// # Errors
+///
+/// Returns an error if the tree fails to tokenize, parse or analyze.
+///
+/// ```
+/// use bulloak_syntax::{parse_with_tokens, TokenKind};
+///
+/// let (tokens, _ast) =
+///     parse_with_tokens("Foo\n└── It should work.").unwrap();
+/// assert_eq!(tokens[0].kind, TokenKind::Word);
+/// assert_eq!(tokens[2].kind, TokenKind::It);
+/// ```
+pub fn parse_with_tokens(text: &str) -> anyhow::Result<(Vec<Token>, ast::Ast)> {
+    parse_tree(text, &KeywordAliases::new())
+}
+
+/// Tokenizes, parses and analyzes a single tree.
+fn parse_tree(
+    text: &str,
+    aliases: &KeywordAliases,
+) -> anyhow::Result<(Vec<Token>, ast::Ast)> {
+    let tokens = tokenizer::Tokenizer::new()
+        .with_keyword_aliases(aliases.clone())
+        .tokenize(text)?;
     let ast = parser::Parser::new().parse(text, &tokens)?;
     let mut analyzer = semantics::SemanticAnalyzer::new(text);
     analyzer.analyze(&ast)?;
 
-    Ok(ast)
+    Ok((tokens, ast))
 }
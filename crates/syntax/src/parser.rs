@@ -4,7 +4,7 @@ use std::{borrow::Borrow, cell::Cell, fmt, result};
 use thiserror::Error;
 
 use super::{
-    ast::{Action, Ast, Condition, Description, Root},
+    ast::{Action, Ast, Condition, Description, Directive, Marker, Root},
     tokenizer::{Token, TokenKind},
 };
 use crate::{
@@ -108,6 +108,38 @@ pub enum ErrorKind {
     /// A tee is the last child.
     #[error("a `Tee` must not be the last child")]
     TeeLastChild,
+
+    /// A header directive without a tag was found.
+    #[error("found a directive without a tag")]
+    DirectiveTagMissing,
+
+    /// An `@enum` or `@struct` directive isn't of the form
+    /// `@TAG IDENTIFIER { BODY }`.
+    #[error("found a type declaration without a name or a body")]
+    TypeDeclarationInvalid,
+}
+
+impl ErrorKind {
+    /// Returns the stable code of this kind of error.
+    #[must_use]
+    pub const fn code(&self) -> &'static str {
+        match self {
+            ErrorKind::TokenUnexpected(_) => "B101",
+            ErrorKind::DescriptionTokenUnexpected(_) => "B102",
+            ErrorKind::WhenUnexpected => "B103",
+            ErrorKind::GivenUnexpected => "B104",
+            ErrorKind::ItUnexpected => "B105",
+            ErrorKind::WordUnexpected(_) => "B106",
+            ErrorKind::EofUnexpected => "B107",
+            ErrorKind::TreeEmpty => "B108",
+            ErrorKind::TitleMissing => "B109",
+            ErrorKind::TreeRootless => "B110",
+            ErrorKind::CornerNotLastChild => "B111",
+            ErrorKind::TeeLastChild => "B112",
+            ErrorKind::DirectiveTagMissing => "B113",
+            ErrorKind::TypeDeclarationInvalid => "B114",
+        }
+    }
 }
 
 /// A parser for a sequence of .tree tokens into an abstract syntax tree (AST).
@@ -136,7 +168,16 @@ impl Parser {
         text: &str,
         tokens: &[Token],
     ) -> Result<Ast> {
-        ParserI::new(self, text, tokens).parse()
+        // Comments don't take part in the grammar, so they are attached to
+        // the nodes they sit next to once the tree is built.
+        let (comments, tokens): (Vec<_>, Vec<_>) = tokens
+            .iter()
+            .cloned()
+            .partition(|token| token.kind == TokenKind::Comment);
+        let mut ast = ParserI::new(self, text, &tokens).parse()?;
+        attach_comments(&mut ast, &comments);
+
+        Ok(ast)
     }
 
     /// Reset the parser to its initial state.
@@ -194,6 +235,19 @@ impl<'t, P: Borrow<Parser>> ParserI<'t, P> {
         self.tokens.get(current_index + 1)
     }
 
+    /// Return a reference to the first token of the title of the branch
+    /// that starts at the current token.
+    ///
+    /// This is the next token, unless it is a status symbol, in which case
+    /// it is the token after it.
+    fn peek_title_start(&self) -> Option<&Token> {
+        let mut index = self.parser().current.get() + 1;
+        if self.tokens.get(index).is_some_and(|t| t.kind == TokenKind::Status) {
+            index += 1;
+        }
+        self.tokens.get(index)
+    }
+
     /// Return the previous token.
     ///
     /// Returns `None` if the parser is currently at the start
@@ -224,30 +278,75 @@ impl<'t, P: Borrow<Parser>> ParserI<'t, P> {
     pub(crate) fn parse(&self) -> Result<Ast> {
         self.parser().reset();
 
+        let directives = self.parse_directives()?;
         let root_token = self
             .current()
             .ok_or(self.error(Span::default(), ErrorKind::TreeEmpty))?;
 
         match root_token.kind {
-            TokenKind::Word => self.parse_root(root_token),
+            TokenKind::Word => self.parse_root(root_token, directives),
             _ => Err(self.error(root_token.span, ErrorKind::TreeRootless)),
         }
     }
 
+    /// Parse the header directives above the root, if any.
+    ///
+    /// A directive has the form:
+    /// ```grammar
+    /// @TAG CONTENT
+    /// ```
+    ///
+    /// The content of `@enum` and `@struct` directives must be a type
+    /// declaration, e.g. `@enum Status { Active, Paused }`.
+    fn parse_directives(&self) -> Result<Vec<Directive>> {
+        let mut directives = vec![];
+        while let Some(token) =
+            self.current().filter(|t| t.kind == TokenKind::Directive)
+        {
+            let directive = Directive::from_lexeme(&token.lexeme, token.span)
+                .ok_or(
+                self.error(token.span, ErrorKind::DirectiveTagMissing),
+            )?;
+            if directive.is_type_declaration()
+                && directive.type_declaration().is_none()
+            {
+                return Err(
+                    self.error(token.span, ErrorKind::TypeDeclarationInvalid)
+                );
+            }
+            directives.push(directive);
+            self.consume();
+        }
+
+        Ok(directives)
+    }
+
     /// Parse the root node of the AST.
     ///
     /// A root has the form:
     /// ```grammar
-    /// CONTRACT_NAME
+    /// CONTRACT_NAME <MARKER>*
     /// (<TEE> [Condition | Action])*
     /// <CORNER> [Condition | Action]
     /// ```
     ///
     /// Panics if called when the parser is not at a `Word` token.
-    fn parse_root(&self, token: &Token) -> Result<Ast> {
+    fn parse_root(
+        &self,
+        token: &Token,
+        directives: Vec<Directive>,
+    ) -> Result<Ast> {
         assert!(matches!(token.kind, TokenKind::Word));
         self.consume();
 
+        let mut markers = vec![];
+        while let Some(marker) =
+            self.current().filter(|t| t.kind == TokenKind::Marker)
+        {
+            markers.extend(Marker::from_lexeme(&marker.lexeme, marker.span));
+            self.consume();
+        }
+
         // The loop invariant is that `self.current` is a
         // `Tee` or the last `Corner`.
         let mut children = vec![];
@@ -257,7 +356,11 @@ impl<'t, P: Borrow<Parser>> ParserI<'t, P> {
                     TokenKind::Corner | TokenKind::Tee => {
                         self.parse_branch(current_token)?
                     }
-                    TokenKind::Word => Err(self.error(
+                    TokenKind::Word
+                    | TokenKind::Marker
+                    | TokenKind::Status
+                    | TokenKind::Directive
+                    | TokenKind::Comment => Err(self.error(
                         current_token.span,
                         ErrorKind::WordUnexpected(current_token.lexeme.clone()),
                     ))?,
@@ -285,6 +388,9 @@ impl<'t, P: Borrow<Parser>> ParserI<'t, P> {
             span: Span::new(token.span.start, last_span.end),
             children,
             contract_name: token.lexeme.clone(),
+            directives,
+            markers,
+            comments: vec![],
         }))
     }
 
@@ -298,7 +404,7 @@ impl<'t, P: Borrow<Parser>> ParserI<'t, P> {
     fn parse_branch(&self, token: &Token) -> Result<Ast> {
         assert!(matches!(token.kind, TokenKind::Tee | TokenKind::Corner));
 
-        let first_token = self.peek().ok_or(self.error(
+        let first_token = self.peek_title_start().ok_or(self.error(
             token.span.with_start(token.span.end),
             ErrorKind::EofUnexpected,
         ))?;
@@ -343,11 +449,12 @@ impl<'t, P: Borrow<Parser>> ParserI<'t, P> {
     fn parse_condition(&self, token: &Token) -> Result<Ast> {
         assert!(matches!(token.kind, TokenKind::Tee | TokenKind::Corner));
 
+        let status = self.parse_status();
         let start_token = self.peek().ok_or(self.error(
             token.span.with_start(token.span.end),
             ErrorKind::EofUnexpected,
         ))?;
-        let title = self.parse_string(start_token);
+        let (title, markers) = self.parse_title(start_token);
 
         if title.len() == start_token.lexeme.len() {
             return Err(self.error(start_token.span, ErrorKind::TitleMissing));
@@ -360,7 +467,7 @@ impl<'t, P: Borrow<Parser>> ParserI<'t, P> {
             // The column determines the tree level we are in.
             .is_some_and(|t| t.span.start.column > token.span.start.column)
         {
-            let next_token = self.peek().ok_or(self.error(
+            let next_token = self.peek_title_start().ok_or(self.error(
                 token.span.with_start(token.span.end),
                 ErrorKind::EofUnexpected,
             ))?;
@@ -382,7 +489,10 @@ impl<'t, P: Borrow<Parser>> ParserI<'t, P> {
 
         let previous = self.previous().unwrap();
         Ok(Ast::Condition(Condition {
-            title: sanitize(&title),
+            title: sanitize_condition_title(&title),
+            status,
+            markers,
+            comment: None,
             children,
             span: Span::new(token.span.start, previous.span.end),
         }))
@@ -402,11 +512,12 @@ impl<'t, P: Borrow<Parser>> ParserI<'t, P> {
     fn parse_action(&self, token: &Token) -> Result<Ast> {
         assert!(matches!(token.kind, TokenKind::Tee | TokenKind::Corner));
 
+        let status = self.parse_status();
         let start_token = self.peek().ok_or(self.error(
             token.span.with_start(token.span.end),
             ErrorKind::EofUnexpected,
         ))?;
-        let title = self.parse_string(start_token);
+        let (title, markers) = self.parse_title(start_token);
 
         let mut children = vec![];
         while self
@@ -422,10 +533,12 @@ impl<'t, P: Borrow<Parser>> ParserI<'t, P> {
 
             let current_token = self.current().unwrap();
             let ast = match next_token.kind {
-                TokenKind::Word => self.parse_description(
-                    current_token,
-                    current_token.span.start.column - token.span.start.column,
-                )?,
+                TokenKind::Word | TokenKind::Marker | TokenKind::Status => self
+                    .parse_description(
+                        current_token,
+                        current_token.span.start.column
+                            - token.span.start.column,
+                    )?,
                 _ => Err(self.error(
                     next_token.span,
                     ErrorKind::DescriptionTokenUnexpected(
@@ -440,6 +553,9 @@ impl<'t, P: Borrow<Parser>> ParserI<'t, P> {
         let previous = self.previous().unwrap();
         Ok(Ast::Action(Action {
             title,
+            status,
+            markers,
+            comment: None,
             children,
             span: Span::new(token.span.start, previous.span.end),
         }))
@@ -494,7 +610,7 @@ impl<'t, P: Borrow<Parser>> ParserI<'t, P> {
     /// A string is a sequence of words separated by spaces.
     ///
     /// Consumes all the tokens including the given token until no more words
-    /// are found.
+    /// are found. Markers and status symbols are treated as regular words.
     fn parse_string(&self, start_token: &Token) -> String {
         self.consume();
         let mut string = String::from(&start_token.lexeme);
@@ -505,7 +621,9 @@ impl<'t, P: Borrow<Parser>> ParserI<'t, P> {
                 TokenKind::Word
                 | TokenKind::It
                 | TokenKind::When
-                | TokenKind::Given => {
+                | TokenKind::Given
+                | TokenKind::Marker
+                | TokenKind::Status => {
                     string = string + " " + &token.lexeme;
                 }
                 _ => break,
@@ -514,6 +632,109 @@ impl<'t, P: Borrow<Parser>> ParserI<'t, P> {
 
         string
     }
+
+    /// Parse the status symbol of a condition or an action, if any.
+    ///
+    /// Consumes the status token if the next token is one, so that the
+    /// title starts at the token after the current one.
+    fn parse_status(&self) -> Option<String> {
+        let token = self.peek().filter(|t| t.kind == TokenKind::Status)?;
+        self.consume();
+        Some(token.lexeme.clone())
+    }
+
+    /// Parse the title of a condition or an action.
+    ///
+    /// A title is a string where any marker is collected separately instead
+    /// of being included in the text.
+    fn parse_title(&self, start_token: &Token) -> (String, Vec<Marker>) {
+        self.consume();
+        let mut title = String::from(&start_token.lexeme);
+        let mut markers = vec![];
+
+        // Consume all words and markers.
+        while let Some(token) = self.consume() {
+            match token.kind {
+                TokenKind::Word
+                | TokenKind::It
+                | TokenKind::When
+                | TokenKind::Given
+                | TokenKind::Status => {
+                    title = title + " " + &token.lexeme;
+                }
+                TokenKind::Marker => {
+                    markers
+                        .extend(Marker::from_lexeme(&token.lexeme, token.span));
+                }
+                _ => break,
+            }
+        }
+
+        (title, markers)
+    }
+}
+
+/// Attaches `comments` to the nodes of `ast`.
+///
+/// Comments above the root or on its line belong to the root, and a comment
+/// on the line of a condition or an action belongs to that node. Any other
+/// comment is dropped.
+fn attach_comments(ast: &mut Ast, comments: &[Token]) {
+    let Ast::Root(root) = ast else {
+        return;
+    };
+
+    for comment in comments {
+        let line = comment.span.start.line;
+        if line <= root.span.start.line {
+            root.comments.push(comment.lexeme.clone());
+            continue;
+        }
+        if let Some(node) = find_node_comment(&mut root.children, line) {
+            *node = Some(comment.lexeme.clone());
+        }
+    }
+}
+
+/// Returns the comment slot of the condition or action in `nodes` whose title
+/// is on `line`, searching recursively.
+fn find_node_comment(
+    nodes: &mut [Ast],
+    line: usize,
+) -> Option<&mut Option<String>> {
+    nodes.iter_mut().find_map(|node| {
+        let node_line = node.span().start.line;
+        let (comment, children) = match node {
+            Ast::Condition(condition) => {
+                (&mut condition.comment, &mut condition.children)
+            }
+            Ast::Action(action) => (&mut action.comment, &mut action.children),
+            Ast::Root(_) | Ast::ActionDescription(_) => return None,
+        };
+        if node_line == line {
+            Some(comment)
+        } else {
+            find_node_comment(children, line)
+        }
+    })
+}
+
+/// Sanitizes the title of a condition.
+///
+/// The braces and commas of a value set, e.g. `{Active, Paused}`, are kept
+/// so that later phases can expand it, while its values are sanitized like
+/// the rest of the title.
+fn sanitize_condition_title(title: &str) -> String {
+    let Some((head, rest)) = title.split_once('{') else {
+        return sanitize(title);
+    };
+    let Some((values, tail)) = rest.split_once('}') else {
+        return sanitize(title);
+    };
+
+    let values: Vec<_> =
+        values.split(',').map(|value| sanitize(value.trim())).collect();
+    format!("{}{{{}}}{}", sanitize(head), values.join(", "), sanitize(tail))
 }
 
 #[cfg(test)]
@@ -522,7 +743,7 @@ mod tests {
     use pretty_assertions::assert_eq;
 
     use crate::{
-        ast::{Action, Ast, Condition, Description, Root},
+        ast::{Action, Ast, Condition, Description, Directive, Marker, Root},
         parser::{self, ErrorKind, Parser},
         span::Span,
         test_utils::{p, s, TestError},
@@ -619,6 +840,9 @@ mod tests {
                 span: s(p(0, 1, 1), p(6, 1, 7)),
                 children: vec![],
                 contract_name: String::from("FooTest"),
+                directives: vec![],
+                markers: vec![],
+                comments: vec![],
             })
         );
     }
@@ -634,13 +858,22 @@ mod tests {
             parse(input).unwrap(),
             Ast::Root(Root {
                 contract_name: String::from("Foo_Test"),
+                directives: vec![],
+                markers: vec![],
+                comments: vec![],
                 span: s(p(0, 1, 1), p(74, 3, 23)),
                 children: vec![Ast::Condition(Condition {
                     span: s(p(9, 2, 1), p(74, 3, 23)),
                     title: String::from("when something bad happens"),
+                    status: None,
+                    markers: vec![],
+                    comment: None,
                     children: vec![Ast::Action(Action {
                         span: s(p(49, 3, 4), p(74, 3, 23)),
                         title: String::from("it should revert"),
+                        status: None,
+                        markers: vec![],
+                        comment: None,
                         children: vec![]
                     })],
                 })],
@@ -660,13 +893,22 @@ mod tests {
             parse(input).unwrap(),
             Ast::Root(Root {
                 contract_name: String::from("Foo_Test"),
+                directives: vec![],
+                markers: vec![],
+                comments: vec![],
                 span: s(p(0, 1, 1), p(104, 4, 23)),
                 children: vec![Ast::Condition(Condition {
                     span: s(p(9, 2, 1), p(104, 4, 23)),
                     title: String::from("when something bad happens"),
+                    status: None,
+                    markers: vec![],
+                    comment: None,
                     children: vec![Ast::Action(Action {
                         span: s(p(49, 3, 4), p(104, 4, 23)),
                         title: String::from("it should revert"),
+                        status: None,
+                        markers: vec![],
+                        comment: None,
                         children: vec![Ast::ActionDescription(Description {
                             span: s(p(82, 4, 7), p(104, 4, 23)),
                             text: String::from("   because _bad_"),
@@ -691,13 +933,22 @@ mod tests {
             parse(input).unwrap(),
             Ast::Root(Root {
                 contract_name: String::from("Foo_Test"),
+                directives: vec![],
+                markers: vec![],
+                comments: vec![],
                 span: s(p(0, 1, 1), p(177, 6, 24)),
                 children: vec![Ast::Condition(Condition {
                     span: s(p(9, 2, 1), p(177, 6, 24)),
                     title: String::from("when something bad happens"),
+                    status: None,
+                    markers: vec![],
+                    comment: None,
                     children: vec![Ast::Action(Action {
                         span: s(p(49, 3, 4), p(177, 6, 24)),
                         title: String::from("it should revert"),
+                        status: None,
+                        markers: vec![],
+                        comment: None,
                         children: vec![
                             Ast::ActionDescription(Description {
                                 span: s(p(82, 4, 7), p(110, 4, 29)),
@@ -791,22 +1042,37 @@ mod tests {
             parse(input).unwrap(),
             Ast::Root(Root {
                 contract_name: String::from("FooBarTheBest_Test"),
+                directives: vec![],
+                markers: vec![],
+                comments: vec![],
                 span: s(p(0, 1, 1), p(140, 5, 23)),
                 children: vec![
                     Ast::Condition(Condition {
                         title: String::from("when stuff called"),
+                        status: None,
+                        markers: vec![],
+                        comment: None,
                         span: s(p(19, 2, 1), p(77, 3, 23)),
                         children: vec![Ast::Action(Action {
                             title: String::from("it should revert"),
+                            status: None,
+                            markers: vec![],
+                            comment: None,
                             span: s(p(52, 3, 4), p(77, 3, 23)),
                             children: vec![]
                         })],
                     }),
                     Ast::Condition(Condition {
                         title: String::from("given not stuff called"),
+                        status: None,
+                        markers: vec![],
+                        comment: None,
                         span: s(p(79, 4, 1), p(140, 5, 23)),
                         children: vec![Ast::Action(Action {
                             title: String::from("it should revert"),
+                            status: None,
+                            markers: vec![],
+                            comment: None,
                             span: s(p(115, 5, 4), p(140, 5, 23)),
                             children: vec![]
                         })],
@@ -828,9 +1094,15 @@ mod tests {
             parse(input).unwrap(),
             Ast::Root(Root {
                 contract_name: String::from("Foo"),
+                directives: vec![],
+                markers: vec![],
+                comments: vec![],
                 span: s(p(0, 1, 1), p(31, 2, 22)),
                 children: vec![Ast::Action(Action {
                     title: String::from("It reverts when X."),
+                    status: None,
+                    markers: vec![],
+                    comment: None,
                     span: s(p(4, 2, 1), p(31, 2, 22)),
                     children: vec![]
                 })],
@@ -850,12 +1122,21 @@ mod tests {
             parse(input).unwrap(),
             Ast::Root(Root {
                 contract_name: String::from("FooB-rTheBestOf_Test"),
+                directives: vec![],
+                markers: vec![],
+                comments: vec![],
                 span: s(p(0, 1, 1), p(77, 3, 23)),
                 children: vec![Ast::Condition(Condition {
                     title: String::from("when st_ff alld"),
+                    status: None,
+                    markers: vec![],
+                    comment: None,
                     span: s(p(21, 2, 1), p(77, 3, 23)),
                     children: vec![Ast::Action(Action {
                         title: String::from("it should revert"),
+                        status: None,
+                        markers: vec![],
+                        comment: None,
                         span: s(p(52, 3, 4), p(77, 3, 23)),
                         children: vec![]
                     })],
@@ -863,4 +1144,215 @@ mod tests {
             })
         );
     }
+
+    #[test]
+    fn value_sets() {
+        let input = indoc! {"
+            Foo_Test
+            └── given the st-tus in {Act-ve,Paused}
+               └── it works
+        "};
+
+        let Ast::Root(root) = parse(input).unwrap() else { panic!() };
+        let Ast::Condition(condition) = &root.children[0] else { panic!() };
+        assert_eq!(condition.title, "given the st_tus in {Act_ve, Paused}");
+    }
+
+    #[test]
+    fn markers() {
+        let input = indoc! {"
+            Foo_Test
+            └── when something [gas] happens
+               └── it [evm:cancun] should revert
+                  └── because [reasons]
+        "};
+        assert_eq!(
+            parse(input).unwrap(),
+            Ast::Root(Root {
+                contract_name: String::from("Foo_Test"),
+                directives: vec![],
+                markers: vec![],
+                comments: vec![],
+                span: s(p(0, 1, 1), p(123, 4, 27)),
+                children: vec![Ast::Condition(Condition {
+                    span: s(p(9, 2, 1), p(123, 4, 27)),
+                    title: String::from("when something happens"),
+                    status: None,
+                    markers: vec![Marker {
+                        name: String::from("gas"),
+                        value: None,
+                        span: s(p(34, 2, 20), p(38, 2, 24)),
+                    }],
+                    comment: None,
+                    children: vec![Ast::Action(Action {
+                        span: s(p(51, 3, 4), p(123, 4, 27)),
+                        title: String::from("it should revert"),
+                        status: None,
+                        markers: vec![Marker {
+                            name: String::from("evm"),
+                            value: Some(String::from("cancun")),
+                            span: s(p(64, 3, 11), p(75, 3, 22)),
+                        }],
+                        comment: None,
+                        children: vec![Ast::ActionDescription(Description {
+                            span: s(p(97, 4, 7), p(123, 4, 27)),
+                            text: String::from("   because [reasons]"),
+                        })]
+                    })],
+                })],
+            })
+        );
+    }
+
+    #[test]
+    fn statuses() {
+        let input = indoc! {"
+            Foo_Test
+            └── ✅ when something happens
+               └── ❌ it should revert
+                  └── ⚠️ because
+        "};
+        assert_eq!(
+            parse(input).unwrap(),
+            Ast::Root(Root {
+                contract_name: String::from("Foo_Test"),
+                directives: vec![],
+                markers: vec![],
+                comments: vec![],
+                span: s(p(0, 1, 1), p(109, 4, 20)),
+                children: vec![Ast::Condition(Condition {
+                    span: s(p(9, 2, 1), p(109, 4, 20)),
+                    title: String::from("when something happens"),
+                    status: Some(String::from("✅")),
+                    markers: vec![],
+                    comment: None,
+                    children: vec![Ast::Action(Action {
+                        span: s(p(49, 3, 4), p(109, 4, 20)),
+                        title: String::from("it should revert"),
+                        status: Some(String::from("❌")),
+                        markers: vec![],
+                        comment: None,
+                        children: vec![Ast::ActionDescription(Description {
+                            span: s(p(86, 4, 7), p(109, 4, 20)),
+                            text: String::from("   ⚠️ because"),
+                        })]
+                    })],
+                })],
+            })
+        );
+    }
+
+    #[test]
+    fn directives() {
+        let input = indoc! {"
+            @title My Test Suite
+            @custom:security-contact security@example.com
+            Foo_Test
+            └── it should revert
+        "};
+        assert_eq!(
+            parse(input).unwrap(),
+            Ast::Root(Root {
+                contract_name: String::from("Foo_Test"),
+                directives: vec![
+                    Directive {
+                        tag: String::from("title"),
+                        content: String::from("My Test Suite"),
+                        span: s(p(0, 1, 1), p(19, 1, 20)),
+                    },
+                    Directive {
+                        tag: String::from("custom:security-contact"),
+                        content: String::from("security@example.com"),
+                        span: s(p(21, 2, 1), p(65, 2, 45)),
+                    },
+                ],
+                markers: vec![],
+                comments: vec![],
+                span: s(p(67, 3, 1), p(101, 4, 20)),
+                children: vec![Ast::Action(Action {
+                    span: s(p(76, 4, 1), p(101, 4, 20)),
+                    title: String::from("it should revert"),
+                    status: None,
+                    markers: vec![],
+                    comment: None,
+                    children: vec![],
+                })],
+            })
+        );
+
+        assert_eq!(
+            parse("@\nFoo_Test\n└── it should revert").unwrap_err(),
+            e(ErrorKind::DirectiveTagMissing, s(p(0, 1, 1), p(0, 1, 1)))
+        );
+    }
+
+    #[test]
+    fn type_declarations() {
+        let Ast::Root(root) = parse(
+            "@enum Status { Active, Paused }\nFoo_Test\n└── it should revert",
+        )
+        .unwrap() else {
+            unreachable!()
+        };
+        assert_eq!(
+            root.directives[0].type_declaration().as_deref(),
+            Some("enum Status { Active, Paused }")
+        );
+
+        for input in [
+            "@enum { Active, Paused }\nFoo_Test\n└── it should revert",
+            "@struct Position\nFoo_Test\n└── it should revert",
+            "@struct Position { uint256 amount; } x\nFoo_Test\n└── it reverts",
+        ] {
+            assert_eq!(
+                parse(input).unwrap_err().kind,
+                ErrorKind::TypeDeclarationInvalid
+            );
+        }
+    }
+
+    #[test]
+    fn root_markers() {
+        let Ast::Root(root) =
+            parse("Foo_Test [evm:cancun]\n└── it should revert").unwrap()
+        else {
+            unreachable!()
+        };
+
+        assert_eq!(root.contract_name, "Foo_Test");
+        assert_eq!(
+            root.markers,
+            vec![Marker {
+                name: String::from("evm"),
+                value: Some(String::from("cancun")),
+                span: s(p(9, 1, 10), p(20, 1, 21)),
+            }]
+        );
+    }
+
+    #[test]
+    fn comments() {
+        let input = indoc! {"
+            // The vault suite.
+            Foo_Test // Covers deposits.
+            ├── when the amount is zero // See issue #42.
+            │   // This comment is dropped.
+            │   └── it should revert // With `ZeroAmount`.
+            └── it should deposit
+        "};
+        let Ast::Root(root) = parse(input).unwrap() else { unreachable!() };
+        assert_eq!(root.comments, vec!["The vault suite.", "Covers deposits."]);
+
+        let Ast::Condition(condition) = &root.children[0] else {
+            unreachable!()
+        };
+        assert_eq!(condition.comment.as_deref(), Some("See issue #42."));
+        let Ast::Action(action) = &condition.children[0] else {
+            unreachable!()
+        };
+        assert_eq!(action.comment.as_deref(), Some("With `ZeroAmount`."));
+
+        let Ast::Action(action) = &root.children[1] else { unreachable!() };
+        assert_eq!(action.comment, None);
+    }
 }
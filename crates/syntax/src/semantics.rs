@@ -87,6 +87,23 @@ pub enum ErrorKind {
     /// Found no rules to emit.
     #[error("no rules where defined")]
     TreeEmpty,
+    /// Found an action description with no text.
+    #[error("found an empty action description")]
+    DescriptionEmpty,
+}
+
+impl ErrorKind {
+    /// Returns the stable code of this kind of error.
+    #[must_use]
+    pub const fn code(&self) -> &'static str {
+        match self {
+            ErrorKind::IdentifierDuplicated(_) => "B201",
+            ErrorKind::ConditionEmpty => "B202",
+            ErrorKind::NodeUnexpected => "B203",
+            ErrorKind::TreeEmpty => "B204",
+            ErrorKind::DescriptionEmpty => "B205",
+        }
+    }
 }
 
 /// A visitor that performs semantic analysis on an AST.
@@ -232,17 +249,31 @@ impl Visitor for SemanticAnalyzer<'_> {
 
     fn visit_action(
         &mut self,
-        _action: &ast::Action,
+        action: &ast::Action,
     ) -> result::Result<Self::Output, Self::Error> {
-        // We don't implement any semantic rules here for now.
+        for ast in &action.children {
+            match ast {
+                Ast::ActionDescription(description) => {
+                    self.visit_description(description)?;
+                }
+                node => {
+                    self.error(*node.span(), ErrorKind::NodeUnexpected);
+                }
+            }
+        }
+
         Ok(())
     }
 
     fn visit_description(
         &mut self,
-        _description: &ast::Description,
+        description: &ast::Description,
     ) -> result::Result<Self::Output, Self::Error> {
-        // We don't implement any semantic rules here for now.
+        // The parser never produces these, but hand-built ASTs may.
+        if description.text.trim().is_empty() {
+            self.error(description.span, ErrorKind::DescriptionEmpty);
+        }
+
         Ok(())
     }
 }
@@ -271,8 +302,14 @@ mod tests {
     fn unexpected_node() {
         let ast = ast::Ast::Root(ast::Root {
             contract_name: "Foo_Test".to_owned(),
+            directives: vec![],
+            markers: vec![],
+            comments: vec![],
             children: vec![ast::Ast::Root(ast::Root {
                 contract_name: "Foo_Test".to_owned(),
+                directives: vec![],
+                markers: vec![],
+                comments: vec![],
                 children: vec![],
                 span: Span::new(Position::new(0, 1, 1), Position::new(7, 1, 8)),
             })],
@@ -291,6 +328,89 @@ mod tests {
         );
     }
 
+    #[test]
+    fn validates_programmatic_asts() {
+        let span = Span::new(Position::new(0, 1, 1), Position::new(7, 1, 8));
+        let condition = |title: &str, children| {
+            ast::Ast::Condition(ast::Condition {
+                title: title.to_owned(),
+                status: None,
+                markers: vec![],
+                comment: None,
+                children,
+                span,
+            })
+        };
+        let action = ast::Ast::Action(ast::Action {
+            title: "it works".to_owned(),
+            status: None,
+            markers: vec![],
+            comment: None,
+            children: vec![],
+            span,
+        });
+        let root = |children| {
+            ast::Ast::Root(ast::Root {
+                contract_name: "Foo_Test".to_owned(),
+                directives: vec![],
+                markers: vec![],
+                comments: vec![],
+                children,
+                span,
+            })
+        };
+
+        assert!(root(vec![condition("when a", vec![action])])
+            .validate()
+            .is_ok());
+        assert_eq!(
+            root(vec![condition("when a", vec![])]).validate().unwrap_err().0,
+            vec![semantics::Error {
+                kind: ConditionEmpty,
+                text: String::new(),
+                span,
+            }]
+        );
+    }
+
+    #[test]
+    fn empty_description() {
+        let span = Span::new(Position::new(0, 1, 1), Position::new(7, 1, 8));
+        let description = |text: &str| {
+            ast::Ast::ActionDescription(ast::Description {
+                text: text.to_owned(),
+                span,
+            })
+        };
+        let root = |children| {
+            ast::Ast::Root(ast::Root {
+                contract_name: "Foo_Test".to_owned(),
+                directives: vec![],
+                markers: vec![],
+                comments: vec![],
+                children: vec![ast::Ast::Action(ast::Action {
+                    title: "it works".to_owned(),
+                    status: None,
+                    markers: vec![],
+                    comment: None,
+                    children,
+                    span,
+                })],
+                span,
+            })
+        };
+
+        assert!(root(vec![description("It does things.")]).validate().is_ok());
+        assert_eq!(
+            root(vec![description("  ")]).validate().unwrap_err().0,
+            vec![semantics::Error {
+                kind: DescriptionEmpty,
+                text: String::new(),
+                span,
+            }]
+        );
+    }
+
     #[test]
     fn duplicated_condition() {
         assert_eq!(
@@ -6,7 +6,9 @@ pub(crate) const TREES_SEPARATOR: &str = "\n\n";
 /// newlines.
 pub(crate) fn split_trees(text: &str) -> Box<dyn Iterator<Item = &str> + '_> {
     if text.trim().is_empty() {
-        return Box::new(std::iter::once(""));
+        // Keep the empty tree a subslice of `text`, see
+        // `split_trees_with_lines`.
+        return Box::new(std::iter::once(&text[..0]));
     }
 
     let trees = text.split(TREES_SEPARATOR).map(str::trim);
@@ -16,6 +18,18 @@ pub(crate) fn split_trees(text: &str) -> Box<dyn Iterator<Item = &str> + '_> {
     Box::new(no_isolated_comments)
 }
 
+/// Splits the input text into distinct trees like [`split_trees`], pairing
+/// each tree with the line it starts at.
+pub(crate) fn split_trees_with_lines(
+    text: &str,
+) -> impl Iterator<Item = (usize, &str)> {
+    split_trees(text).map(move |tree| {
+        // Every tree is a subslice of `text`, so this is its offset.
+        let offset = tree.as_ptr() as usize - text.as_ptr() as usize;
+        (text[..offset].matches('\n').count() + 1, tree)
+    })
+}
+
 /// Return whether the given string only contains lines starting with `//`.
 fn not_only_comments(tree: &&str) -> bool {
     !tree.lines().all(|l| l.trim().starts_with("//"))
@@ -23,7 +37,7 @@ fn not_only_comments(tree: &&str) -> bool {
 
 #[cfg(test)]
 mod tests {
-    use super::split_trees;
+    use super::{split_trees, split_trees_with_lines};
 
     #[test]
     fn splits_trees() {
@@ -57,4 +71,21 @@ mod tests {
             assert_eq!(results, expected, "Failed on input: {}", input);
         }
     }
+
+    #[test]
+    fn splits_trees_with_lines() {
+        let text = "\n// A comment.\nFoo_Test\n└── it should revert\n\n\nFoo_Test2\n└── it should revert\n";
+        let trees: Vec<_> = split_trees_with_lines(text).collect();
+        assert_eq!(
+            trees,
+            vec![
+                (2, "// A comment.\nFoo_Test\n└── it should revert"),
+                (7, "Foo_Test2\n└── it should revert"),
+            ]
+        );
+        assert_eq!(
+            split_trees_with_lines(" \n").collect::<Vec<_>>(),
+            vec![(1, "")]
+        );
+    }
 }
@@ -1,16 +1,23 @@
 //! Defines a scanner for bulloak trees that produces a token stream.
 
-use std::{borrow::Borrow, cell::Cell, fmt, result};
+use std::{borrow::Borrow, cell::Cell, collections::BTreeMap, fmt, result};
 
 use thiserror::Error;
 
 use crate::{
     error::FrontendError,
     span::{Position, Span},
+    utils::upper_first_letter,
 };
 
 type Result<T> = result::Result<T, Error>;
 
+/// Custom words recognized as keywords, mapped to the keyword they stand
+/// for, e.g. `if` to `when`.
+///
+/// Words are matched ignoring case, so they are expected to be lowercase.
+pub type KeywordAliases = BTreeMap<String, String>;
+
 /// An error that occurred while tokenizing a .tree string into a sequence of
 /// tokens.
 #[derive(Error, Clone, Debug, Eq, PartialEq)]
@@ -57,13 +64,23 @@ pub enum ErrorKind {
     IdentifierCharInvalid(char),
 }
 
+impl ErrorKind {
+    /// Returns the stable code of this kind of error.
+    #[must_use]
+    pub const fn code(&self) -> &'static str {
+        match self {
+            ErrorKind::IdentifierCharInvalid(_) => "B001",
+        }
+    }
+}
+
 /// `Token` represents a single unit of meaning in a .tree.
 ///
 /// A token has a kind, a span, and a lexeme. The kind is
 /// the type of the token, the span is the range in which a
 /// token appears in the original text, and the lexeme is the
 /// text that the token represents.
-#[derive(PartialEq, Eq)]
+#[derive(Clone, PartialEq, Eq)]
 pub struct Token {
     /// The type of the token.
     pub kind: TokenKind,
@@ -81,7 +98,11 @@ impl Token {
             TokenKind::Word
             | TokenKind::When
             | TokenKind::Given
-            | TokenKind::It => false,
+            | TokenKind::It
+            | TokenKind::Marker
+            | TokenKind::Status
+            | TokenKind::Directive
+            | TokenKind::Comment => false,
         }
     }
 }
@@ -93,7 +114,7 @@ impl fmt::Debug for Token {
 }
 
 /// The type of a token.
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum TokenKind {
     /// A token representing the `├` character.
     Tee,
@@ -110,8 +131,33 @@ pub enum TokenKind {
     Given,
     /// A token representing an `it` keyword.
     It,
+    /// A token representing a marker attached to a title.
+    ///
+    /// Markers have the form `[name]` or `[name:value]`, e.g. `[assembly]`.
+    Marker,
+    /// A token representing a status symbol, e.g. `✅`.
+    ///
+    /// See [`STATUS_SYMBOLS`] for the list of recognized symbols.
+    Status,
+    /// A token representing a header directive, e.g. `@title My Test Suite`.
+    ///
+    /// Directives can only appear before the root of a tree and span the
+    /// rest of their line.
+    Directive,
+    /// A token representing a comment, e.g. `// see issue #42`.
+    ///
+    /// Comments span the rest of their line. Their lexeme is the text after
+    /// the `//`, without surrounding whitespace.
+    Comment,
 }
 
+/// The status symbols that may prefix the title of a condition or an action.
+///
+/// Some teams use these to track the progress of a spec. They are stored
+/// separately from the title, so they don't end up in the generated names.
+pub const STATUS_SYMBOLS: &[&str] =
+    &["✅", "❌", "✔", "✔️", "✖", "✖️", "⚠", "⚠️", "🚧", "⏳"];
+
 /// A tokenizer for .tree files.
 ///
 /// This struct represents the state of the tokenizer. It is not
@@ -127,6 +173,8 @@ pub(crate) struct Tokenizer {
     /// a character that is not a valid identifier character.
     /// This is to prevent malformed names when emitting identifiers.
     identifier_mode: Cell<bool>,
+    /// Custom words to recognize as keywords.
+    keyword_aliases: KeywordAliases,
 }
 
 impl Default for Tokenizer {
@@ -142,9 +190,20 @@ impl Tokenizer {
         Self {
             pos: Cell::new(Position::new(0, 1, 1)),
             identifier_mode: Cell::new(false),
+            keyword_aliases: KeywordAliases::new(),
         }
     }
 
+    /// Recognize the words in `keyword_aliases` as the keywords they map to.
+    #[must_use]
+    pub(crate) fn with_keyword_aliases(
+        mut self,
+        keyword_aliases: KeywordAliases,
+    ) -> Self {
+        self.keyword_aliases = keyword_aliases;
+        self
+    }
+
     /// Tokenize the input .tree text.
     ///
     /// `tokenize` is the entry point of the Tokenizer.
@@ -241,6 +300,21 @@ impl<'s, T: Borrow<Tokenizer>> TokenizerI<'s, T> {
         self.text()[self.offset() + self.char().len_utf8()..].chars().next()
     }
 
+    /// Whether everything before the current position in its line is part of
+    /// the indentation of a tree, i.e. whitespace and vertical lines.
+    fn is_indentation(&self) -> bool {
+        let line_start =
+            self.text()[..self.offset()].rfind('\n').map_or(0, |idx| idx + 1);
+        self.text()[line_start..self.offset()]
+            .chars()
+            .all(|c| matches!(c, ' ' | '\t' | '│' | '|'))
+    }
+
+    /// Whether the tokenizer is at an ASCII branch, i.e. `|--` or `` `-- ``.
+    fn is_ascii_branch(&self) -> bool {
+        self.text()[self.offset() + self.char().len_utf8()..].starts_with("--")
+    }
+
     /// Enters identifier mode.
     fn enter_identifier_mode(&self) {
         self.tokenizer().identifier_mode.set(true);
@@ -309,15 +383,49 @@ impl<'s, T: Borrow<Tokenizer>> TokenizerI<'s, T> {
                     span: self.span(),
                     lexeme: "└".to_owned(),
                 }),
+                // ASCII trees use `|--` and `` `-- `` as branches, and `|` as
+                // the vertical line. They are tokenized like their Unicode
+                // equivalents, so that both kinds of connectors can be mixed.
+                '|' | '`'
+                    if self.is_indentation() && self.is_ascii_branch() =>
+                {
+                    let (kind, lexeme) = if self.char() == '|' {
+                        (TokenKind::Tee, "├")
+                    } else {
+                        (TokenKind::Corner, "└")
+                    };
+                    tokens.push(Token {
+                        kind,
+                        span: self.span(),
+                        lexeme: lexeme.to_owned(),
+                    });
+                    while self.peek() == Some('-') {
+                        self.scan();
+                    }
+                }
+                '|' if self.is_indentation()
+                    && self.peek().is_none_or(char::is_whitespace) => {}
                 // Comments start with `//`.
                 '/' if self.peek().is_some_and(|c| c == '/') => {
                     self.exit_mode();
-                    self.scan_comments();
+                    tokens.extend(self.scan_comment());
+                }
+                // Directives can only appear before the root.
+                '@' if tokens.iter().all(|t| {
+                    matches!(t.kind, TokenKind::Directive | TokenKind::Comment)
+                }) =>
+                {
+                    tokens.push(self.scan_directive());
                 }
                 _ => {
                     let token = self.scan_word()?;
-                    let last_is_branch =
-                        tokens.last().is_some_and(Token::is_branch);
+                    // A status symbol may sit between the branch and the
+                    // keyword.
+                    let last_is_branch = tokens
+                        .iter()
+                        .rev()
+                        .find(|t| t.kind != TokenKind::Status)
+                        .is_some_and(Token::is_branch);
                     if last_is_branch
                         && (token.kind == TokenKind::When
                             || token.kind == TokenKind::Given)
@@ -336,13 +444,39 @@ impl<'s, T: Borrow<Tokenizer>> TokenizerI<'s, T> {
         Ok(tokens)
     }
 
-    /// Discards all characters until the end of the line.
-    fn scan_comments(&self) {
-        loop {
-            match self.peek() {
-                Some('\n') | None => break,
-                Some(_) => self.scan(),
-            };
+    /// Consumes a comment, i.e. everything until the end of the line.
+    ///
+    /// Returns `None` if the comment has no text.
+    fn scan_comment(&self) -> Option<Token> {
+        let span_start = self.pos();
+        let rest = &self.text()[self.offset()..];
+        let text = rest.lines().next().unwrap_or_default();
+        while self.peek().is_some_and(|c| c != '\n') {
+            self.scan();
+        }
+
+        let lexeme = text.trim_start_matches('/').trim();
+        (!lexeme.is_empty()).then(|| Token {
+            kind: TokenKind::Comment,
+            span: self.span().with_start(span_start),
+            lexeme: lexeme.to_owned(),
+        })
+    }
+
+    /// Consumes a header directive, i.e. everything until the end of the
+    /// line.
+    fn scan_directive(&self) -> Token {
+        let span_start = self.pos();
+        let rest = &self.text()[self.offset()..];
+        let lexeme = rest.lines().next().unwrap_or_default().trim_end();
+        for _ in 1..lexeme.chars().count() {
+            self.scan();
+        }
+
+        Token {
+            kind: TokenKind::Directive,
+            span: self.span().with_start(span_start),
+            lexeme: lexeme.to_owned(),
         }
     }
 
@@ -352,6 +486,17 @@ impl<'s, T: Borrow<Tokenizer>> TokenizerI<'s, T> {
     /// If the word is a keyword, then the appropriate token is returned.
     /// Otherwise, a `Word` token is returned.
     fn scan_word(&self) -> Result<Token> {
+        if let Some(marker) = self.scan_marker() {
+            return Ok(marker);
+        }
+        if self.is_identifier_mode() {
+            if let Some(values) = self.scan_value_set() {
+                return Ok(values);
+            }
+        } else if let Some(status) = self.scan_status() {
+            return Ok(status);
+        }
+
         let mut lexeme = String::new();
         let span_start = self.pos();
 
@@ -370,12 +515,25 @@ impl<'s, T: Borrow<Tokenizer>> TokenizerI<'s, T> {
                 || self.peek().is_some_and(char::is_whitespace)
             {
                 lexeme.push(self.char());
-                let kind = match lexeme.to_lowercase().as_str() {
+                let word = lexeme.to_lowercase();
+                let alias = self.tokenizer().keyword_aliases.get(&word);
+                let kind = match alias.unwrap_or(&word).as_str() {
                     "when" => TokenKind::When,
                     "it" => TokenKind::It,
                     "given" => TokenKind::Given,
                     _ => TokenKind::Word,
                 };
+                // Aliases read as the keyword they stand for, so that titles
+                // generate the same names as with the keyword.
+                if let Some(keyword) = alias.filter(|_| kind != TokenKind::Word)
+                {
+                    let is_capitalized = lexeme.starts_with(char::is_uppercase);
+                    lexeme = if is_capitalized {
+                        upper_first_letter(keyword)
+                    } else {
+                        keyword.clone()
+                    };
+                }
 
                 return Ok(Token {
                     kind,
@@ -388,6 +546,111 @@ impl<'s, T: Borrow<Tokenizer>> TokenizerI<'s, T> {
             self.scan();
         }
     }
+
+    /// Consumes a marker from the input, if the word at the current position
+    /// is one.
+    ///
+    /// Markers are recognized regardless of the tokenizer mode, which means
+    /// they can be attached to conditions even though their characters are
+    /// not valid identifier characters.
+    fn scan_marker(&self) -> Option<Token> {
+        self.scan_whole_word(TokenKind::Marker, is_marker)
+    }
+
+    /// Scans a value set, e.g. `{Active, Paused}`, as a single word.
+    ///
+    /// Value sets may contain spaces, but they can't span several lines and
+    /// their values must be valid identifiers. Returns `None` without
+    /// consuming any character if there is no value set at the current
+    /// position.
+    fn scan_value_set(&self) -> Option<Token> {
+        let rest = &self.text()[self.offset()..];
+        let line = rest.lines().next().unwrap_or_default();
+        let end = line.find('}')? + 1;
+        let lexeme = &line[..end];
+        let inner = lexeme.strip_prefix('{')?.strip_suffix('}')?;
+        let is_valid = inner
+            .chars()
+            .all(|c| is_valid_identifier_char(c) || c == ',' || c == ' ');
+        let is_word_end = line[end..].is_empty()
+            || line[end..].starts_with(char::is_whitespace);
+        if !is_valid || !is_word_end {
+            return None;
+        }
+
+        let span_start = self.pos();
+        for _ in 1..lexeme.chars().count() {
+            self.scan();
+        }
+
+        Some(Token {
+            kind: TokenKind::Word,
+            span: self.span().with_start(span_start),
+            lexeme: lexeme.to_owned(),
+        })
+    }
+
+    /// Scans a status symbol, e.g. `✅`.
+    ///
+    /// Returns `None` without consuming any character if the word at the
+    /// current position is not a recognized status symbol.
+    fn scan_status(&self) -> Option<Token> {
+        self.scan_whole_word(TokenKind::Status, |word| {
+            STATUS_SYMBOLS.contains(&word)
+        })
+    }
+
+    /// Scans the word at the current position as a token of the given kind
+    /// if it satisfies `predicate`.
+    ///
+    /// Returns `None` without consuming any character otherwise.
+    fn scan_whole_word(
+        &self,
+        kind: TokenKind,
+        predicate: impl Fn(&str) -> bool,
+    ) -> Option<Token> {
+        let rest = &self.text()[self.offset()..];
+        let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+        let lexeme = &rest[..end];
+        if !predicate(lexeme) {
+            return None;
+        }
+
+        let span_start = self.pos();
+        for _ in 1..lexeme.chars().count() {
+            self.scan();
+        }
+
+        Some(Token {
+            kind,
+            span: self.span().with_start(span_start),
+            lexeme: lexeme.to_owned(),
+        })
+    }
+}
+
+/// Checks whether a word is a marker, i.e. `[name]` or `[name:value]`.
+///
+/// A marker name starts with an ASCII letter and may only contain
+/// alphanumeric characters, `_` and `-`.
+fn is_marker(word: &str) -> bool {
+    let Some(inner) = word.strip_prefix('[').and_then(|w| w.strip_suffix(']'))
+    else {
+        return false;
+    };
+
+    let (name, value) = match inner.split_once(':') {
+        Some((name, value)) => (name, Some(value)),
+        None => (inner, None),
+    };
+    let is_valid_name = name.starts_with(|c: char| c.is_ascii_alphabetic())
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+    let is_valid_value =
+        value.is_none_or(|v| !v.is_empty() && !v.contains(['[', ']']));
+
+    is_valid_name && is_valid_value
 }
 
 /// Checks whether a character can appear in an identifier.
@@ -477,6 +740,7 @@ mod tests {
                 t(TokenKind::Word,   "something", s(p(24, 2, 10), p(32, 2, 18))),
                 t(TokenKind::Word,   "bad",       s(p(34, 2, 20), p(36, 2, 22))),
                 t(TokenKind::Word,   "happens",   s(p(38, 2, 24), p(44, 2, 30))),
+                t(TokenKind::Comment, "some comments", s(p(46, 2, 32), p(62, 2, 48))),
                 t(TokenKind::Corner, "└",         s(p(67, 3, 4),  p(67, 3, 4))),
                 t(TokenKind::It,     "it",        s(p(77, 3, 8),  p(78, 3, 9))),
                 t(TokenKind::Word,   "should",    s(p(80, 3, 11), p(85, 3, 16))),
@@ -501,6 +765,7 @@ mod tests {
                 t(TokenKind::Word,   "something", s(p(24, 2, 10), p(32, 2, 18))),
                 t(TokenKind::Word,   "bad",       s(p(34, 2, 20), p(36, 2, 22))),
                 t(TokenKind::Word,   "happens",   s(p(38, 2, 24), p(44, 2, 30))),
+                t(TokenKind::Comment, "some comments", s(p(49, 3, 4), p(64, 3, 19))),
                 t(TokenKind::Corner, "└",         s(p(69, 4, 4),  p(69, 4, 4))),
                 t(TokenKind::It,     "it",        s(p(79, 4, 8),  p(80, 4, 9))),
                 t(TokenKind::Word,   "should",    s(p(82, 4, 11), p(87, 4, 16))),
@@ -537,6 +802,77 @@ mod tests {
         };
     }
 
+    #[test]
+    fn ascii_connectors() {
+        let unicode = indoc! {"
+            Foo_Test
+            ├── when a
+            │   └── it works
+            └── given b
+                └── it works too
+        "};
+        // `|` and `|--` mixed with their Unicode equivalents.
+        let ascii = indoc! {"
+            Foo_Test
+            |-- when a
+            │   `-- it works
+            └── given b
+                `-- it works too
+        "};
+
+        let summary = |text: &str| -> Vec<_> {
+            tokenize(text)
+                .unwrap()
+                .into_iter()
+                .map(|token| {
+                    let start = token.span.start;
+                    (token.kind, token.lexeme, start.line, start.column)
+                })
+                .collect()
+        };
+        assert_eq!(summary(ascii), summary(unicode));
+
+        let ascii = "Foo_Test\n|-- when a\n|   `-- it works\n";
+        let unicode = "Foo_Test\n├── when a\n│   └── it works\n";
+        assert_eq!(summary(ascii), summary(unicode));
+
+        // Pipes and backticks elsewhere are still part of the text.
+        let tokens = summary("Foo_Test\n└── it returns `a | b`\n");
+        assert!(tokens.iter().any(|(_, lexeme, _, _)| lexeme == "|"));
+        assert!(tokens.iter().any(|(_, lexeme, _, _)| lexeme == "`a"));
+    }
+
+    #[test]
+    fn keyword_aliases() {
+        let aliases = [("if", "when"), ("scenario", "given")]
+            .into_iter()
+            .map(|(word, keyword)| (word.to_owned(), keyword.to_owned()))
+            .collect();
+        let mut tokenizer = Tokenizer::new().with_keyword_aliases(aliases);
+        let tokens = tokenizer
+            .tokenize("Foo_Test\n└── Scenario a if b\n   └── it works")
+            .unwrap();
+
+        let tokens: Vec<_> = tokens
+            .iter()
+            .map(|token| (&token.kind, token.lexeme.as_str()))
+            .collect();
+        assert_eq!(
+            tokens,
+            vec![
+                (&TokenKind::Word, "Foo_Test"),
+                (&TokenKind::Corner, "└"),
+                (&TokenKind::Given, "Given"),
+                (&TokenKind::Word, "a"),
+                (&TokenKind::When, "when"),
+                (&TokenKind::Word, "b"),
+                (&TokenKind::Corner, "└"),
+                (&TokenKind::It, "it"),
+                (&TokenKind::Word, "works"),
+            ]
+        );
+    }
+
     #[test]
     fn only_filename_and_newline() {
         let simple_name = String::from("foo\n");
@@ -779,4 +1115,117 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn markers() {
+        let file_contents = String::from(
+            "Foo_Test\n└── when x [gas]\n   └── it [evm:cancun] does [x",
+        );
+
+        assert_eq!(
+            tokenize(&file_contents).unwrap(),
+            vec![
+                t(TokenKind::Word, "Foo_Test", s(p(0, 1, 1), p(7, 1, 8))),
+                t(TokenKind::Corner, "└", s(p(9, 2, 1), p(9, 2, 1))),
+                t(TokenKind::When, "when", s(p(19, 2, 5), p(22, 2, 8))),
+                t(TokenKind::Word, "x", s(p(24, 2, 10), p(24, 2, 10))),
+                t(TokenKind::Marker, "[gas]", s(p(26, 2, 12), p(30, 2, 16))),
+                t(TokenKind::Corner, "└", s(p(35, 3, 4), p(35, 3, 4))),
+                t(TokenKind::It, "it", s(p(45, 3, 8), p(46, 3, 9))),
+                t(
+                    TokenKind::Marker,
+                    "[evm:cancun]",
+                    s(p(48, 3, 11), p(59, 3, 22))
+                ),
+                t(TokenKind::Word, "does", s(p(61, 3, 24), p(64, 3, 27))),
+                t(TokenKind::Word, "[x", s(p(66, 3, 29), p(67, 3, 30))),
+            ]
+        );
+
+        // Malformed markers in conditions are still invalid identifiers.
+        assert_eq!(
+            tokenize("Foo_Test\n└── when x [1]").unwrap_err(),
+            e(IdentifierCharInvalid('['), Span::splat(p(26, 2, 12)))
+        );
+    }
+
+    #[test]
+    fn value_sets() {
+        let file_contents =
+            String::from("Foo_Test\n└── given x in {A, B}\n   └── it works");
+
+        assert_eq!(
+            tokenize(&file_contents).unwrap(),
+            vec![
+                t(TokenKind::Word, "Foo_Test", s(p(0, 1, 1), p(7, 1, 8))),
+                t(TokenKind::Corner, "└", s(p(9, 2, 1), p(9, 2, 1))),
+                t(TokenKind::Given, "given", s(p(19, 2, 5), p(23, 2, 9))),
+                t(TokenKind::Word, "x", s(p(25, 2, 11), p(25, 2, 11))),
+                t(TokenKind::Word, "in", s(p(27, 2, 13), p(28, 2, 14))),
+                t(TokenKind::Word, "{A, B}", s(p(30, 2, 16), p(35, 2, 21))),
+                t(TokenKind::Corner, "└", s(p(40, 3, 4), p(40, 3, 4))),
+                t(TokenKind::It, "it", s(p(50, 3, 8), p(51, 3, 9))),
+                t(TokenKind::Word, "works", s(p(53, 3, 11), p(57, 3, 15))),
+            ]
+        );
+
+        // Value sets must be closed on the same line.
+        assert_eq!(
+            tokenize("Foo_Test\n└── given x in {A, B").unwrap_err(),
+            e(IdentifierCharInvalid('{'), Span::splat(p(30, 2, 16)))
+        );
+    }
+
+    #[test]
+    fn statuses() {
+        let file_contents =
+            String::from("Foo_Test\n└── ✅ when x\n   └── ❌ it ✅");
+
+        assert_eq!(
+            tokenize(&file_contents).unwrap(),
+            vec![
+                t(TokenKind::Word, "Foo_Test", s(p(0, 1, 1), p(7, 1, 8))),
+                t(TokenKind::Corner, "└", s(p(9, 2, 1), p(9, 2, 1))),
+                t(TokenKind::Status, "✅", s(p(19, 2, 5), p(19, 2, 5))),
+                t(TokenKind::When, "when", s(p(23, 2, 7), p(26, 2, 10))),
+                t(TokenKind::Word, "x", s(p(28, 2, 12), p(28, 2, 12))),
+                t(TokenKind::Corner, "└", s(p(33, 3, 4), p(33, 3, 4))),
+                t(TokenKind::Status, "❌", s(p(43, 3, 8), p(43, 3, 8))),
+                t(TokenKind::It, "it", s(p(47, 3, 10), p(48, 3, 11))),
+                t(TokenKind::Status, "✅", s(p(50, 3, 13), p(50, 3, 13))),
+            ]
+        );
+
+        // Status symbols are not valid identifier characters.
+        assert_eq!(
+            tokenize("Foo_Test\n└── when ✅").unwrap_err(),
+            e(IdentifierCharInvalid('✅'), Span::splat(p(24, 2, 10)))
+        );
+    }
+
+    #[test]
+    fn directives() {
+        let file_contents =
+            String::from("@title My Suite\n@dev x @y\nFoo_Test\n└── it @z");
+
+        assert_eq!(
+            tokenize(&file_contents).unwrap(),
+            vec![
+                t(
+                    TokenKind::Directive,
+                    "@title My Suite",
+                    s(p(0, 1, 1), p(14, 1, 15))
+                ),
+                t(
+                    TokenKind::Directive,
+                    "@dev x @y",
+                    s(p(16, 2, 1), p(24, 2, 9))
+                ),
+                t(TokenKind::Word, "Foo_Test", s(p(26, 3, 1), p(33, 3, 8))),
+                t(TokenKind::Corner, "└", s(p(35, 4, 1), p(35, 4, 1))),
+                t(TokenKind::It, "it", s(p(45, 4, 5), p(46, 4, 6))),
+                t(TokenKind::Word, "@z", s(p(48, 4, 8), p(49, 4, 9))),
+            ]
+        );
+    }
 }
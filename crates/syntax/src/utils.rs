@@ -7,6 +7,11 @@ use unicode_xid::UnicodeXID;
 /// This function takes a string slice and returns a new `String` with the first
 /// letter capitalized. If the string is empty, it returns an empty string.
 ///
+/// Leading characters that are not alphanumeric, such as quotes or
+/// backticks, are skipped, since they are later removed by [`sanitize`].
+/// Words that start with a digit are returned as is, which keeps hex prefixes
+/// like `0x` lowercase.
+///
 /// # Arguments
 ///
 /// * `s` - A string slice that holds the input string
@@ -19,14 +24,20 @@ use unicode_xid::UnicodeXID;
 ///
 /// ```
 /// # use bulloak_syntax::utils::upper_first_letter;
-/// let result = upper_first_letter("hello");
-/// assert_eq!(result, "Hello");
+/// assert_eq!(upper_first_letter("hello"), "Hello");
+/// assert_eq!(upper_first_letter("`amount`"), "`Amount`");
+/// assert_eq!(upper_first_letter("0xdead"), "0xdead");
 /// ```
 pub fn upper_first_letter(s: &str) -> String {
-    let mut c = s.chars();
+    let Some(idx) = s.find(char::is_alphanumeric) else {
+        return s.to_owned();
+    };
+
+    let (prefix, rest) = s.split_at(idx);
+    let mut c = rest.chars();
     c.next()
         .map(char::to_uppercase)
-        .map(|first| first.to_string() + c.as_str())
+        .map(|first| prefix.to_owned() + &first.to_string() + c.as_str())
         .unwrap_or_default()
 }
 
@@ -91,6 +102,10 @@ pub fn sanitize(identifier: &str) -> String {
 /// in the title and removing the spaces. For example, the sentence
 /// `when only owner` is converted to the `WhenOnlyOwner` string.
 ///
+/// Words are capitalized with [`upper_first_letter`], so the rest of a word
+/// is kept as is. This means that `ERC-20` stays `ERC-20`, which [`sanitize`]
+/// later turns into `ERC_20`, and `v2` becomes `V2`.
+///
 /// # Arguments
 ///
 /// * `sentence` - A string slice that holds the input sentence
@@ -165,7 +180,7 @@ pub fn pluralize<'a>(
 
 #[cfg(test)]
 mod tests {
-    use super::to_pascal_case;
+    use super::{sanitize, to_pascal_case};
 
     #[test]
     fn to_modifier() {
@@ -173,4 +188,15 @@ mod tests {
         assert_eq!(to_pascal_case("when"), "When");
         assert_eq!(to_pascal_case(""), "");
     }
+
+    #[test]
+    fn to_pascal_case_alphanumeric_words() {
+        let name = |title| sanitize(&to_pascal_case(title));
+        assert_eq!(name("when the token is ERC-20"), "WhenTheTokenIsERC_20");
+        assert_eq!(name("when the token is erc20"), "WhenTheTokenIsErc20");
+        assert_eq!(name("when using v2"), "WhenUsingV2");
+        assert_eq!(name("when the address is 0x0"), "WhenTheAddressIs0x0");
+        assert_eq!(name("when `amount` is 2x"), "WhenAmountIs2x");
+        assert_eq!(name("when 'x' is (v2)"), "WhenXIsV2");
+    }
 }